@@ -1,4 +1,5 @@
 use clap::{Parser, Subcommand};
+use serde::{Deserialize, Serialize};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, subcommand_negates_reqs = true)]
@@ -21,6 +22,17 @@ pub enum Command {
         #[command(subcommand)]
         command: ModelCommand,
     },
+    /// 沙箱镜像管理（构建/状态/清理）
+    Sandbox {
+        #[command(subcommand)]
+        command: SandboxCommand,
+    },
+    /// 用量与费用报表（按模型、分类、日期汇总已保存的对话）
+    Usage {
+        /// 只统计最近 N 天
+        #[arg(long)]
+        days: Option<u32>,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -29,6 +41,23 @@ pub enum ModelCommand {
     Add,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum SandboxCommand {
+    /// 构建沙箱镜像（默认使用项目根目录的 Dockerfile，并追加配置中的额外 pip/apt 包）
+    Build {
+        /// 自定义 Dockerfile 路径（覆盖配置中的 sandbox.dockerfile）
+        #[arg(long)]
+        dockerfile: Option<String>,
+        /// 不使用构建缓存
+        #[arg(long, default_value_t = false)]
+        no_cache: bool,
+    },
+    /// 查看沙箱镜像与容器状态
+    Status,
+    /// 清理已退出会话遗留的容器与旧版本镜像
+    Prune,
+}
+
 #[derive(clap::Args, Debug, Clone)]
 pub struct Args {
     /// 模型 key 或模型名称（来自 config.json 的 models）
@@ -54,7 +83,7 @@ pub struct Args {
     #[arg(long, default_value = "https://api.deepseek.com")]
     pub base_url: String,
 
-    /// 显示 reasoning_content（如果返回）
+    /// 默认展开思考过程（运行中可用 /reasoning 切换）
     #[arg(long, default_value_t = false)]
     pub show_reasoning: bool,
 
@@ -73,7 +102,7 @@ pub struct Args {
     /// 工具开关表达式（逗号分隔，前缀 - 表示禁用）
     ///
     /// 默认：全部关闭（不向模型暴露任何 tools）。
-    /// 示例：--enable "read_file,read_code" 或 --enable "code_exec,-modify_file" 或 --enable "ask_questions"
+    /// 示例：--enable "read_file,read_code" 或 --enable "code_exec,-modify_file" 或 --enable "ask_questions,delegate_task"
     #[arg(long, allow_hyphen_values = true)]
     pub enable: Option<String>,
 
@@ -81,6 +110,14 @@ pub struct Args {
     #[arg(long)]
     pub log_requests: Option<String>,
 
+    /// 录制模型请求与完整流式事件到 cassette 文件（JSON Lines，每行一次请求）
+    #[arg(long, conflicts_with = "replay_cassette")]
+    pub record_cassette: Option<String>,
+
+    /// 从 cassette 文件回放模型响应，不访问网络（工具调用仍在本地执行）
+    #[arg(long)]
+    pub replay_cassette: Option<String>,
+
     /// 填充历史消息用于性能手工测试
     #[arg(long, default_value_t = false)]
     pub perf: bool,
@@ -103,6 +140,22 @@ pub struct Args {
     #[arg(long, default_value_t = false)]
     pub read_only: bool,
 
+    /// Python 常驻内核模式：同一对话内的 code_exec 共享变量与导入（容器内常驻进程）
+    #[arg(long, default_value_t = false)]
+    pub python_kernel: bool,
+
+    /// 隔离工作区模式：容器挂载工作区副本（仅复制未被 gitignore 的文件），改动需通过 /changes 审阅后同步回宿主机
+    #[arg(long, default_value_t = false)]
+    pub isolated_workspace: bool,
+
+    /// 隔离工作区副本的总大小上限（MB）
+    #[arg(long, default_value_t = 512)]
+    pub isolated_workspace_max_mb: u64,
+
+    /// 自动压缩：上下文接近模型窗口上限时，自动将较早的对话压缩为摘要（同 /compact）
+    #[arg(long, default_value_t = false)]
+    pub auto_compact: bool,
+
     /// 等待 gdb attach 后再继续执行（用于调试）
     #[arg(long, default_value_t = false)]
     pub wait_gdb: bool,
@@ -136,6 +189,10 @@ impl Args {
         self.resolve_enabled().ask_questions
     }
 
+    pub fn delegate_task_enabled(&self) -> bool {
+        self.resolve_enabled().delegate_task
+    }
+
     pub fn yolo_enabled(&self) -> bool {
         self.yolo
     }
@@ -144,46 +201,92 @@ impl Args {
         self.read_only
     }
 
+    pub fn python_kernel_enabled(&self) -> bool {
+        self.python_kernel
+    }
+
+    pub fn isolated_workspace_enabled(&self) -> bool {
+        self.isolated_workspace
+    }
+
+    /// `--enable` 解析出的工具开关，未叠加只读模式；作为各对话工具开关的默认值。
+    pub fn tool_flags(&self) -> ToolFlags {
+        self.resolve_enabled()
+    }
+
     fn resolve_enabled(&self) -> ToolFlags {
         let Some(expr) = self.enable.as_deref() else {
-            return default_tool_flags();
+            return ToolFlags::default();
         };
         resolve_enabled_from_expr(expr)
     }
 }
 
-#[derive(Clone, Copy)]
-struct ToolFlags {
-    web_search: bool,
-    code_exec: bool,
-    read_file: bool,
-    read_code: bool,
-    modify_file: bool,
-    ask_questions: bool,
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolFlags {
+    pub web_search: bool,
+    pub code_exec: bool,
+    pub read_file: bool,
+    pub read_code: bool,
+    pub modify_file: bool,
+    pub ask_questions: bool,
+    pub delegate_task: bool,
 }
 
-fn default_tool_flags() -> ToolFlags {
-    ToolFlags {
-        web_search: false,
-        code_exec: false,
-        read_file: false,
-        read_code: false,
-        modify_file: false,
-        ask_questions: false,
+impl ToolFlags {
+    /// 与 `--enable` 中的写法一致
+    pub const NAMES: [&'static str; 7] = [
+        "web_search",
+        "code_exec",
+        "read_file",
+        "read_code",
+        "modify_file",
+        "ask_questions",
+        "delegate_task",
+    ];
+
+    pub fn get(&self, name: &str) -> bool {
+        match name {
+            "web_search" => self.web_search,
+            "code_exec" => self.code_exec,
+            "read_file" => self.read_file,
+            "read_code" => self.read_code,
+            "modify_file" => self.modify_file,
+            "ask_questions" => self.ask_questions,
+            "delegate_task" => self.delegate_task,
+            _ => false,
+        }
+    }
+
+    /// 未知的工具名返回 false。
+    pub fn set(&mut self, name: &str, enable: bool) -> bool {
+        let slot = match name {
+            "web_search" => &mut self.web_search,
+            "code_exec" => &mut self.code_exec,
+            "read_file" => &mut self.read_file,
+            "read_code" => &mut self.read_code,
+            "modify_file" => &mut self.modify_file,
+            "ask_questions" => &mut self.ask_questions,
+            "delegate_task" => &mut self.delegate_task,
+            _ => return false,
+        };
+        *slot = enable;
+        true
     }
 }
 
 fn resolve_enabled_from_expr(expr: &str) -> ToolFlags {
-    let mut flags = default_tool_flags();
+    let mut flags = ToolFlags::default();
     for raw in expr.split(',') {
         if let Some((name, enable)) = parse_enable_item(raw) {
-            apply_tool_flag(&mut flags, name, enable);
+            flags.set(name, enable);
         }
     }
     flags
 }
 
-fn parse_enable_item(raw: &str) -> Option<(&str, bool)> {
+pub(crate) fn parse_enable_item(raw: &str) -> Option<(&str, bool)> {
     let item = raw.trim();
     if item.is_empty() {
         return None;
@@ -194,15 +297,3 @@ fn parse_enable_item(raw: &str) -> Option<(&str, bool)> {
         Some((item, true))
     }
 }
-
-fn apply_tool_flag(flags: &mut ToolFlags, name: &str, enable: bool) {
-    match name {
-        "web_search" => flags.web_search = enable,
-        "code_exec" => flags.code_exec = enable,
-        "read_file" => flags.read_file = enable,
-        "read_code" => flags.read_code = enable,
-        "modify_file" => flags.modify_file = enable,
-        "ask_questions" => flags.ask_questions = enable,
-        _ => {}
-    }
-}
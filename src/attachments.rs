@@ -0,0 +1,271 @@
+//! 消息附件模块
+//!
+//! 图片与 `@路径` 引用的文件按内容摘要保存在对话目录旁的 attachments 目录中，
+//! 消息里只记录名称、类型与路径。文件保存的是发送时的快照，之后修改不会影响历史记录。
+
+use crate::conversation::conversations_dir;
+use crate::persist::content_hash;
+use crate::types::Attachment;
+use base64::Engine as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 单张图片的大小上限（各家接口的限制大多在 20MB 左右）。
+const MAX_IMAGE_BYTES: usize = 20 * 1024 * 1024;
+/// 单个引用文件的大小上限，超出部分截断。
+const MAX_FILE_BYTES: usize = 64 * 1024;
+/// 一条消息中引用文件的总大小上限。
+pub const MAX_MENTION_TOTAL_BYTES: usize = 256 * 1024;
+const TEXT_MIME: &str = "text/plain";
+
+pub fn attachments_dir() -> Result<PathBuf, String> {
+    conversations_dir()
+        .map(|dir| dir.join("attachments"))
+        .map_err(|e| format!("无法确定附件目录：{e}"))
+}
+
+/// 读取本地图片文件并复制到附件目录。
+pub fn attach_image_file(path: &str) -> Result<Attachment, String> {
+    let path = expand_home(path.trim());
+    let bytes = fs::read(&path).map_err(|e| format!("读取图片失败：{} ({e})", path.display()))?;
+    let mime = sniff_image_mime(&bytes)
+        .or_else(|| image_mime_for_path(&path))
+        .ok_or_else(|| {
+            format!(
+                "不支持的图片格式：{}（支持 png/jpeg/gif/webp）",
+                path.display()
+            )
+        })?;
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "image".to_string());
+    store_image(&bytes, mime, name)
+}
+
+pub fn store_image(bytes: &[u8], mime_type: &str, name: String) -> Result<Attachment, String> {
+    if bytes.len() > MAX_IMAGE_BYTES {
+        return Err(format!(
+            "图片过大：{}（上限 {}MB）",
+            name,
+            MAX_IMAGE_BYTES / 1024 / 1024
+        ));
+    }
+    let dir = attachments_dir()?;
+    fs::create_dir_all(&dir).map_err(|e| format!("创建附件目录失败：{e}"))?;
+    let file = dir.join(format!(
+        "{}.{}",
+        content_hash(bytes),
+        extension_for_mime(mime_type)
+    ));
+    if !file.exists() {
+        fs::write(&file, bytes).map_err(|e| format!("保存图片失败：{e}"))?;
+    }
+    Ok(Attachment {
+        name,
+        mime_type: mime_type.to_string(),
+        path: file.to_string_lossy().to_string(),
+    })
+}
+
+/// 读取工作区内的文本文件并保存快照；`rel` 为相对工作区的路径。
+pub fn attach_text_file(root: &Path, rel: &str) -> Result<Attachment, String> {
+    let path = root
+        .join(rel)
+        .canonicalize()
+        .map_err(|e| format!("读取文件失败：{rel} ({e})"))?;
+    if !path.starts_with(root) {
+        return Err(format!("只能引用工作区内的文件：{rel}"));
+    }
+    let bytes = fs::read(&path).map_err(|e| format!("读取文件失败：{rel} ({e})"))?;
+    if bytes.contains(&0) {
+        return Err(format!("无法引用二进制文件：{rel}"));
+    }
+    let text = String::from_utf8(bytes).map_err(|_| format!("文件不是 UTF-8 文本：{rel}"))?;
+    let text = truncate_text(text);
+    let dir = attachments_dir()?;
+    fs::create_dir_all(&dir).map_err(|e| format!("创建附件目录失败：{e}"))?;
+    let file = dir.join(format!("{}.txt", content_hash(text.as_bytes())));
+    if !file.exists() {
+        fs::write(&file, &text).map_err(|e| format!("保存文件快照失败：{e}"))?;
+    }
+    Ok(Attachment {
+        name: rel.to_string(),
+        mime_type: TEXT_MIME.to_string(),
+        path: file.to_string_lossy().to_string(),
+    })
+}
+
+pub fn is_image(attachment: &Attachment) -> bool {
+    attachment.mime_type.starts_with("image/")
+}
+
+/// 附件快照的字节数，读取失败时为 0。
+pub fn stored_size(attachment: &Attachment) -> u64 {
+    fs::metadata(&attachment.path).map(|m| m.len()).unwrap_or(0)
+}
+
+/// 引用文件发送给模型时的形式：路径加带语言标注的代码块。
+pub fn fenced_block(attachment: &Attachment) -> String {
+    let content = match fs::read_to_string(&attachment.path) {
+        Ok(content) => content,
+        Err(_) => return format!("[文件快照缺失：{}]", attachment.name),
+    };
+    let fence = fence_for(&content);
+    let lang = language_for_path(&attachment.name);
+    format!(
+        "`{}`\n{fence}{lang}\n{}\n{fence}",
+        attachment.name,
+        content.trim_end_matches('\n')
+    )
+}
+
+/// 提取文本中的 `@路径` 引用（`@` 需位于行首或空白之后）。
+pub fn mentioned_paths(text: &str) -> Vec<String> {
+    let mut paths: Vec<String> = Vec::new();
+    for token in text.split_whitespace() {
+        let Some(path) = token.strip_prefix('@') else {
+            continue;
+        };
+        let path = path.trim_end_matches([',', '，', '。', ';', ':', '：', ')', '）']);
+        if !path.is_empty() && !paths.iter().any(|p| p == path) {
+            paths.push(path.to_string());
+        }
+    }
+    paths
+}
+
+pub fn load_base64(attachment: &Attachment) -> Result<String, String> {
+    let bytes = fs::read(&attachment.path)
+        .map_err(|e| format!("读取附件失败：{} ({e})", attachment.path))?;
+    Ok(base64::engine::general_purpose::STANDARD.encode(bytes))
+}
+
+/// 聊天记录与输入框中显示的占位标签。
+pub fn placeholder(attachment: &Attachment) -> String {
+    if is_image(attachment) {
+        format!("🖼 {}", attachment.name)
+    } else {
+        format!("📄 {}", attachment.name)
+    }
+}
+
+pub fn placeholders(attachments: &[Attachment]) -> String {
+    attachments
+        .iter()
+        .map(placeholder)
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+fn truncate_text(mut text: String) -> String {
+    if text.len() <= MAX_FILE_BYTES {
+        return text;
+    }
+    let total = text.len();
+    let mut end = MAX_FILE_BYTES;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text.truncate(end);
+    text.push_str(&format!(
+        "\n…（文件过大，仅保留前 {} KB，原始大小 {total} 字节）",
+        MAX_FILE_BYTES / 1024
+    ));
+    text
+}
+
+fn fence_for(content: &str) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for ch in content.chars() {
+        if ch == '`' {
+            run += 1;
+            longest = longest.max(run);
+        } else {
+            run = 0;
+        }
+    }
+    "`".repeat(longest.max(2) + 1)
+}
+
+fn language_for_path(name: &str) -> &'static str {
+    let ext = Path::new(name)
+        .extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "rs" => "rust",
+        "py" => "python",
+        "js" | "mjs" | "cjs" => "javascript",
+        "ts" => "typescript",
+        "tsx" => "tsx",
+        "jsx" => "jsx",
+        "go" => "go",
+        "java" => "java",
+        "kt" => "kotlin",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hpp" => "cpp",
+        "cs" => "csharp",
+        "rb" => "ruby",
+        "php" => "php",
+        "swift" => "swift",
+        "sh" | "bash" => "bash",
+        "zsh" => "zsh",
+        "toml" => "toml",
+        "yaml" | "yml" => "yaml",
+        "json" => "json",
+        "md" => "markdown",
+        "html" | "htm" => "html",
+        "css" => "css",
+        "sql" => "sql",
+        "xml" => "xml",
+        "lua" => "lua",
+        _ => "",
+    }
+}
+
+fn sniff_image_mime(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return Some("image/png");
+    }
+    if bytes.starts_with(&[0xff, 0xd8, 0xff]) {
+        return Some("image/jpeg");
+    }
+    if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        return Some("image/gif");
+    }
+    if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        return Some("image/webp");
+    }
+    None
+}
+
+fn image_mime_for_path(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_string_lossy().to_ascii_lowercase();
+    match ext.as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+fn extension_for_mime(mime_type: &str) -> &'static str {
+    match mime_type {
+        "image/jpeg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        _ => "png",
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    if let Some(rest) = path.strip_prefix("~/")
+        && let Ok(home) = std::env::var("HOME")
+    {
+        return PathBuf::from(home).join(rest);
+    }
+    PathBuf::from(path)
+}
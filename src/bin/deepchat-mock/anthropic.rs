@@ -0,0 +1,167 @@
+//! Anthropic 兼容的 `/v1/messages`：thinking / text / tool_use 三类内容块。
+
+use serde_json::{Value, json};
+use std::io;
+
+use crate::http::SseWriter;
+use crate::script::{MockResponse, MockToolCall};
+
+const MESSAGE_ID: &str = "msg_mock";
+const SIGNATURE: &str = "mock-signature";
+
+pub(crate) fn stream(
+    out: &mut SseWriter<'_>,
+    model: &str,
+    response: &MockResponse,
+) -> io::Result<()> {
+    let usage = response.usage.unwrap_or_default();
+    let start = json!({
+        "type": "message_start",
+        "message": {
+            "id": MESSAGE_ID,
+            "type": "message",
+            "role": "assistant",
+            "model": model,
+            "content": [],
+            "stop_reason": null,
+            "stop_sequence": null,
+            "usage": {
+                "input_tokens": usage.prompt_tokens,
+                "output_tokens": 0,
+                "cache_read_input_tokens": usage.cached_tokens,
+            },
+        },
+    });
+    out.event(Some("message_start"), &start.to_string())?;
+    let mut index = 0;
+    if !response.reasoning.is_empty() {
+        stream_thinking(out, index, &response.reasoning)?;
+        index += 1;
+    }
+    if !response.text.is_empty() {
+        stream_text(out, index, &response.text)?;
+        index += 1;
+    }
+    for (n, call) in response.tool_calls.iter().enumerate() {
+        stream_tool_use(out, index, n, call)?;
+        index += 1;
+    }
+    let delta = json!({
+        "type": "message_delta",
+        "delta": { "stop_reason": stop_reason(response), "stop_sequence": null },
+        "usage": { "output_tokens": usage.completion_tokens },
+    });
+    out.event(Some("message_delta"), &delta.to_string())?;
+    out.event(
+        Some("message_stop"),
+        &json!({ "type": "message_stop" }).to_string(),
+    )
+}
+
+fn stream_thinking(out: &mut SseWriter<'_>, index: usize, pieces: &[String]) -> io::Result<()> {
+    block_start(out, index, json!({ "type": "thinking", "thinking": "" }))?;
+    for piece in pieces {
+        block_delta(
+            out,
+            index,
+            json!({ "type": "thinking_delta", "thinking": piece }),
+        )?;
+    }
+    let signature = json!({
+        "type": "content_block_delta",
+        "index": index,
+        "delta": { "type": "signature_delta", "signature": SIGNATURE },
+    });
+    out.event(Some("content_block_delta"), &signature.to_string())?;
+    block_stop(out, index)
+}
+
+fn stream_text(out: &mut SseWriter<'_>, index: usize, pieces: &[String]) -> io::Result<()> {
+    block_start(out, index, json!({ "type": "text", "text": "" }))?;
+    for piece in pieces {
+        block_delta(out, index, json!({ "type": "text_delta", "text": piece }))?;
+    }
+    block_stop(out, index)
+}
+
+fn stream_tool_use(
+    out: &mut SseWriter<'_>,
+    index: usize,
+    n: usize,
+    call: &MockToolCall,
+) -> io::Result<()> {
+    let block =
+        json!({ "type": "tool_use", "id": tool_use_id(n, call), "name": call.name, "input": {} });
+    block_start(out, index, block)?;
+    let delta = json!({ "type": "input_json_delta", "partial_json": call.arguments_text() });
+    block_delta(out, index, delta)?;
+    block_stop(out, index)
+}
+
+fn block_start(out: &mut SseWriter<'_>, index: usize, block: Value) -> io::Result<()> {
+    let data = json!({ "type": "content_block_start", "index": index, "content_block": block });
+    out.event(Some("content_block_start"), &data.to_string())
+}
+
+fn block_delta(out: &mut SseWriter<'_>, index: usize, delta: Value) -> io::Result<()> {
+    let data = json!({ "type": "content_block_delta", "index": index, "delta": delta });
+    out.chunk(Some("content_block_delta"), &data)
+}
+
+fn block_stop(out: &mut SseWriter<'_>, index: usize) -> io::Result<()> {
+    let data = json!({ "type": "content_block_stop", "index": index });
+    out.event(Some("content_block_stop"), &data.to_string())
+}
+
+pub(crate) fn complete(model: &str, response: &MockResponse) -> Value {
+    let mut content = Vec::new();
+    if !response.reasoning.is_empty() {
+        content.push(json!({
+            "type": "thinking",
+            "thinking": response.reasoning.concat(),
+            "signature": SIGNATURE,
+        }));
+    }
+    if !response.text.is_empty() {
+        content.push(json!({ "type": "text", "text": response.text.concat() }));
+    }
+    for (n, call) in response.tool_calls.iter().enumerate() {
+        content.push(json!({
+            "type": "tool_use",
+            "id": tool_use_id(n, call),
+            "name": call.name,
+            "input": call.arguments_value(),
+        }));
+    }
+    let usage = response.usage.unwrap_or_default();
+    json!({
+        "id": MESSAGE_ID,
+        "type": "message",
+        "role": "assistant",
+        "model": model,
+        "content": content,
+        "stop_reason": stop_reason(response),
+        "stop_sequence": null,
+        "usage": {
+            "input_tokens": usage.prompt_tokens,
+            "output_tokens": usage.completion_tokens,
+            "cache_read_input_tokens": usage.cached_tokens,
+        },
+    })
+}
+
+pub(crate) fn error_body(message: &str) -> Value {
+    json!({ "type": "error", "error": { "type": "mock_error", "message": message } })
+}
+
+fn tool_use_id(n: usize, call: &MockToolCall) -> String {
+    call.id.clone().unwrap_or_else(|| format!("toolu_mock_{n}"))
+}
+
+fn stop_reason(response: &MockResponse) -> &'static str {
+    if response.tool_calls.is_empty() {
+        "end_turn"
+    } else {
+        "tool_use"
+    }
+}
@@ -0,0 +1,146 @@
+use serde_json::Value;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::thread;
+use std::time::Duration;
+
+pub(crate) struct HttpRequest {
+    pub method: String,
+    pub path: String,
+    pub body: Vec<u8>,
+}
+
+/// 只解析 mock 需要的部分：请求行、Content-Length 与请求体。
+pub(crate) fn read_request(stream: &TcpStream) -> Result<HttpRequest, String> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader
+        .read_line(&mut line)
+        .map_err(|e| format!("读取请求失败：{e}"))?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+    let content_length = read_content_length(&mut reader)?;
+    let mut body = vec![0; content_length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|e| format!("读取请求体失败：{e}"))?;
+    Ok(HttpRequest { method, path, body })
+}
+
+fn read_content_length(reader: &mut impl BufRead) -> Result<usize, String> {
+    let mut length = 0;
+    loop {
+        let mut header = String::new();
+        reader
+            .read_line(&mut header)
+            .map_err(|e| format!("读取请求头失败：{e}"))?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            return Ok(length);
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            length = value.trim().parse().unwrap_or(0);
+        }
+    }
+}
+
+pub(crate) fn write_json(stream: &mut TcpStream, status: u16, body: &Value) -> io::Result<()> {
+    write_json_with(stream, status, "", body)
+}
+
+/// 错误响应：脚本指定 `retry_after` 时附带 Retry-After 头。
+pub(crate) fn write_error(
+    stream: &mut TcpStream,
+    status: u16,
+    retry_after: Option<u64>,
+    body: &Value,
+) -> io::Result<()> {
+    let headers = retry_after.map_or(String::new(), |secs| format!("Retry-After: {secs}\r\n"));
+    write_json_with(stream, status, &headers, body)
+}
+
+fn write_json_with(
+    stream: &mut TcpStream,
+    status: u16,
+    extra_headers: &str,
+    body: &Value,
+) -> io::Result<()> {
+    let body = body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {status} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n{extra_headers}Connection: close\r\n\r\n{body}",
+        reason_phrase(status),
+        body.len()
+    )?;
+    stream.flush()
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        529 => "Overloaded",
+        _ => "Error",
+    }
+}
+
+/// SSE 输出：内容片段之间按脚本延迟，并可在第 N 个片段处模拟断线。
+pub(crate) struct SseWriter<'a> {
+    stream: &'a mut TcpStream,
+    chunk_delay: Duration,
+    fail_after: Option<usize>,
+    sent: usize,
+}
+
+impl<'a> SseWriter<'a> {
+    pub(crate) fn start(
+        stream: &'a mut TcpStream,
+        chunk_delay_ms: u64,
+        fail_after: Option<usize>,
+    ) -> io::Result<Self> {
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n"
+        )?;
+        stream.flush()?;
+        Ok(Self {
+            stream,
+            chunk_delay: Duration::from_millis(chunk_delay_ms),
+            fail_after,
+            sent: 0,
+        })
+    }
+
+    /// 元数据事件（开始/结束标记等），不计入片段数也不延迟。
+    pub(crate) fn event(&mut self, name: Option<&str>, data: &str) -> io::Result<()> {
+        if let Some(name) = name {
+            writeln!(self.stream, "event: {name}")?;
+        }
+        writeln!(self.stream, "data: {data}\n")?;
+        self.stream.flush()
+    }
+
+    /// 内容片段（文本、推理、工具调用）。
+    pub(crate) fn chunk(&mut self, name: Option<&str>, data: &Value) -> io::Result<()> {
+        if self.fail_after == Some(self.sent) {
+            return Err(io::Error::new(
+                io::ErrorKind::ConnectionAborted,
+                "按脚本模拟连接中断",
+            ));
+        }
+        if self.sent > 0 && !self.chunk_delay.is_zero() {
+            thread::sleep(self.chunk_delay);
+        }
+        self.sent += 1;
+        self.event(name, &data.to_string())
+    }
+}
@@ -0,0 +1,184 @@
+//! deepchat-mock：离线模拟 LLM 服务
+//!
+//! 在本机提供 OpenAI / DeepSeek 兼容的 `/chat/completions` 与 Anthropic 兼容的 `/v1/messages`，
+//! 按脚本文件（YAML 或 JSON）回放文本、推理、工具调用、用量、延迟与错误。
+//! 把配置中 `ModelItem.base_url` 指向 `http://127.0.0.1:<port>` 即可离线复现界面与工具流程；
+//! 提供方按模型名判断（`claude-*` 走 Anthropic，`deepseek-*` 走 DeepSeek，其余走 OpenAI）。
+
+mod anthropic;
+mod http;
+mod openai;
+mod script;
+
+use clap::Parser;
+use serde_json::Value;
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use http::{HttpRequest, SseWriter, write_error, write_json};
+use script::{MockResponse, MockScript, ScriptPlayer};
+
+#[derive(Debug, Clone, Parser)]
+#[command(
+    name = "deepchat-mock",
+    about = "离线模拟 OpenAI / DeepSeek / Anthropic 流式接口，按脚本回放响应"
+)]
+struct Args {
+    /// 脚本文件（.yaml / .yml / .json）
+    script: PathBuf,
+
+    /// 监听地址
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+
+    /// 监听端口；0 表示由系统分配
+    #[arg(long, default_value_t = 8787)]
+    port: u16,
+}
+
+#[derive(Clone, Copy)]
+enum Provider {
+    ChatCompletions,
+    Anthropic,
+}
+
+impl Provider {
+    fn from_path(path: &str) -> Option<Self> {
+        let path = path.split('?').next().unwrap_or(path).trim_end_matches('/');
+        if path.ends_with("/chat/completions") {
+            Some(Self::ChatCompletions)
+        } else if path.ends_with("/messages") {
+            Some(Self::Anthropic)
+        } else {
+            None
+        }
+    }
+
+    fn error_body(self, message: &str) -> Value {
+        match self {
+            Self::ChatCompletions => openai::error_body(message),
+            Self::Anthropic => anthropic::error_body(message),
+        }
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+}
+
+fn run() -> Result<(), String> {
+    let args = Args::parse();
+    let player = ScriptPlayer::new(MockScript::load(&args.script)?);
+    let listener = TcpListener::bind((args.host.as_str(), args.port))
+        .map_err(|e| format!("监听 {}:{} 失败：{e}", args.host, args.port))?;
+    // 端口为 0 时由系统分配，这里打印实际地址
+    let addr = listener
+        .local_addr()
+        .map_err(|e| format!("读取监听地址失败：{e}"))?;
+    eprintln!(
+        "deepchat-mock 已启动：http://{addr}（{} 条脚本响应）",
+        player.len()
+    );
+    let player = Arc::new(Mutex::new(player));
+    for stream in listener.incoming().flatten() {
+        let player = Arc::clone(&player);
+        thread::spawn(move || {
+            if let Err(e) = handle_connection(stream, &player) {
+                eprintln!("{e}");
+            }
+        });
+    }
+    Ok(())
+}
+
+fn handle_connection(mut stream: TcpStream, player: &Mutex<ScriptPlayer>) -> Result<(), String> {
+    let request = http::read_request(&stream)?;
+    let Some(provider) = Provider::from_path(&request.path) else {
+        let body = openai::error_body(&format!("未知接口：{} {}", request.method, request.path));
+        return write_json(&mut stream, 404, &body).map_err(|e| format!("写入响应失败：{e}"));
+    };
+    let body: Value = match serde_json::from_slice(&request.body) {
+        Ok(body) => body,
+        Err(e) => {
+            let body = provider.error_body(&format!("请求体不是合法 JSON：{e}"));
+            return write_json(&mut stream, 400, &body).map_err(|e| format!("写入响应失败：{e}"));
+        }
+    };
+    let (index, response) = player
+        .lock()
+        .map_err(|_| "脚本状态已损坏".to_string())?
+        .next_for(fresh_user_text(&body).as_deref());
+    log_request(&request, index);
+    respond(&mut stream, provider, &body, &response)
+}
+
+fn log_request(request: &HttpRequest, index: Option<usize>) {
+    let index = index.map_or("无匹配".to_string(), |idx| format!("#{idx}"));
+    eprintln!("{} {} → 响应 {index}", request.method, request.path);
+}
+
+fn respond(
+    stream: &mut TcpStream,
+    provider: Provider,
+    body: &Value,
+    response: &MockResponse,
+) -> Result<(), String> {
+    if response.delay_ms > 0 {
+        thread::sleep(Duration::from_millis(response.delay_ms));
+    }
+    let model = body["model"].as_str().unwrap_or("mock");
+    if let Some(error) = &response.error
+        && error.after_chunks.is_none()
+    {
+        let body = provider.error_body(&error.message);
+        return write_error(stream, error.status, error.retry_after, &body)
+            .map_err(|e| format!("写入响应失败：{e}"));
+    }
+    if !body["stream"].as_bool().unwrap_or(false) {
+        let body = match provider {
+            Provider::ChatCompletions => openai::complete(model, response),
+            Provider::Anthropic => anthropic::complete(model, response),
+        };
+        return write_json(stream, 200, &body).map_err(|e| format!("写入响应失败：{e}"));
+    }
+    let fail_after = response.error.as_ref().and_then(|e| e.after_chunks);
+    let result =
+        SseWriter::start(stream, response.chunk_delay_ms, fail_after).and_then(|mut out| {
+            match provider {
+                Provider::ChatCompletions => openai::stream(&mut out, model, response),
+                Provider::Anthropic => anthropic::stream(&mut out, model, response),
+            }
+        });
+    match result {
+        Err(e) if e.kind() == std::io::ErrorKind::ConnectionAborted && fail_after.is_some() => {
+            eprintln!("已按脚本中断连接");
+            Ok(())
+        }
+        other => other.map_err(|e| format!("写入响应失败：{e}")),
+    }
+}
+
+/// 最后一条消息是用户输入时返回其文本；工具结果回传（OpenAI 的 tool 消息、Anthropic 的 tool_result 块）返回 None。
+fn fresh_user_text(body: &Value) -> Option<String> {
+    let last = body["messages"].as_array()?.last()?;
+    if last["role"] != "user" {
+        return None;
+    }
+    let text = match &last["content"] {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts
+            .iter()
+            .filter(|part| part["type"] == "text")
+            .filter_map(|part| part["text"].as_str())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    };
+    (!text.is_empty()).then_some(text)
+}
@@ -0,0 +1,119 @@
+//! OpenAI / DeepSeek 兼容的 `/chat/completions`（推理内容走 DeepSeek 的 `reasoning_content` 字段）。
+
+use serde_json::{Value, json};
+use std::io;
+
+use crate::http::SseWriter;
+use crate::script::{MockResponse, MockUsage};
+
+const RESPONSE_ID: &str = "chatcmpl-mock";
+
+pub(crate) fn stream(
+    out: &mut SseWriter<'_>,
+    model: &str,
+    response: &MockResponse,
+) -> io::Result<()> {
+    out.event(
+        None,
+        &chunk(model, json!({ "role": "assistant", "content": "" }), None).to_string(),
+    )?;
+    for piece in &response.reasoning {
+        out.chunk(
+            None,
+            &chunk(model, json!({ "reasoning_content": piece }), None),
+        )?;
+    }
+    for piece in &response.text {
+        out.chunk(None, &chunk(model, json!({ "content": piece }), None))?;
+    }
+    for (index, call) in response.tool_calls.iter().enumerate() {
+        let delta = json!({ "tool_calls": [tool_call_json(index, call)] });
+        out.chunk(None, &chunk(model, delta, None))?;
+    }
+    out.event(
+        None,
+        &chunk(model, json!({}), Some(finish_reason(response))).to_string(),
+    )?;
+    if let Some(usage) = response.usage {
+        let data = json!({
+            "id": RESPONSE_ID,
+            "object": "chat.completion.chunk",
+            "created": 0,
+            "model": model,
+            "choices": [],
+            "usage": usage_json(usage),
+        });
+        out.event(None, &data.to_string())?;
+    }
+    out.event(None, "[DONE]")
+}
+
+pub(crate) fn complete(model: &str, response: &MockResponse) -> Value {
+    let mut message = json!({ "role": "assistant", "content": response.text.concat() });
+    if !response.reasoning.is_empty() {
+        message["reasoning_content"] = json!(response.reasoning.concat());
+    }
+    if !response.tool_calls.is_empty() {
+        let calls: Vec<Value> = response
+            .tool_calls
+            .iter()
+            .enumerate()
+            .map(|(index, call)| tool_call_json(index, call))
+            .collect();
+        message["tool_calls"] = json!(calls);
+    }
+    json!({
+        "id": RESPONSE_ID,
+        "object": "chat.completion",
+        "created": 0,
+        "model": model,
+        "choices": [{ "index": 0, "message": message, "finish_reason": finish_reason(response) }],
+        "usage": usage_json(response.usage.unwrap_or_default()),
+    })
+}
+
+pub(crate) fn error_body(message: &str) -> Value {
+    json!({ "error": { "message": message, "type": "mock_error" } })
+}
+
+fn chunk(model: &str, delta: Value, finish_reason: Option<&str>) -> Value {
+    json!({
+        "id": RESPONSE_ID,
+        "object": "chat.completion.chunk",
+        "created": 0,
+        "model": model,
+        "choices": [{ "index": 0, "delta": delta, "finish_reason": finish_reason }],
+    })
+}
+
+fn tool_call_json(index: usize, call: &crate::script::MockToolCall) -> Value {
+    let id = call
+        .id
+        .clone()
+        .unwrap_or_else(|| format!("call_mock_{index}"));
+    json!({
+        "index": index,
+        "id": id,
+        "type": "function",
+        "function": { "name": call.name, "arguments": call.arguments_text() },
+    })
+}
+
+fn finish_reason(response: &MockResponse) -> &'static str {
+    if response.tool_calls.is_empty() {
+        "stop"
+    } else {
+        "tool_calls"
+    }
+}
+
+fn usage_json(usage: MockUsage) -> Value {
+    json!({
+        "prompt_tokens": usage.prompt_tokens,
+        "completion_tokens": usage.completion_tokens,
+        "total_tokens": usage.prompt_tokens + usage.completion_tokens,
+        "prompt_cache_hit_tokens": usage.cached_tokens,
+        "prompt_cache_miss_tokens": usage.prompt_tokens.saturating_sub(usage.cached_tokens),
+        "prompt_tokens_details": { "cached_tokens": usage.cached_tokens },
+    })
+}
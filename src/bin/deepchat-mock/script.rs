@@ -0,0 +1,249 @@
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::path::Path;
+
+/// 回放脚本：每次请求取一条响应。
+#[derive(Debug, Deserialize)]
+pub(crate) struct MockScript {
+    #[serde(default)]
+    pub responses: Vec<MockResponse>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct MockResponse {
+    /// 仅当最后一条用户消息包含该文本时使用
+    #[serde(rename = "match")]
+    pub matches: Option<String>,
+    #[serde(deserialize_with = "one_or_many")]
+    pub reasoning: Vec<String>,
+    #[serde(deserialize_with = "one_or_many")]
+    pub text: Vec<String>,
+    pub tool_calls: Vec<MockToolCall>,
+    pub usage: Option<MockUsage>,
+    /// 返回第一个字节前的等待（毫秒）
+    pub delay_ms: u64,
+    /// 相邻两个流式片段之间的等待（毫秒）
+    pub chunk_delay_ms: u64,
+    pub error: Option<MockError>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct MockToolCall {
+    #[serde(default)]
+    pub id: Option<String>,
+    pub name: String,
+    /// 对象会序列化为 JSON；字符串原样发送（可用来模拟非法参数）
+    #[serde(default)]
+    pub arguments: Value,
+}
+
+impl MockToolCall {
+    pub(crate) fn arguments_text(&self) -> String {
+        match &self.arguments {
+            Value::String(raw) => raw.clone(),
+            Value::Null => "{}".to_string(),
+            other => other.to_string(),
+        }
+    }
+
+    pub(crate) fn arguments_value(&self) -> Value {
+        match &self.arguments {
+            Value::String(raw) => {
+                serde_json::from_str(raw).unwrap_or_else(|_| Value::Object(Default::default()))
+            }
+            Value::Null => Value::Object(Default::default()),
+            other => other.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct MockUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub cached_tokens: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct MockError {
+    #[serde(default = "default_error_status")]
+    pub status: u16,
+    #[serde(default = "default_error_message")]
+    pub message: String,
+    /// 设置后先正常发送这么多个流式片段再断开连接，模拟传输中断
+    #[serde(default)]
+    pub after_chunks: Option<usize>,
+    /// 设置后在错误响应中带上 Retry-After 头（秒）
+    #[serde(default)]
+    pub retry_after: Option<u64>,
+}
+
+fn default_error_status() -> u16 {
+    500
+}
+
+fn default_error_message() -> String {
+    "mock error".to_string()
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(text) => vec![text],
+        OneOrMany::Many(chunks) => chunks,
+    })
+}
+
+impl MockScript {
+    /// 按扩展名解析：`.json` 用 JSON，其余按 YAML。
+    pub(crate) fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("读取脚本失败：{}：{e}", path.display()))?;
+        let is_json = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        Self::parse(&text, is_json)
+    }
+
+    pub(crate) fn parse(text: &str, is_json: bool) -> Result<Self, String> {
+        let script: MockScript = if is_json {
+            serde_json::from_str(text).map_err(|e| format!("解析脚本失败：{e}"))?
+        } else {
+            serde_norway::from_str(text).map_err(|e| format!("解析脚本失败：{e}"))?
+        };
+        if script.responses.is_empty() {
+            return Err("脚本中没有任何响应（responses 为空）".to_string());
+        }
+        Ok(script)
+    }
+}
+
+/// 依次回放脚本响应：`match` 命中的优先，其余按顺序循环使用。
+pub(crate) struct ScriptPlayer {
+    responses: Vec<MockResponse>,
+    cursor: usize,
+}
+
+impl ScriptPlayer {
+    pub(crate) fn new(script: MockScript) -> Self {
+        Self {
+            responses: script.responses,
+            cursor: 0,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    /// `user_text` 为 None 表示本次请求是工具结果回传，此时不参与 `match`，避免同一响应反复命中。
+    pub(crate) fn next_for(&mut self, user_text: Option<&str>) -> (Option<usize>, MockResponse) {
+        if let Some(text) = user_text
+            && let Some(idx) = self.responses.iter().position(|r| {
+                r.matches
+                    .as_deref()
+                    .is_some_and(|needle| text.contains(needle))
+            })
+        {
+            return (Some(idx), self.responses[idx].clone());
+        }
+        let sequential: Vec<usize> = (0..self.responses.len())
+            .filter(|&idx| self.responses[idx].matches.is_none())
+            .collect();
+        if sequential.is_empty() {
+            return (None, fallback_response());
+        }
+        let idx = sequential[self.cursor % sequential.len()];
+        self.cursor += 1;
+        (Some(idx), self.responses[idx].clone())
+    }
+}
+
+fn fallback_response() -> MockResponse {
+    MockResponse {
+        text: vec!["（mock 脚本中没有匹配的响应）".to_string()],
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(yaml: &str) -> ScriptPlayer {
+        ScriptPlayer::new(MockScript::parse(yaml, false).expect("script"))
+    }
+
+    #[test]
+    fn text_accepts_one_string_or_chunks() {
+        let script = MockScript::parse(
+            "responses:\n  - text: 你好\n  - text: [\"a\", \"b\"]\n    reasoning: 想\n",
+            false,
+        )
+        .expect("script");
+        assert_eq!(script.responses[0].text, vec!["你好"]);
+        assert_eq!(script.responses[1].text, vec!["a", "b"]);
+        assert_eq!(script.responses[1].reasoning, vec!["想"]);
+    }
+
+    #[test]
+    fn json_script_parses_tool_calls_usage_and_errors() {
+        let json = r#"{"responses": [
+            {"tool_calls": [{"name": "web_search", "arguments": {"query": "x"}}],
+             "usage": {"prompt_tokens": 10, "cached_tokens": 4}},
+            {"tool_calls": [{"name": "modify_file", "arguments": "not json"}],
+             "error": {"after_chunks": 2}}
+        ]}"#;
+        let script = MockScript::parse(json, true).expect("script");
+        let first = &script.responses[0];
+        assert_eq!(first.tool_calls[0].arguments_text(), r#"{"query":"x"}"#);
+        let usage = first.usage.expect("usage");
+        assert_eq!((usage.prompt_tokens, usage.cached_tokens), (10, 4));
+        let second = &script.responses[1];
+        assert_eq!(second.tool_calls[0].arguments_text(), "not json");
+        assert!(second.tool_calls[0].arguments_value().is_object());
+        let error = second.error.as_ref().expect("error");
+        assert_eq!((error.status, error.after_chunks), (500, Some(2)));
+    }
+
+    #[test]
+    fn rejects_empty_or_invalid_scripts() {
+        assert!(MockScript::parse("responses: []\n", false).is_err());
+        assert!(MockScript::parse("responses:\n  - delay_ms: soon\n", false).is_err());
+        assert!(MockScript::parse("{", true).is_err());
+    }
+
+    #[test]
+    fn match_takes_priority_and_tool_results_stay_sequential() {
+        let mut player =
+            player("responses:\n  - text: one\n  - match: 搜索\n    text: hit\n  - text: two\n");
+        assert_eq!(player.next_for(Some("请搜索一下")).0, Some(1));
+        assert_eq!(player.next_for(None).0, Some(0));
+        assert_eq!(player.next_for(Some("搜索")).0, Some(1));
+        assert_eq!(player.next_for(None).0, Some(2));
+        assert_eq!(player.next_for(Some("其他")).0, Some(0));
+    }
+
+    #[test]
+    fn only_matched_entries_fall_back_when_nothing_matches() {
+        let mut player = player("responses:\n  - match: 搜索\n    text: hit\n");
+        let (idx, response) = player.next_for(Some("你好"));
+        assert_eq!(idx, None);
+        assert_eq!(response.text, fallback_response().text);
+    }
+
+    #[test]
+    fn demo_script_loads() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("scripts/mock/demo.yaml");
+        let script = MockScript::load(&path).expect("demo script");
+        assert!(script.responses.iter().any(|r| !r.tool_calls.is_empty()));
+    }
+}
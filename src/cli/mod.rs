@@ -1 +1,3 @@
-pub(crate) mod model;
+pub mod model;
+pub mod sandbox;
+pub mod usage;
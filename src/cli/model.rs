@@ -1,8 +1,11 @@
-use crate::config::{Config, ModelItem, default_config_path, load_config, save_config};
+use crate::config::{
+    Config, ExecBackend, ModelItem, SandboxConfig, default_config_path, load_config, save_config,
+};
+use crate::llm::agent_limits::AgentLimits;
 use std::io::{self, Write};
 use std::path::PathBuf;
 
-pub(crate) fn run_add(cfg_override: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+pub fn run_add(cfg_override: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
     let path = resolve_config_path(cfg_override)?;
     let mut cfg = load_or_create_config(&path)?;
     let item = prompt_model_item()?;
@@ -39,6 +42,10 @@ fn default_config(prompts_dir: &str) -> Config {
         hooks: Vec::new(),
         prompts_dir: prompts_dir.to_string(),
         tavily_api_key: String::new(),
+        exec_backend: ExecBackend::Docker,
+        sandbox: SandboxConfig::default(),
+        agent_limits: AgentLimits::default(),
+        title_model: None,
     }
 }
 
@@ -47,6 +54,10 @@ fn prompt_model_item() -> Result<ModelItem, Box<dyn std::error::Error>> {
     let base_url = prompt_non_empty("API Base URL（如 https://api.deepseek.com）: ")?;
     let model = prompt_non_empty("模型名称（如 deepseek-chat）: ")?;
     let max_tokens = prompt_optional_u64("max_tokens（可选，回车跳过；Anthropic 必填）: ")?;
+    let context_window = prompt_optional_u64("上下文窗口 tokens（可选，回车按模型名推断）: ")?;
+    let input_price = prompt_optional_f64("输入单价 $/百万 tokens（可选，回车跳过）: ")?;
+    let output_price = prompt_optional_f64("输出单价 $/百万 tokens（可选，回车跳过）: ")?;
+    let cached_input_price = prompt_optional_f64("缓存输入单价 $/百万 tokens（可选，回车跳过）: ")?;
     let api_key = prompt_non_empty("API Key: ")?;
     Ok(ModelItem {
         key,
@@ -54,6 +65,16 @@ fn prompt_model_item() -> Result<ModelItem, Box<dyn std::error::Error>> {
         api_key,
         model,
         max_tokens,
+        context_window,
+        input_price,
+        output_price,
+        cached_input_price,
+        reasoning_effort: None,
+        thinking_budget: None,
+        connect_timeout_secs: None,
+        first_token_timeout_secs: None,
+        idle_timeout_secs: None,
+        retry_on_stall: None,
     })
 }
 
@@ -70,6 +91,22 @@ fn prompt_optional_u64(prompt: &str) -> Result<Option<u64>, Box<dyn std::error::
     }
 }
 
+fn prompt_optional_f64(prompt: &str) -> Result<Option<f64>, Box<dyn std::error::Error>> {
+    loop {
+        let s = prompt_line(prompt)?;
+        let s = s.trim();
+        if s.is_empty() {
+            return Ok(None);
+        }
+        if let Ok(v) = s.parse::<f64>()
+            && v.is_finite()
+            && v >= 0.0
+        {
+            return Ok(Some(v));
+        }
+    }
+}
+
 fn upsert_model(cfg: &mut Config, item: ModelItem) -> Result<(), Box<dyn std::error::Error>> {
     let key = item.key.clone();
     if let Some(existing) = cfg.models.iter_mut().find(|m| m.key == item.key) {
@@ -0,0 +1,91 @@
+use crate::args::Args;
+use crate::config::{Config, SandboxConfig, default_config_path, load_config};
+use crate::services::code_exec_container_env::{code_exec_image, host_backend_enabled};
+use crate::services::sandbox_image::{
+    build_image, build_spec, count_sandbox_containers, inspect_image, prune_sandbox,
+    startup_image_hint, status_label,
+};
+use std::path::PathBuf;
+
+pub fn run_build(
+    cfg_override: Option<&str>,
+    dockerfile: Option<&str>,
+    no_cache: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let sandbox = load_sandbox_config(cfg_override)?;
+    let spec = build_spec(&sandbox, dockerfile)?;
+    let image = code_exec_image();
+    println!("构建沙箱镜像：{image}");
+    println!("Dockerfile：{}", spec.dockerfile.display());
+    print_extra_packages(&sandbox);
+    build_image(&image, &spec, no_cache)?;
+    println!("沙箱镜像已构建：{image}（{}）", spec.hash);
+    Ok(())
+}
+
+pub fn run_status(cfg_override: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let sandbox = load_sandbox_config(cfg_override)?;
+    let image = code_exec_image();
+    let spec = build_spec(&sandbox, None);
+    let expected = spec.as_ref().ok().map(|spec| spec.hash.as_str());
+    let status = inspect_image(&image, expected);
+    println!("镜像：{image}");
+    match &spec {
+        Ok(spec) => println!("Dockerfile：{}", spec.dockerfile.display()),
+        Err(err) => println!("Dockerfile：{err}"),
+    }
+    print_extra_packages(&sandbox);
+    println!("状态：{}", status_label(&status));
+    if let Some(count) = count_sandbox_containers() {
+        println!("deepchat 容器：{count} 个（deepchat sandbox prune 可清理已退出会话遗留的容器）");
+    }
+    Ok(())
+}
+
+pub fn run_prune() -> Result<(), Box<dyn std::error::Error>> {
+    let report = prune_sandbox()?;
+    println!("已清理遗留容器：{} 个", report.containers);
+    if report.images.is_empty() {
+        println!("没有需要清理的旧镜像");
+    } else {
+        println!("{}", report.images);
+    }
+    Ok(())
+}
+
+/// 启用了依赖容器的工具而沙箱镜像缺失或过期时，返回启动提示。
+pub fn startup_notice(args: &Args, cfg: &Config) -> Option<String> {
+    let needs_container = args.code_exec_enabled()
+        || args.read_file_enabled()
+        || args.read_code_enabled()
+        || args.modify_file_enabled();
+    if !needs_container || host_backend_enabled() {
+        return None;
+    }
+    startup_image_hint(&cfg.sandbox)
+}
+
+/// 配置文件不存在时使用默认沙箱配置，保证首次使用也能直接构建镜像。
+fn load_sandbox_config(
+    cfg_override: Option<&str>,
+) -> Result<SandboxConfig, Box<dyn std::error::Error>> {
+    let path = match cfg_override {
+        Some(p) => PathBuf::from(p),
+        None => default_config_path()?,
+    };
+    if !path.exists() {
+        return Ok(SandboxConfig::default());
+    }
+    let cfg =
+        load_config(&path).map_err(|e| format!("配置文件错误：{} ({})", path.display(), e))?;
+    Ok(cfg.sandbox)
+}
+
+fn print_extra_packages(sandbox: &SandboxConfig) {
+    if !sandbox.apt_packages.is_empty() {
+        println!("额外 apt 包：{}", sandbox.apt_packages.join(" "));
+    }
+    if !sandbox.pip_packages.is_empty() {
+        println!("额外 pip 包：{}", sandbox.pip_packages.join(" "));
+    }
+}
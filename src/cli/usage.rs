@@ -0,0 +1,143 @@
+use crate::conversation::{ConversationData, UsageRecord, conversations_dir};
+use crate::llm::pricing::format_cost;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Default)]
+struct UsageTotals {
+    requests: u64,
+    prompt_tokens: u64,
+    completion_tokens: u64,
+    cached_tokens: u64,
+    cost: f64,
+    unpriced: u64,
+}
+
+impl UsageTotals {
+    fn add(&mut self, record: &UsageRecord) {
+        self.requests += 1;
+        self.prompt_tokens = self.prompt_tokens.saturating_add(record.prompt_tokens);
+        self.completion_tokens = self
+            .completion_tokens
+            .saturating_add(record.completion_tokens);
+        self.cached_tokens = self.cached_tokens.saturating_add(record.cached_tokens);
+        match record.cost {
+            Some(cost) => self.cost += cost,
+            None => self.unpriced += 1,
+        }
+    }
+}
+
+#[derive(Default)]
+struct UsageReport {
+    total: UsageTotals,
+    by_model: BTreeMap<String, UsageTotals>,
+    by_category: BTreeMap<String, UsageTotals>,
+    by_day: BTreeMap<String, UsageTotals>,
+}
+
+pub fn run_report(days: Option<u32>) -> Result<(), Box<dyn std::error::Error>> {
+    let dir = conversations_dir()?;
+    let since = days.map(cutoff_timestamp).unwrap_or(0);
+    let report = build_report(&load_conversations(&dir), since);
+    if report.total.requests == 0 {
+        println!("暂无用量记录：{}", dir.display());
+        return Ok(());
+    }
+    print_section("按模型", &report.by_model);
+    print_section("按分类", &report.by_category);
+    print_section("按日期", &report.by_day);
+    println!();
+    println!("合计：{}", totals_line(&report.total));
+    if report.total.unpriced > 0 {
+        println!(
+            "其中 {} 次请求的模型未配置单价（config.json 的 input_price/output_price），未计入费用",
+            report.total.unpriced
+        );
+    }
+    Ok(())
+}
+
+/// 读取失败或格式不对的对话文件直接跳过，不影响其余统计。
+fn load_conversations(dir: &Path) -> Vec<ConversationData> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|entry| std::fs::read_to_string(entry.path()).ok())
+        .filter_map(|text| serde_json::from_str::<ConversationData>(&text).ok())
+        .collect()
+}
+
+fn build_report(conversations: &[ConversationData], since: u64) -> UsageReport {
+    let mut report = UsageReport::default();
+    for conv in conversations {
+        let category = if conv.category.trim().is_empty() {
+            "默认".to_string()
+        } else {
+            conv.category.clone()
+        };
+        for record in conv.usage.iter().filter(|r| r.timestamp >= since) {
+            report.total.add(record);
+            report
+                .by_model
+                .entry(record.model.clone())
+                .or_default()
+                .add(record);
+            report
+                .by_category
+                .entry(category.clone())
+                .or_default()
+                .add(record);
+            report
+                .by_day
+                .entry(day_label(record.timestamp))
+                .or_default()
+                .add(record);
+        }
+    }
+    report
+}
+
+fn print_section(title: &str, rows: &BTreeMap<String, UsageTotals>) {
+    println!();
+    println!("{title}：");
+    let width = rows.keys().map(|k| k.chars().count()).max().unwrap_or(0);
+    for (key, totals) in rows {
+        let pad = width.saturating_sub(key.chars().count());
+        println!("  {key}{}  {}", " ".repeat(pad), totals_line(totals));
+    }
+}
+
+fn totals_line(totals: &UsageTotals) -> String {
+    let mut line = format!(
+        "请求 {} 次 · tokens: {}/{}",
+        totals.requests, totals.prompt_tokens, totals.completion_tokens
+    );
+    if totals.cached_tokens > 0 {
+        line.push_str(&format!("（缓存 {}）", totals.cached_tokens));
+    }
+    if totals.unpriced < totals.requests {
+        line.push_str(&format!(" · {}", format_cost(totals.cost)));
+    }
+    line
+}
+
+fn day_label(timestamp: u64) -> String {
+    use chrono::TimeZone;
+    chrono::Local
+        .timestamp_opt(timestamp as i64, 0)
+        .single()
+        .map(|dt| dt.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| "未知日期".to_string())
+}
+
+fn cutoff_timestamp(days: u32) -> u64 {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    now.saturating_sub(u64::from(days) * 24 * 60 * 60)
+}
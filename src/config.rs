@@ -4,6 +4,8 @@
 
 use serde::{Deserialize, Serialize};
 use crate::hooks::HookSpec;
+use crate::llm::agent_limits::AgentLimits;
+use crate::llm::reasoning::{MIN_THINKING_BUDGET, ReasoningEffort};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -20,6 +22,40 @@ pub struct Config {
     pub hooks: Vec<HookSpec>,
     pub prompts_dir: String,
     pub tavily_api_key: String,
+    /// 工具执行后端：docker（默认，容器隔离）或 host（直接在本机执行，隔离较弱）
+    #[serde(default)]
+    pub exec_backend: ExecBackend,
+    /// 沙箱镜像构建配置（deepchat sandbox build 使用）
+    #[serde(default)]
+    pub sandbox: SandboxConfig,
+    /// 单次用户输入触发的工具循环上限；对话内可用 `/limits` 调整
+    #[serde(default)]
+    pub agent_limits: AgentLimits,
+    /// 用于生成对话标题的模型 key，宜选便宜的小模型；不填时截取首条提问作为标题
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_model: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecBackend {
+    #[default]
+    Docker,
+    Host,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct SandboxConfig {
+    /// 自定义 Dockerfile 路径（相对路径以配置文件所在目录为基准），为空时使用项目根目录的 Dockerfile
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dockerfile: Option<String>,
+    /// 额外安装的 pip 包
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pip_packages: Vec<String>,
+    /// 额外安装的 apt 包
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub apt_packages: Vec<String>,
 }
 
 #[derive(Deserialize, Serialize, Clone)]
@@ -30,6 +66,36 @@ pub struct ModelItem {
     pub api_key: String,
     pub model: String,
     pub max_tokens: Option<u64>,
+    /// 上下文窗口大小（tokens）；不填则按模型名推断，未知模型不裁剪历史
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_window: Option<u64>,
+    /// 输入单价（美元 / 百万 tokens）；输入与输出单价都不填则不统计费用
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_price: Option<f64>,
+    /// 输出单价（美元 / 百万 tokens）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_price: Option<f64>,
+    /// 缓存命中的输入单价（美元 / 百万 tokens）；不填则按输入单价计
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached_input_price: Option<f64>,
+    /// 推理强度：low / medium / high（OpenAI 兼容接口的 reasoning_effort；Anthropic 未配置 thinking_budget 时据此换算预算）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<ReasoningEffort>,
+    /// Anthropic extended thinking 的思考预算（tokens，至少 1024）；配置后开启思考
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thinking_budget: Option<u64>,
+    /// 建立连接的超时（秒）；默认 30，0 表示不限制
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_timeout_secs: Option<u64>,
+    /// 等待首个 token 的超时（秒）；默认 180，0 表示不限制
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_token_timeout_secs: Option<u64>,
+    /// 相邻两个流式片段之间的超时（秒）；默认 90，0 表示不限制
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idle_timeout_secs: Option<u64>,
+    /// 响应停滞后是否自动重试；默认开启
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_on_stall: Option<bool>,
 }
 
 pub fn default_config_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
@@ -102,12 +168,37 @@ fn validate_models(cfg: &Config) -> Result<(), Box<dyn std::error::Error>> {
     {
         return Err("配置文件错误：max_tokens 不能为 0".into());
     }
+    if cfg.models.iter().any(|m| {
+        [m.input_price, m.output_price, m.cached_input_price]
+            .into_iter()
+            .flatten()
+            .any(|price| !price.is_finite() || price < 0.0)
+    }) {
+        return Err("配置文件错误：模型单价必须是非负数".into());
+    }
+    if cfg
+        .models
+        .iter()
+        .any(|m| m.thinking_budget.is_some_and(|b| b < MIN_THINKING_BUDGET))
+    {
+        return Err(format!("配置文件错误：thinking_budget 不能小于 {MIN_THINKING_BUDGET}").into());
+    }
     if cfg.models.iter().all(|m| m.key != cfg.default_model) {
         return Err("配置文件错误：default_model 必须在 models 中存在".into());
     }
+    if let Some(key) = &cfg.title_model
+        && cfg.models.iter().all(|m| &m.key != key)
+    {
+        return Err("配置文件错误：title_model 必须在 models 中存在".into());
+    }
     Ok(())
 }
 
+pub fn default_dockerfile_path() -> Option<PathBuf> {
+    let path = find_project_root()?.join("Dockerfile");
+    path.is_file().then_some(path)
+}
+
 fn find_project_root() -> Option<PathBuf> {
     let mut dir = env::current_dir().ok()?;
     loop {
@@ -139,6 +230,19 @@ fn normalize_config_paths(path: &Path, cfg: &mut Config) {
             .to_string_lossy()
             .to_string();
     }
+    normalize_sandbox_dockerfile(path, &mut cfg.sandbox);
+}
+
+fn normalize_sandbox_dockerfile(path: &Path, sandbox: &mut SandboxConfig) {
+    let Some(dockerfile) = sandbox.dockerfile.as_deref() else {
+        return;
+    };
+    if !Path::new(dockerfile).is_relative() {
+        return;
+    }
+    if let Some(parent) = path.parent() {
+        sandbox.dockerfile = Some(parent.join(dockerfile).to_string_lossy().to_string());
+    }
 }
 
 fn apply_env_overrides(cfg: &mut Config) {
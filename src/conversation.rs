@@ -4,6 +4,7 @@
 
 use crate::types::Message;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
 use std::path::PathBuf;
@@ -21,6 +22,57 @@ pub struct ConversationData {
     pub prompt_key: Option<String>,
     #[serde(default)]
     pub code_exec_container_id: Option<String>,
+    /// 被 `/compact` 压缩掉的原始消息，仅供查阅，不再发送给模型。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub archived_messages: Vec<Message>,
+    /// 每次模型响应的用量与费用，`deepchat usage` 据此汇总。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub usage: Vec<UsageRecord>,
+    /// 本对话用 `/limits` 调整过的工具循环上限；未调整时沿用配置
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_limits: Option<crate::llm::agent_limits::AgentLimits>,
+    /// 本对话单独调整过的工具开关；未调整时沿用 `--enable`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_flags: Option<crate::args::ToolFlags>,
+    /// 对话标题；第一轮问答后自动生成，可用 `/title` 修改
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// 重新生成过的回答，按该轮提问的消息下标记录
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub answer_variants: BTreeMap<usize, AnswerVariants>,
+    /// 从其他对话分叉而来时记录来源，用于对话分支树
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fork_of: Option<ForkOrigin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ForkOrigin {
+    pub parent_id: String,
+    /// 分叉点：父对话中被重新提问的那条消息的下标，此前的消息与父对话相同
+    pub message_index: usize,
+}
+
+/// 同一轮提问的多个回答；`messages` 中保留的是当前选中的那个。
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct AnswerVariants {
+    pub selected: usize,
+    /// 每个回答是该提问之后、下一条提问之前的全部消息（含工具调用与结果）
+    pub variants: Vec<Vec<Message>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UsageRecord {
+    /// 模型 key（config.json 的 models[].key）
+    pub model: String,
+    /// Unix 时间戳（秒）
+    pub timestamp: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    #[serde(default)]
+    pub cached_tokens: u64,
+    /// 未配置单价的模型不记录费用
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
 }
 
 pub fn conversations_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
@@ -47,19 +99,37 @@ pub fn conversation_path(id: &str) -> Result<PathBuf, Box<dyn std::error::Error>
 pub fn load_conversation(id: &str) -> Result<ConversationData, Box<dyn std::error::Error>> {
     let path = conversation_path(id)?;
     let text = fs::read_to_string(&path)?;
-    let mut data: ConversationData = serde_json::from_str(&text)?;
+    let mut data: ConversationData = match serde_json::from_str(&text) {
+        Ok(data) => data,
+        Err(err) if crate::persist::is_corrupted_json(&err) => {
+            let moved = crate::persist::quarantine(&path)?;
+            return Err(format!("对话文件已损坏（{err}），已移到 {}", moved.display()).into());
+        }
+        Err(err) => return Err(err.into()),
+    };
     if data.id.trim().is_empty() {
         data.id = id.to_string();
     }
     Ok(data)
 }
 
+/// 只读取对话标题，供 `/list-conv` 列出；文件损坏或没有标题时返回 None。
+pub fn load_conversation_title(id: &str) -> Option<String> {
+    #[derive(Deserialize)]
+    struct TitleOnly {
+        #[serde(default)]
+        title: Option<String>,
+    }
+    let text = fs::read_to_string(conversation_path(id).ok()?).ok()?;
+    serde_json::from_str::<TitleOnly>(&text).ok()?.title
+}
+
 pub fn save_conversation(data: &ConversationData) -> Result<PathBuf, Box<dyn std::error::Error>> {
     let path = conversation_path(&data.id)?;
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
     let text = serde_json::to_string_pretty(data)?;
-    fs::write(&path, text)?;
+    crate::persist::write_atomic(&path, &text)?;
     Ok(path)
 }
@@ -40,6 +40,18 @@ fn handle_simple_commands(line: &str, app: &mut App) -> Result<bool, Box<dyn std
         app.pending_command = Some(PendingCommand::SaveSession);
         return Ok(true);
     }
+    if line == "/kernel-restart" {
+        app.pending_command = Some(PendingCommand::RestartKernel);
+        return Ok(true);
+    }
+    if line == "/compact" {
+        app.pending_command = Some(PendingCommand::CompactConversation);
+        return Ok(true);
+    }
+    if line == "/changes" {
+        app.pending_command = Some(PendingCommand::ShowWorkspaceChanges);
+        return Ok(true);
+    }
     if line == "/help" {
         push_help(app);
         return Ok(true);
@@ -53,6 +65,16 @@ fn handle_command_action(line: &str, app: &mut App) -> Result<(), Box<dyn std::e
         "/category" => handle_category(app, arg),
         "/open" => handle_open(app, arg),
         "/list-conv" => handle_list_conv(app)?,
+        "/image" => handle_image(app, arg),
+        "/compare" => handle_compare(app, arg),
+        "/reasoning" => handle_reasoning(app, arg),
+        "/continue" => handle_continue(app, arg),
+        "/limits" => crate::services::runtime_agent_guard::handle_limits_command(app, arg),
+        "/prompt" => crate::services::runtime_prompts::handle_prompt_command(app, arg),
+        "/tools" => crate::services::runtime_tools::handle_tools_command(app, arg),
+        "/title" => crate::services::runtime_titles::handle_title_command(app, arg),
+        "/regen" => crate::services::runtime_variants::request_regenerate(app, None),
+        "/tree" => app.pending_tree_overlay = true,
         _ => push_unknown(app, line),
     }
     Ok(())
@@ -62,6 +84,10 @@ fn reset_app(app: &mut App) {
     let system = app.messages.iter().find(|m| m.role == ROLE_SYSTEM).cloned();
     app.messages.clear();
     app.assistant_stats.clear();
+    app.pending_attachments.clear();
+    app.title = None;
+    app.answer_variants.clear();
+    app.fork_of = None;
     if let Some(sys) = system {
         app.messages.push(sys);
     }
@@ -74,6 +100,7 @@ fn push_help(app: &mut App) {
         content: commands_help_text(),
         tool_call_id: None,
         tool_calls: None,
+        attachments: Vec::new(),
     });
 }
 
@@ -95,28 +122,80 @@ fn handle_open(app: &mut App, arg: &str) {
     app.pending_command = Some(PendingCommand::OpenConversation);
 }
 
+fn handle_image(app: &mut App, arg: &str) {
+    if arg.is_empty() {
+        push_notice(app, "用法：/image <path>（/image clear 清空待发送的图片）");
+        return;
+    }
+    if arg == "clear" {
+        let count = app.pending_attachments.len();
+        app.pending_attachments.clear();
+        crate::framework::widget_system::notice::push_notice(
+            app,
+            format!("已清空 {count} 张待发送的图片"),
+        );
+        return;
+    }
+    match crate::attachments::attach_image_file(arg) {
+        Ok(attachment) => {
+            let label = crate::attachments::placeholder(&attachment);
+            app.pending_attachments.push(attachment);
+            crate::framework::widget_system::notice::push_notice(
+                app,
+                format!("已添加图片：{label}（随下一条消息发送）"),
+            );
+        }
+        Err(err) => push_notice(app, &err),
+    }
+}
+
+fn handle_compare(app: &mut App, arg: &str) {
+    app.pending_compare_models = Some(arg.to_string());
+    app.pending_command = Some(PendingCommand::SetCompareModels);
+}
+
+fn handle_reasoning(app: &mut App, arg: &str) {
+    app.pending_reasoning_view = Some(arg.to_string());
+    app.pending_command = Some(PendingCommand::SetReasoningView);
+}
+
+fn handle_continue(app: &mut App, arg: &str) {
+    app.pending_agent_steps = Some(arg.to_string());
+    app.pending_command = Some(PendingCommand::ContinueAgent);
+}
+
 fn handle_list_conv(app: &mut App) -> Result<(), Box<dyn std::error::Error>> {
     let ids = list_conversation_ids()?;
     let content = if ids.is_empty() {
         "暂无对话文件。".to_string()
     } else {
-        format!("可用对话：\n{}", ids.join("\n"))
+        let lines: Vec<String> = ids.iter().map(|id| conversation_line(id)).collect();
+        format!("可用对话：\n{}", lines.join("\n"))
     };
     app.messages.push(Message {
         role: ROLE_ASSISTANT.to_string(),
         content,
         tool_call_id: None,
         tool_calls: None,
+        attachments: Vec::new(),
     });
     Ok(())
 }
 
+fn conversation_line(id: &str) -> String {
+    match crate::conversation::load_conversation_title(id) {
+        Some(title) => format!("{id}  {title}"),
+        None => id.to_string(),
+    }
+}
+
 fn push_unknown(app: &mut App, line: &str) {
     app.messages.push(Message {
         role: ROLE_ASSISTANT.to_string(),
         content: format!("未知命令：{line}"),
         tool_call_id: None,
         tool_calls: None,
+        attachments: Vec::new(),
     });
 }
 
@@ -126,5 +205,6 @@ fn push_notice(app: &mut App, content: &str) {
         content: content.to_string(),
         tool_call_id: None,
         tool_calls: None,
+        attachments: Vec::new(),
     });
 }
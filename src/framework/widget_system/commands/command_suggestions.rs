@@ -1,6 +1,7 @@
 use crate::render::RenderTheme;
 use crate::framework::widget_system::commands::{
     CommandSuggestion, CommandSuggestionKind, command_has_args, command_suggestions_for_input,
+    mention_at_cursor, mention_suggestions_for_input,
 };
 use crate::framework::widget_system::widgets::overlay_table::{
     OverlayTable, draw_overlay_table, header_style, row_at, visible_rows,
@@ -43,11 +44,16 @@ pub(crate) fn apply_command_suggestion(app: &mut App) -> bool {
         Some(suggestion) => suggestion,
         None => return false,
     };
-    let (row, line, mut lines) = match current_input_line(app) {
+    let (row, line, mut lines) = match current_input_line(app, suggestion.kind) {
         Some(data) => data,
         None => return false,
     };
-    let (new_line, new_col) = match build_suggestion_line(&suggestion, &line) {
+    let built = if suggestion.kind == CommandSuggestionKind::Mention {
+        build_mention_line(&suggestion, &line, app.input.cursor().1)
+    } else {
+        build_suggestion_line(&suggestion, &line)
+    };
+    let (new_line, new_col) = match built {
         Some(data) => data,
         None => return false,
     };
@@ -66,22 +72,41 @@ fn selected_suggestion(app: &App) -> Option<CommandSuggestion> {
     app.command_suggestions.get(idx).cloned()
 }
 
-fn current_input_line(app: &App) -> Option<(usize, String, Vec<String>)> {
+fn current_input_line(
+    app: &App,
+    kind: CommandSuggestionKind,
+) -> Option<(usize, String, Vec<String>)> {
     let (row, _) = app.input.cursor();
     let lines = app.input.lines().to_vec();
     let line = lines.get(row)?.clone();
-    if !line.starts_with('/') {
+    if kind != CommandSuggestionKind::Mention && !line.starts_with('/') {
         return None;
     }
     Some((row, line, lines))
 }
 
+/// 用选中的路径替换光标处的 `@片段`，并在其后补一个空格。
+fn build_mention_line(
+    suggestion: &CommandSuggestion,
+    line: &str,
+    cursor_col: usize,
+) -> Option<(String, usize)> {
+    let (start, _) = mention_at_cursor(line, cursor_col)?;
+    let start_byte = byte_index_from_char(line, start);
+    let end_byte = byte_index_from_char(line, cursor_col);
+    let rest = line[end_byte..].trim_start();
+    let head = format!("{}@{} ", &line[..start_byte], suggestion.insert);
+    let new_col = head.chars().count();
+    Some((format!("{head}{rest}"), new_col))
+}
+
 fn build_suggestion_line(suggestion: &CommandSuggestion, line: &str) -> Option<(String, usize)> {
     let cmd_end_char = find_first_whitespace(line).unwrap_or(line.chars().count());
     let cmd_end_byte = byte_index_from_char(line, cmd_end_char);
     let mut new_line = match suggestion.kind {
         CommandSuggestionKind::Command => replace_command(line, cmd_end_byte, suggestion),
         CommandSuggestionKind::Argument => replace_argument(line, cmd_end_byte, suggestion),
+        CommandSuggestionKind::Mention => return None,
     };
     if new_line.trim().is_empty() {
         return None;
@@ -152,6 +177,16 @@ fn clamp_command_suggestions(app: &mut App, area: Rect) {
 
 fn build_command_suggestions_table<'a>(app: &'a App, theme: &'a RenderTheme) -> OverlayTable<'a> {
     let header = Row::new(vec![Cell::from("候选"), Cell::from("说明")]).style(header_style(theme));
+    let (title, widths) = match app.command_suggestions.first().map(|item| item.kind) {
+        Some(CommandSuggestionKind::Mention) => (
+            "引用文件 · Tab 应用 · ↑↓ 选择",
+            vec![Constraint::Min(24), Constraint::Length(10)],
+        ),
+        _ => (
+            "命令补全 · Tab 应用 · ↑↓ 选择",
+            vec![Constraint::Length(24), Constraint::Min(10)],
+        ),
+    };
     let rows = app.command_suggestions.iter().map(|item| {
         Row::new(vec![
             Cell::from(item.label.clone()),
@@ -159,10 +194,10 @@ fn build_command_suggestions_table<'a>(app: &'a App, theme: &'a RenderTheme) ->
         ])
     });
     OverlayTable {
-        title: Line::from("命令补全 · Tab 应用 · ↑↓ 选择"),
+        title: Line::from(title),
         header,
         rows: rows.collect(),
-        widths: vec![Constraint::Length(24), Constraint::Min(10)],
+        widths,
         selected: app.command_select.selected,
         scroll: app.command_select.scroll,
         theme,
@@ -231,7 +266,14 @@ fn current_input_suggestions(app: &App) -> Vec<CommandSuggestion> {
         return Vec::new();
     }
     let line = &lines[row];
-    command_suggestions_for_input(line, col)
+    if line.starts_with('/') {
+        return command_suggestions_for_input(line, col);
+    }
+    let popup_open = app
+        .command_suggestions
+        .iter()
+        .any(|item| item.kind == CommandSuggestionKind::Mention);
+    mention_suggestions_for_input(line, col, !popup_open)
 }
 
 fn find_first_whitespace(line: &str) -> Option<usize> {
@@ -0,0 +1,141 @@
+use fuzzy_matcher::FuzzyMatcher;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::{CommandSuggestion, CommandSuggestionKind};
+
+/// 遍历工作区时最多收集的文件数，避免大目录拖慢输入。
+const MAX_WORKSPACE_FILES: usize = 5000;
+const MAX_MENTION_SUGGESTIONS: usize = 50;
+const SKIPPED_DIRS: &[&str] = &["target", "node_modules", "__pycache__", "dist", "build"];
+/// 文件清单缓存的有效期；补全弹出时也会重新遍历。
+const FILE_LIST_TTL: Duration = Duration::from_secs(10);
+
+struct FileListCache {
+    root: PathBuf,
+    files: Vec<String>,
+    listed_at: Instant,
+}
+
+static FILE_LIST: Mutex<Option<FileListCache>> = Mutex::new(None);
+
+/// 光标位于 `@路径` 片段中时返回该片段的起始字符位置与已输入的路径。
+pub(crate) fn mention_at_cursor(line: &str, cursor_col: usize) -> Option<(usize, String)> {
+    let chars: Vec<char> = line.chars().collect();
+    let cursor = cursor_col.min(chars.len());
+    let start = chars[..cursor]
+        .iter()
+        .rposition(|ch| ch.is_whitespace())
+        .map(|idx| idx + 1)
+        .unwrap_or(0);
+    if chars.get(start) != Some(&'@') {
+        return None;
+    }
+    Some((start, chars[start + 1..cursor].iter().collect()))
+}
+
+/// `opening` 表示补全列表此前未显示，此时重新遍历工作区，之后输入沿用缓存。
+pub(crate) fn mention_suggestions_for_input(
+    line: &str,
+    cursor_col: usize,
+    opening: bool,
+) -> Vec<CommandSuggestion> {
+    let Some((_, pattern)) = mention_at_cursor(line, cursor_col) else {
+        return Vec::new();
+    };
+    let Some(root) = crate::services::workspace::cached_workspace_root() else {
+        return Vec::new();
+    };
+    let files = cached_workspace_files(&root, opening);
+    rank_files(files, &pattern)
+        .into_iter()
+        .take(MAX_MENTION_SUGGESTIONS)
+        .map(file_to_suggestion)
+        .collect()
+}
+
+fn rank_files(files: Vec<String>, pattern: &str) -> Vec<String> {
+    if pattern.is_empty() {
+        return files;
+    }
+    let matcher = fuzzy_matcher::skim::SkimMatcherV2::default();
+    let mut scored: Vec<(String, i64)> = files
+        .into_iter()
+        .filter_map(|file| {
+            let score = matcher.fuzzy_match(&file, pattern)?;
+            Some((file, score))
+        })
+        .collect();
+    scored.sort_by(|a, b| {
+        b.1.cmp(&a.1)
+            .then_with(|| a.0.len().cmp(&b.0.len()))
+            .then_with(|| a.0.cmp(&b.0))
+    });
+    scored.into_iter().map(|(file, _)| file).collect()
+}
+
+fn file_to_suggestion(path: String) -> CommandSuggestion {
+    CommandSuggestion {
+        label: path.clone(),
+        description: "引用文件".to_string(),
+        insert: path,
+        kind: CommandSuggestionKind::Mention,
+    }
+}
+
+/// 每次按键都会重新计算补全，遍历结果按工作区缓存，避免大目录拖慢输入。
+fn cached_workspace_files(root: &Path, refresh: bool) -> Vec<String> {
+    let Ok(mut cache) = FILE_LIST.lock() else {
+        return list_workspace_files(root);
+    };
+    let fresh = cache
+        .as_ref()
+        .is_some_and(|cached| cached.root == root && cached.listed_at.elapsed() < FILE_LIST_TTL);
+    if refresh || !fresh {
+        *cache = Some(FileListCache {
+            root: root.to_path_buf(),
+            files: list_workspace_files(root),
+            listed_at: Instant::now(),
+        });
+    }
+    cache
+        .as_ref()
+        .map(|cached| cached.files.clone())
+        .unwrap_or_default()
+}
+
+fn list_workspace_files(root: &Path) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut entries: Vec<_> = entries.flatten().collect();
+        entries.sort_by_key(|entry| entry.file_name());
+        for entry in entries {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') {
+                continue;
+            }
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                if !SKIPPED_DIRS.contains(&name.as_str()) {
+                    stack.push(entry.path());
+                }
+            } else if file_type.is_file()
+                && let Ok(rel) = entry.path().strip_prefix(root)
+            {
+                files.push(rel.to_string_lossy().to_string());
+                if files.len() >= MAX_WORKSPACE_FILES {
+                    return files;
+                }
+            }
+        }
+    }
+    files.sort();
+    files
+}
@@ -1,8 +1,10 @@
 pub(crate) mod command_input;
 pub(crate) mod command_suggestions;
 mod commands_suggestions;
+mod mention_suggestions;
 
 pub(crate) use commands_suggestions::command_suggestions_for_input;
+pub(crate) use mention_suggestions::{mention_at_cursor, mention_suggestions_for_input};
 
 #[derive(Copy, Clone)]
 pub(crate) struct CommandSpec {
@@ -24,6 +26,8 @@ pub(crate) struct CommandSuggestion {
 pub(crate) enum CommandSuggestionKind {
     Command,
     Argument,
+    /// 输入中的 `@路径` 文件引用
+    Mention,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -83,7 +87,85 @@ const COMMANDS: &[CommandSpec] = &[
     CommandSpec {
         name: "/list-conv",
         args: "",
-        description: "列出所有对话",
+        description: "列出所有对话及其标题",
+        arg_provider: None,
+    },
+    CommandSpec {
+        name: "/image",
+        args: "<path>",
+        description: "附加图片，随下一条消息发送（也可 Ctrl+V 粘贴剪贴板图片）",
+        arg_provider: None,
+    },
+    CommandSpec {
+        name: "/compare",
+        args: "<模型...|off>",
+        description: "对比模式：下一条消息同时发给 2～3 个模型，分栏比较后保留其一",
+        arg_provider: None,
+    },
+    CommandSpec {
+        name: "/reasoning",
+        args: "[show|hide]",
+        description: "展开或折叠模型的思考过程",
+        arg_provider: None,
+    },
+    CommandSpec {
+        name: "/continue",
+        args: "[N]",
+        description: "工具循环因上限暂停后，再允许执行 N 步（默认 5）",
+        arg_provider: None,
+    },
+    CommandSpec {
+        name: "/limits",
+        args: "[steps=N] [time=秒] [tokens=N] [repeat=N] [depth=N]|reset",
+        description: "查看或调整本对话单轮工具循环的上限，0 表示不限制",
+        arg_provider: None,
+    },
+    CommandSpec {
+        name: "/prompt",
+        args: "new|edit|dup|rename|delete <名称> …|reload",
+        description: "新建、编辑、复制、重命名或删除系统提示词（F5 列表中也可操作）",
+        arg_provider: None,
+    },
+    CommandSpec {
+        name: "/tools",
+        args: "[工具名|-工具名 …]|reset",
+        description: "开关本对话可用的工具；不带参数时打开工具面板（F11）",
+        arg_provider: None,
+    },
+    CommandSpec {
+        name: "/title",
+        args: "[标题]",
+        description: "查看或修改本对话的标题",
+        arg_provider: None,
+    },
+    CommandSpec {
+        name: "/regen",
+        args: "",
+        description: "重新生成最后一轮回答，原回答保留，导航模式下用 [ / ] 切换",
+        arg_provider: None,
+    },
+    CommandSpec {
+        name: "/tree",
+        args: "",
+        description: "打开对话分支树，查看分叉关系并对比两个分支",
+        arg_provider: None,
+    },
+    CommandSpec {
+        name: "/compact",
+        args: "",
+        description: "将较早的对话压缩为摘要以继续长对话",
+        arg_provider: None,
+    },
+    CommandSpec {
+        name: "/kernel-restart",
+        args: "",
+        description: "重启当前对话的 Python 常驻内核",
+        arg_provider: None,
+    },
+    CommandSpec {
+        name: "/changes",
+        args: "",
+        description: "审阅隔离工作区的改动并同步到宿主机",
         arg_provider: None,
     },
 ];
@@ -118,7 +200,12 @@ pub(crate) fn list_conversation_ids() -> Result<Vec<String>, Box<dyn std::error:
     let entries = std::fs::read_dir(dir)?;
     let mut ids = Vec::new();
     for entry in entries.flatten() {
-        if let Some(stem) = entry.path().file_stem() {
+        let path = entry.path();
+        // 跳过写入中的临时文件与被隔离的损坏文件
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        if let Some(stem) = path.file_stem() {
             ids.push(stem.to_string_lossy().to_string());
         }
     }
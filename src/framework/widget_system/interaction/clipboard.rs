@@ -33,6 +33,24 @@ pub fn get() -> Option<String> {
     None
 }
 
+/// 读取剪贴板中的图片并编码为 PNG。
+pub fn get_image_png() -> Option<Vec<u8>> {
+    let mut clipboard = Clipboard::new().ok()?;
+    let image = clipboard.get_image().ok()?;
+    encode_png(image.width as u32, image.height as u32, &image.bytes)
+}
+
+fn encode_png(width: u32, height: u32, rgba: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().ok()?;
+    writer.write_image_data(rgba).ok()?;
+    writer.finish().ok()?;
+    Some(out)
+}
+
 fn set_arboard(text: &str) -> bool {
     let Ok(mut clipboard) = Clipboard::new() else {
         return false;
@@ -7,6 +7,9 @@ use crate::framework::widget_system::runtime::state::{App, Focus};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use tui_textarea::TextArea;
 
+/// 只发送图片、未输入文字时使用的默认提问。
+const IMAGE_ONLY_PROMPT: &str = "请查看图片。";
+
 pub fn handle_key(key: KeyEvent, app: &mut App) -> Result<bool, Box<dyn std::error::Error>> {
     if app.pending_code_exec.is_some() || app.pending_file_patch.is_some() {
         return Ok(false);
@@ -79,15 +82,32 @@ fn cut_selection(app: &mut App) {
 }
 
 fn paste_clipboard(app: &mut App) {
-    if let Some(text) = clipboard::get() {
+    if let Some(text) = clipboard::get().filter(|text| !text.is_empty()) {
         app.input.set_yank_text(text);
         app.input.paste();
+    } else if let Some(png) = clipboard::get_image_png() {
+        attach_clipboard_image(app, &png);
     } else {
         maybe_notice_clipboard_unavailable(app);
     }
     refresh_command_suggestions(app);
 }
 
+fn attach_clipboard_image(app: &mut App, png: &[u8]) {
+    let name = format!("clipboard-{}.png", chrono::Local::now().format("%H%M%S"));
+    match crate::attachments::store_image(png, "image/png", name) {
+        Ok(attachment) => {
+            let label = crate::attachments::placeholder(&attachment);
+            app.pending_attachments.push(attachment);
+            crate::framework::widget_system::notice::push_notice(
+                app,
+                format!("已添加图片：{label}（随下一条消息发送）"),
+            );
+        }
+        Err(err) => crate::framework::widget_system::notice::push_notice(app, err),
+    }
+}
+
 fn maybe_notice_clipboard_unavailable(app: &mut App) {
     if !is_ssh() {
         return;
@@ -156,16 +176,62 @@ fn handle_enter_key(app: &mut App) -> Result<bool, Box<dyn std::error::Error>> {
     let line = line.trim_end().to_string();
     app.input = TextArea::default();
     refresh_command_suggestions(app);
+    if let Some(key) = app.editing_prompt.take() {
+        crate::services::runtime_prompts::submit_inline_edit(app, key, line);
+        return Ok(false);
+    }
     if line.trim().is_empty() {
+        if !app.pending_attachments.is_empty() {
+            app.pending_send = Some(IMAGE_ONLY_PROMPT.to_string());
+        }
         return Ok(false);
     }
     if line.starts_with('/') {
         return handle_command_line(&line, app);
     }
+    attach_mentioned_files(app, &line);
     app.pending_send = Some(line);
     Ok(false)
 }
 
+/// 把消息中 `@路径` 引用的工作区文件作为附件随消息发送，超出总大小上限的文件跳过。
+fn attach_mentioned_files(app: &mut App, line: &str) {
+    let paths = crate::attachments::mentioned_paths(line);
+    if paths.is_empty() {
+        return;
+    }
+    let Some(root) = crate::services::workspace::cached_workspace_root() else {
+        return;
+    };
+    let mut total: u64 = app
+        .pending_attachments
+        .iter()
+        .filter(|a| !crate::attachments::is_image(a))
+        .map(crate::attachments::stored_size)
+        .sum();
+    let mut errors = Vec::new();
+    for path in paths {
+        if !root.join(&path).is_file() {
+            continue;
+        }
+        match crate::attachments::attach_text_file(&root, &path) {
+            Ok(attachment) => {
+                let size = crate::attachments::stored_size(&attachment);
+                if total + size > crate::attachments::MAX_MENTION_TOTAL_BYTES as u64 {
+                    errors.push(format!("引用文件总大小超过上限，已跳过：{path}"));
+                    continue;
+                }
+                total += size;
+                app.pending_attachments.push(attachment);
+            }
+            Err(err) => errors.push(err),
+        }
+    }
+    if !errors.is_empty() {
+        crate::framework::widget_system::notice::push_notice(app, errors.join("\n"));
+    }
+}
+
 fn handle_scroll_key(key: KeyEvent, app: &mut App) {
     match key.code {
         KeyCode::Home => scroll_home(app, key.modifiers.contains(KeyModifiers::CONTROL)),
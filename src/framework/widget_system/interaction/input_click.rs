@@ -5,7 +5,7 @@ use unicode_width::UnicodeWidthChar;
 
 pub fn update_input_view_top(app: &mut App, input_area: Rect) {
     let inner = input_inner_area(input_area);
-    let height = inner.height.max(1) as u16;
+    let height = inner.height.max(1);
     let cursor_row = app.input.cursor().0 as u16;
     let prev_top = app.input_view_top_row;
     app.input_view_top_row = next_scroll_top(prev_top, cursor_row, height);
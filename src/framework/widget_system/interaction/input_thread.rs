@@ -1,11 +1,18 @@
 use crate::framework::widget_system::runtime::events::RuntimeEvent;
 use crossterm::event;
 use std::sync::Arc;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::Sender;
 use std::thread::JoinHandle;
 use std::time::Duration;
 
+/// 运行外部编辑器期间暂停读取终端事件，避免抢走编辑器的按键。
+static PAUSED: AtomicBool = AtomicBool::new(false);
+
+pub(crate) fn set_input_paused(paused: bool) {
+    PAUSED.store(paused, Ordering::Relaxed);
+}
+
 pub(crate) fn start_input_thread(
     tx: Sender<RuntimeEvent>,
     stop: Arc<AtomicBool>,
@@ -16,9 +23,13 @@ pub(crate) fn start_input_thread(
 fn run_input_loop(tx: Sender<RuntimeEvent>, stop: Arc<AtomicBool>) {
     let poll_timeout = Duration::from_millis(50);
     loop {
-        if stop.load(std::sync::atomic::Ordering::Relaxed) {
+        if stop.load(Ordering::Relaxed) {
             return;
         }
+        if PAUSED.load(Ordering::Relaxed) {
+            std::thread::sleep(poll_timeout);
+            continue;
+        }
         if !poll_once(&tx, poll_timeout) {
             return;
         }
@@ -7,7 +7,10 @@ pub(crate) enum ShortcutScope {
     Jump,
     Model,
     Prompt,
+    Tools,
+    Tree,
     QuestionReview,
+    WorkspaceChanges,
     CodeExec,
     Help,
 }
@@ -78,12 +81,12 @@ const SHORTCUTS: &[Shortcut] = &[
     Shortcut {
         scope: ShortcutScope::Chat,
         keys: "F5",
-        description: "系统提示词列表",
+        description: "系统提示词列表（可新建、编辑、复制、重命名、删除）",
     },
     Shortcut {
         scope: ShortcutScope::Chat,
         keys: "F6",
-        description: "终止生成",
+        description: "终止生成 / 停止等待子任务",
     },
     Shortcut {
         scope: ShortcutScope::Chat,
@@ -95,6 +98,11 @@ const SHORTCUTS: &[Shortcut] = &[
         keys: "F7",
         description: "弹窗终端",
     },
+    Shortcut {
+        scope: ShortcutScope::Chat,
+        keys: "F11",
+        description: "本对话的工具开关（Tab 标题中 ⚡ 表示可执行代码，✎ 表示可修改文件）",
+    },
     Shortcut {
         scope: ShortcutScope::Chat,
         keys: "F10 / Shift+F10",
@@ -125,6 +133,21 @@ const SHORTCUTS: &[Shortcut] = &[
         keys: "h / l",
         description: "切换对话",
     },
+    Shortcut {
+        scope: ShortcutScope::Nav,
+        keys: "r",
+        description: "重新生成最后一轮回答（原回答保留为候选）",
+    },
+    Shortcut {
+        scope: ShortcutScope::Nav,
+        keys: "[ / ]",
+        description: "切换所选这一轮的上一个 / 下一个回答（标签上显示 ‹2/3›）",
+    },
+    Shortcut {
+        scope: ShortcutScope::Nav,
+        keys: "t",
+        description: "打开对话分支树（/tree）",
+    },
     Shortcut {
         scope: ShortcutScope::Summary,
         keys: "↑/↓",
@@ -180,6 +203,36 @@ const SHORTCUTS: &[Shortcut] = &[
         keys: "Enter",
         description: "确认系统提示词",
     },
+    Shortcut {
+        scope: ShortcutScope::Tools,
+        keys: "Space / Enter",
+        description: "开启 / 关闭当前工具",
+    },
+    Shortcut {
+        scope: ShortcutScope::Tools,
+        keys: "r",
+        description: "恢复为启动参数 --enable 的默认设置",
+    },
+    Shortcut {
+        scope: ShortcutScope::Tree,
+        keys: "Enter",
+        description: "进入所选对话",
+    },
+    Shortcut {
+        scope: ShortcutScope::Tree,
+        keys: "[ / ]",
+        description: "跳到上一个 / 下一个同级分支",
+    },
+    Shortcut {
+        scope: ShortcutScope::Tree,
+        keys: "m",
+        description: "标记 / 取消标记所选分支",
+    },
+    Shortcut {
+        scope: ShortcutScope::Tree,
+        keys: "d",
+        description: "对比已标记的分支与所选分支（未标记时与父对话对比），Esc 返回",
+    },
     Shortcut {
         scope: ShortcutScope::QuestionReview,
         keys: "↑/↓",
@@ -215,6 +268,16 @@ const SHORTCUTS: &[Shortcut] = &[
         keys: "Enter / Esc",
         description: "提交 / 取消",
     },
+    Shortcut {
+        scope: ShortcutScope::WorkspaceChanges,
+        keys: "Space / A",
+        description: "选择 / 全选待同步的改动",
+    },
+    Shortcut {
+        scope: ShortcutScope::WorkspaceChanges,
+        keys: "Enter / Esc",
+        description: "同步到宿主机 / 关闭",
+    },
     Shortcut {
         scope: ShortcutScope::CodeExec,
         keys: "鼠标点击",
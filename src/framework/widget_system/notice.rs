@@ -9,6 +9,7 @@ use textwrap::wrap;
 use unicode_width::UnicodeWidthStr;
 
 const NOTICE_TTL: Duration = Duration::from_secs(2);
+const LONG_NOTICE_TTL: Duration = Duration::from_secs(10);
 const MIN_WIDTH: usize = 16;
 const MIN_HEIGHT: usize = 3;
 
@@ -19,6 +20,14 @@ pub(crate) fn push_notice(app: &mut App, text: impl Into<String>) {
     });
 }
 
+/// 需要用户阅读或决定下一步的提示（如沙箱镜像缺失、工具循环因上限暂停），停留时间更长。
+pub(crate) fn push_long_notice(app: &mut App, text: impl Into<String>) {
+    app.notice = Some(Notice {
+        text: text.into(),
+        expires_at: Instant::now() + LONG_NOTICE_TTL,
+    });
+}
+
 pub(crate) fn draw_notice(f: &mut Frame<'_>, area: Rect, app: &mut App, theme: &RenderTheme) {
     let Some(notice) = active_notice(app) else {
         return;
@@ -5,10 +5,14 @@ pub(crate) enum OverlayKind {
     Model,
     Prompt,
     QuestionReview,
+    WorkspaceChanges,
     CodeExec,
     FilePatch,
     Terminal,
     Help,
+    Compare,
+    Tools,
+    Tree,
 }
 
 #[derive(Copy, Clone, Debug, Default)]
@@ -32,6 +36,7 @@ impl OverlayState {
                 OverlayKind::Summary
                     | OverlayKind::Jump
                     | OverlayKind::QuestionReview
+                    | OverlayKind::WorkspaceChanges
                     | OverlayKind::Terminal
                     | OverlayKind::Help
             )
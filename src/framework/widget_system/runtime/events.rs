@@ -14,6 +14,17 @@ pub(crate) enum LlmEvent {
         calls: Vec<ToolCall>,
         usage: Option<Usage>,
     },
+    /// 本次尝试失败，将在 delay_ms 后发起第 attempt 次请求；此前输出的部分内容作废。
+    Retrying {
+        attempt: u32,
+        max_attempts: u32,
+        delay_ms: u64,
+    },
+    /// 超时未收到数据，流已中止；`will_retry` 为 true 时随后会收到 Retrying。
+    Stalled {
+        reason: String,
+        will_retry: bool,
+    },
 }
 
 pub(crate) struct UiEvent {
@@ -27,6 +38,7 @@ pub(crate) enum RuntimeEvent {
     Llm(UiEvent),
     Preheat(PreheatResult),
     Terminal(crate::framework::widget_system::widgets::terminal::TerminalEvent),
+    Title(crate::services::runtime_titles::TitleEvent),
 }
 
 pub(crate) struct EventBatch {
@@ -34,6 +46,7 @@ pub(crate) struct EventBatch {
     pub(crate) llm: Vec<UiEvent>,
     pub(crate) preheat: Vec<PreheatResult>,
     pub(crate) terminal: Vec<crate::framework::widget_system::widgets::terminal::TerminalEvent>,
+    pub(crate) titles: Vec<crate::services::runtime_titles::TitleEvent>,
 }
 
 impl EventBatch {
@@ -43,6 +56,7 @@ impl EventBatch {
             llm: Vec::new(),
             preheat: Vec::new(),
             terminal: Vec::new(),
+            titles: Vec::new(),
         }
     }
 
@@ -52,6 +66,7 @@ impl EventBatch {
             RuntimeEvent::Llm(e) => self.llm.push(e),
             RuntimeEvent::Preheat(e) => self.preheat.push(e),
             RuntimeEvent::Terminal(e) => self.terminal.push(e),
+            RuntimeEvent::Title(e) => self.titles.push(e),
         }
     }
 
@@ -60,6 +75,7 @@ impl EventBatch {
         self.llm.clear();
         self.preheat.clear();
         self.terminal.clear();
+        self.titles.clear();
     }
 }
 
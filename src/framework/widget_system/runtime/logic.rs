@@ -1,7 +1,10 @@
+use crate::conversation::UsageRecord;
 use crate::types::ToolCall;
 use crate::types::{Message, ROLE_ASSISTANT};
 use crate::framework::widget_system::runtime::events::LlmEvent;
-use crate::framework::widget_system::runtime::state::App;
+use crate::framework::widget_system::runtime::state::{App, LlmRetryStatus};
+use crate::llm::pricing::{ModelPricing, format_cost};
+use std::time::{Duration, Instant};
 
 pub enum StreamAction {
     None,
@@ -9,43 +12,104 @@ pub enum StreamAction {
     ToolCalls(Vec<ToolCall>),
 }
 
-pub fn handle_stream_event(app: &mut App, event: LlmEvent, elapsed_ms: u64) -> StreamAction {
+pub fn handle_stream_event(
+    app: &mut App,
+    event: LlmEvent,
+    elapsed_ms: u64,
+    pricing: Option<&ModelPricing>,
+) -> StreamAction {
     match event {
         LlmEvent::Chunk(s) => handle_chunk(app, &s),
         LlmEvent::ReasoningChunk(s) => handle_reasoning_chunk(app, &s),
         LlmEvent::Error(err) => handle_error(app, &err),
-        LlmEvent::Done { usage } => handle_done(app, usage.as_ref(), elapsed_ms),
+        LlmEvent::Done { usage } => handle_done(app, usage.as_ref(), elapsed_ms, pricing),
         LlmEvent::ToolCalls { calls, usage } => {
-            handle_tool_calls(app, calls, usage.as_ref(), elapsed_ms)
+            handle_tool_calls(app, calls, usage.as_ref(), elapsed_ms, pricing)
         }
+        LlmEvent::Retrying {
+            attempt,
+            max_attempts,
+            delay_ms,
+        } => handle_retrying(app, attempt, max_attempts, delay_ms),
+        LlmEvent::Stalled { reason, will_retry } => handle_stalled(app, &reason, will_retry),
     }
 }
 
 fn handle_chunk(app: &mut App, chunk: &str) -> StreamAction {
+    app.llm_retry = None;
     app.stream_buffer.push_str(chunk);
     flush_completed_lines(app);
     StreamAction::None
 }
 
 fn handle_reasoning_chunk(app: &mut App, chunk: &str) -> StreamAction {
+    app.llm_retry = None;
     append_to_pending_reasoning(app, chunk);
     StreamAction::None
 }
 
 fn handle_error(app: &mut App, err: &str) -> StreamAction {
+    app.compaction = None;
     set_pending_assistant_content(app, err);
     clear_stream_state(app);
     StreamAction::Done
 }
 
+fn handle_retrying(app: &mut App, attempt: u32, max_attempts: u32, delay_ms: u64) -> StreamAction {
+    discard_partial_output(app);
+    app.llm_retry = Some(LlmRetryStatus {
+        attempt,
+        max_attempts,
+        resume_at: Instant::now() + Duration::from_millis(delay_ms),
+    });
+    StreamAction::None
+}
+
+/// 停滞且不再重试时保留已输出的部分并标记为停滞；会重试时交给随后的 Retrying 处理。
+fn handle_stalled(app: &mut App, reason: &str, will_retry: bool) -> StreamAction {
+    if will_retry {
+        return StreamAction::None;
+    }
+    flush_remaining_buffer(app);
+    let has_partial = app
+        .pending_assistant
+        .and_then(|idx| app.messages.get(idx))
+        .is_some_and(|msg| !msg.content.is_empty());
+    if !has_partial {
+        set_pending_assistant_content(app, reason);
+    }
+    if let Some(idx) = app.pending_assistant.take() {
+        let stats = format!("已停滞 · {reason}");
+        app.assistant_stats.insert(idx, stats);
+        app.dirty_indices.push(idx);
+    }
+    app.compaction = None;
+    clear_stream_state(app);
+    StreamAction::Done
+}
+
+/// 重试会从头重新生成回复，清空失败尝试已流式输出的内容，避免重复。
+fn discard_partial_output(app: &mut App) {
+    app.stream_buffer.clear();
+    if let Some(idx) = app.pending_reasoning
+        && let Some(msg) = app.messages.get_mut(idx)
+    {
+        msg.content.clear();
+        app.dirty_indices.push(idx);
+    }
+    set_pending_assistant_content(app, "");
+}
+
 fn handle_done(
     app: &mut App,
     usage: Option<&crate::types::Usage>,
     elapsed_ms: u64,
+    pricing: Option<&ModelPricing>,
 ) -> StreamAction {
     flush_remaining_buffer(app);
-    record_assistant_stats(app, usage, elapsed_ms);
-    update_usage_totals(app, usage);
+    let cost = usage.zip(pricing).map(|(u, p)| p.cost(u));
+    record_assistant_stats(app, usage, elapsed_ms, cost);
+    update_usage_totals(app, usage, cost);
     clear_stream_state(app);
     StreamAction::Done
 }
@@ -55,10 +119,13 @@ fn handle_tool_calls(
     calls: Vec<ToolCall>,
     usage: Option<&crate::types::Usage>,
     elapsed_ms: u64,
+    pricing: Option<&ModelPricing>,
 ) -> StreamAction {
     flush_remaining_buffer(app);
-    attach_tool_calls(app, calls.clone(), elapsed_ms, usage);
-    update_usage_totals(app, usage);
+    let cost = usage.zip(pricing).map(|(u, p)| p.cost(u));
+    app.agent_turn.record_round(&calls);
+    attach_tool_calls(app, calls.clone(), elapsed_ms, usage, cost);
+    update_usage_totals(app, usage, cost);
     clear_stream_state(app);
     StreamAction::ToolCalls(calls)
 }
@@ -68,8 +135,13 @@ fn attach_tool_calls(
     calls: Vec<ToolCall>,
     elapsed_ms: u64,
     usage: Option<&crate::types::Usage>,
+    cost: Option<f64>,
 ) {
-    let stats = format_stats(usage, elapsed_ms);
+    let stats = format!(
+        "{} · 第 {} 步",
+        format_stats(usage, elapsed_ms, cost),
+        app.agent_turn.steps
+    );
     if let Some(idx) = app.pending_assistant.take() {
         if let Some(msg) = app.messages.get_mut(idx) {
             msg.tool_calls = Some(calls);
@@ -78,29 +150,64 @@ fn attach_tool_calls(
     }
 }
 
-fn record_assistant_stats(app: &mut App, usage: Option<&crate::types::Usage>, elapsed_ms: u64) {
-    let stats = format_stats(usage, elapsed_ms);
+fn record_assistant_stats(
+    app: &mut App,
+    usage: Option<&crate::types::Usage>,
+    elapsed_ms: u64,
+    cost: Option<f64>,
+) {
+    let mut stats = format_stats(usage, elapsed_ms, cost);
+    let steps = app.agent_turn.steps;
+    if steps > 0 && app.compaction.is_none() {
+        stats.push_str(&format!(" · 共 {steps} 步"));
+    }
     if let Some(idx) = app.pending_assistant.take() {
         app.assistant_stats.insert(idx, stats);
     }
 }
 
-fn update_usage_totals(app: &mut App, usage: Option<&crate::types::Usage>) {
+fn update_usage_totals(app: &mut App, usage: Option<&crate::types::Usage>, cost: Option<f64>) {
     let Some(u) = usage else {
         return;
     };
-    let p = u.prompt_tokens.unwrap_or(0);
-    let c = u.completion_tokens.unwrap_or(0);
-    let t = u.total_tokens.unwrap_or(p + c);
+    let record = UsageRecord {
+        model: app.model_key.clone(),
+        timestamp: unix_now(),
+        prompt_tokens: u.prompt_tokens.unwrap_or(0),
+        completion_tokens: u.completion_tokens.unwrap_or(0),
+        cached_tokens: u.cached_tokens.unwrap_or(0),
+        cost,
+    };
+    add_usage_totals(app, &record);
+    app.agent_turn
+        .add_tokens(record.prompt_tokens + record.completion_tokens);
+    app.usage_log.push(record);
+}
+
+/// 累加一条用量记录到标签页合计；加载历史对话时也用它恢复合计。
+pub fn add_usage_totals(app: &mut App, record: &UsageRecord) {
+    let p = record.prompt_tokens;
+    let c = record.completion_tokens;
     app.total_prompt_tokens = app.total_prompt_tokens.saturating_add(p);
     app.total_completion_tokens = app.total_completion_tokens.saturating_add(c);
-    app.total_tokens = app.total_tokens.saturating_add(t);
+    app.total_tokens = app.total_tokens.saturating_add(p + c);
+    if let Some(cost) = record.cost {
+        app.total_cost = Some(app.total_cost.unwrap_or(0.0) + cost);
+    }
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
 }
 
 fn clear_stream_state(app: &mut App) {
     app.pending_assistant = None;
     app.pending_reasoning = None;
     app.stream_buffer.clear();
+    app.llm_retry = None;
     app.active_request = None;
     app.busy = false;
     app.busy_since = None;
@@ -118,6 +225,8 @@ pub fn stop_stream(app: &mut App) -> bool {
     }
     app.pending_reasoning = None;
     app.stream_buffer.clear();
+    app.llm_retry = None;
+    app.compaction = None;
     app.busy = false;
     app.busy_since = None;
     true
@@ -135,19 +244,50 @@ pub fn timer_text(app: &App) -> String {
 }
 
 pub fn build_label_suffixes(app: &App, timer_text: &str) -> Vec<(usize, String)> {
-    let mut out = Vec::new();
+    let mut out = crate::services::runtime_variants::variant_labels(app);
     for (idx, stats) in &app.assistant_stats {
-        out.push((*idx, stats.clone()));
+        push_label_suffix(&mut out, *idx, stats.clone());
     }
     if app.busy
         && let Some(idx) = app.pending_assistant
         && !timer_text.is_empty()
     {
-        out.push((idx, timer_text.to_string()));
+        let text = match app.llm_retry.as_ref() {
+            Some(retry) => format!("{timer_text} · {}", format_retry(retry)),
+            None => timer_text.to_string(),
+        };
+        push_label_suffix(&mut out, idx, text);
     }
     out
 }
 
+/// 同一条消息已有后缀（如回答候选「‹2/3›」）时用「 · 」接在后面。
+fn push_label_suffix(out: &mut Vec<(usize, String)>, idx: usize, text: String) {
+    match out.iter_mut().find(|(i, _)| *i == idx) {
+        Some((_, suffix)) => {
+            suffix.push_str(" · ");
+            suffix.push_str(&text);
+        }
+        None => out.push((idx, text)),
+    }
+}
+
+fn format_retry(retry: &LlmRetryStatus) -> String {
+    let remaining = retry
+        .resume_at
+        .saturating_duration_since(Instant::now())
+        .as_secs_f64()
+        .ceil() as u64;
+    if remaining > 0 {
+        format!(
+            "{remaining}s 后重试（第 {}/{} 次）",
+            retry.attempt, retry.max_attempts
+        )
+    } else {
+        format!("重试中（第 {}/{} 次）", retry.attempt, retry.max_attempts)
+    }
+}
+
 pub fn format_timer(ms: u64) -> String {
     let secs = ms as f64 / 1000.0;
     if secs < 60.0 {
@@ -159,7 +299,7 @@ pub fn format_timer(ms: u64) -> String {
     }
 }
 
-fn format_stats(usage: Option<&crate::types::Usage>, elapsed_ms: u64) -> String {
+fn format_stats(usage: Option<&crate::types::Usage>, elapsed_ms: u64, cost: Option<f64>) -> String {
     let time = format_timer(elapsed_ms);
     let tokens = if let Some(u) = usage {
         let p = u.prompt_tokens.unwrap_or(0);
@@ -169,7 +309,10 @@ fn format_stats(usage: Option<&crate::types::Usage>, elapsed_ms: u64) -> String
     } else {
         "n/a".to_string()
     };
-    format!("{time} · tokens: {tokens}")
+    match cost {
+        Some(cost) => format!("{time} · tokens: {tokens} · {}", format_cost(cost)),
+        None => format!("{time} · tokens: {tokens}"),
+    }
 }
 
 fn flush_completed_lines(app: &mut App) {
@@ -198,6 +341,7 @@ fn append_to_pending_assistant(app: &mut App, text: &str) {
                 content: text.to_string(),
                 tool_call_id: None,
                 tool_calls: None,
+                attachments: Vec::new(),
             });
             app.pending_assistant = Some(app.messages.len().saturating_sub(1));
             if let Some(idx) = app.pending_assistant {
@@ -210,6 +354,7 @@ fn append_to_pending_assistant(app: &mut App, text: &str) {
             content: text.to_string(),
             tool_call_id: None,
             tool_calls: None,
+            attachments: Vec::new(),
         });
         app.pending_assistant = Some(app.messages.len().saturating_sub(1));
         if let Some(idx) = app.pending_assistant {
@@ -259,6 +404,7 @@ fn spawn_new_pending_assistant(app: &mut App) {
         content: String::new(),
         tool_call_id: None,
         tool_calls: None,
+        attachments: Vec::new(),
     });
     app.pending_assistant = Some(app.messages.len().saturating_sub(1));
     if let Some(idx) = app.pending_assistant {
@@ -272,6 +418,7 @@ fn push_new_reasoning(app: &mut App, text: &str) {
         content: text.to_string(),
         tool_call_id: None,
         tool_calls: None,
+        attachments: Vec::new(),
     });
     app.pending_reasoning = Some(app.messages.len().saturating_sub(1));
     if let Some(idx) = app.pending_reasoning {
@@ -292,6 +439,7 @@ fn set_pending_assistant_content(app: &mut App, content: &str) {
         content: content.to_string(),
         tool_call_id: None,
         tool_calls: None,
+        attachments: Vec::new(),
     });
     app.pending_assistant = Some(app.messages.len().saturating_sub(1));
     if let Some(idx) = app.pending_assistant {
@@ -13,6 +13,7 @@ pub fn seed_perf_messages(app: &mut App) {
             content,
             tool_call_id: None,
             tool_calls: None,
+            attachments: Vec::new(),
         });
     }
     app.follow = false;
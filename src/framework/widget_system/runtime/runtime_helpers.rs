@@ -2,9 +2,14 @@ use crate::render::{RenderCacheEntry, RenderTheme, insert_empty_cache_entry};
 use crate::types::{Message, ROLE_USER};
 use crate::framework::widget_system::runtime::perf::seed_perf_messages;
 use crate::framework::widget_system::runtime::state::{App, Focus};
+use crate::framework::widget_system::interaction::text_utils::truncate_to_width_ellipsis_char;
+use crate::services::runtime_tools::dangerous_tools_marker;
 use std::collections::BTreeMap;
 use std::sync::mpsc;
 
+/// Tab 栏中标题的最大显示宽度
+const TAB_TITLE_WIDTH: usize = 16;
+
 pub(crate) struct TabState {
     pub(crate) conversation_id: String,
     pub(crate) category: String,
@@ -51,6 +56,14 @@ impl TabState {
         }
     }
 
+    /// 消息被整体替换后丢弃渲染缓存并全部重绘，选中第 `selected` 条消息。
+    pub(crate) fn reset_render_state(&mut self, selected: usize) {
+        self.render_cache.clear();
+        self.app.dirty_indices = (0..self.app.messages.len()).collect();
+        self.app.message_history.selected = selected;
+        self.app.chat_selection = None;
+    }
+
     pub(crate) fn apply_cache_shift(&mut self, theme: &RenderTheme) {
         if let Some(shift) = self.app.cache_shift.take() {
             insert_empty_cache_entry(&mut self.render_cache, shift, theme);
@@ -96,12 +109,23 @@ pub(crate) fn visible_tab_indices(tabs: &[TabState], category: &str) -> Vec<usiz
         .collect()
 }
 
+/// 有标题的对话显示序号与标题；开启了危险工具（code_exec ⚡ / modify_file ✎）的对话在标题后标出。
 pub(crate) fn tab_labels_for_category(tabs: &[TabState], category: &str) -> Vec<String> {
     let visible = visible_tab_indices(tabs, category);
     visible
         .iter()
         .enumerate()
-        .map(|(i, _)| format!(" 对话 {} ", i + 1))
+        .map(|(i, &idx)| {
+            let app = &tabs[idx].app;
+            let marker = dangerous_tools_marker(app);
+            match &app.title {
+                Some(title) => {
+                    let title = truncate_to_width_ellipsis_char(title, TAB_TITLE_WIDTH);
+                    format!(" {} {}{} ", i + 1, title, marker)
+                }
+                None => format!(" 对话 {}{} ", i + 1, marker),
+            }
+        })
         .collect()
 }
 
@@ -147,6 +171,13 @@ pub(crate) fn tab_to_conversation(tab: &TabState) -> crate::conversation::Conver
         model_key: Some(tab.app.model_key.clone()),
         prompt_key: Some(tab.app.prompt_key.clone()),
         code_exec_container_id: tab.app.code_exec_container_id.clone(),
+        archived_messages: tab.app.archived_messages.clone(),
+        usage: tab.app.usage_log.clone(),
+        agent_limits: tab.app.agent_limits,
+        tool_flags: tab.app.tool_flags,
+        title: tab.app.title.clone(),
+        answer_variants: crate::services::runtime_variants::synced_variants(&tab.app),
+        fork_of: tab.app.fork_of.clone(),
     }
 }
 
@@ -227,6 +258,9 @@ fn apply_message_removals(tab_state: &mut TabState, remove: &[usize]) {
         }
     }
     shift_stats_after_removals(&mut app.assistant_stats, remove);
+    if let Some(first) = remove.iter().min() {
+        app.answer_variants.retain(|idx, _| idx < first);
+    }
 }
 
 fn reset_edit_state(app: &mut App, user_text: &str) {
@@ -241,6 +275,7 @@ fn reset_edit_state(app: &mut App, user_text: &str) {
 
 fn clear_stream_state(app: &mut App) {
     app.stream_buffer.clear();
+    app.compaction = None;
     app.busy = false;
     app.busy_since = None;
 }
@@ -4,11 +4,18 @@ use crate::framework::widget_system::runtime::events::{RuntimeEvent, UiEvent};
 use crate::framework::widget_system::runtime::runtime_helpers::TabState;
 use crate::framework::widget_system::runtime_tick::{
     ActiveFrameData, build_exec_header_note, collect_stream_events_from_batch, finalize_done_tabs,
-    preheat_inactive_tabs, prepare_active_frame, sync_code_exec_overlay,
-    sync_file_patch_overlay, sync_question_review_overlay, update_code_exec_results,
-    update_tab_widths,
+    preheat_inactive_tabs, prepare_active_frame, sync_code_exec_overlay, sync_compare_overlay,
+    sync_file_patch_overlay, sync_question_review_overlay, sync_tools_overlay, sync_tree_overlay,
+    sync_workspace_changes_overlay, update_code_exec_results, update_tab_widths,
 };
 use crate::framework::widget_system::runtime::runtime_view::ViewState;
+use crate::services::runtime_autosave::autosave_done_tabs;
+use crate::services::runtime_compact::apply_compactions;
+use crate::services::runtime_compare::{record_compare_finish, start_compare};
+use crate::services::runtime_delegation::{
+    DelegationParams, settle_delegations, spawn_pending_delegations,
+};
+use crate::services::runtime_titles::request_titles;
 use crate::services::runtime_yolo::auto_finalize_code_exec;
 use crate::services::tool_service::ToolService;
 use ratatui::layout::Rect;
@@ -22,6 +29,7 @@ pub(crate) struct ProcessStreamUpdatesParams<'a> {
     pub theme: &'a RenderTheme,
     pub msg_width: usize,
     pub registry: &'a crate::model_registry::ModelRegistry,
+    pub prompt_registry: &'a crate::llm::prompts::PromptRegistry,
     pub args: &'a Args,
     pub tx: &'a mpsc::Sender<RuntimeEvent>,
     pub preheat_tx: &'a mpsc::Sender<crate::framework::widget_system::runtime::runtime_helpers::PreheatTask>,
@@ -42,8 +50,12 @@ pub(crate) struct ActiveFrameDataParams<'a> {
 pub(crate) fn process_stream_updates(
     params: ProcessStreamUpdatesParams<'_>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let (_processed, done_tabs, tool_queue) =
-        collect_stream_events_from_batch(params.llm_events, params.tabs, params.theme);
+    let (_processed, done_tabs, tool_queue) = collect_stream_events_from_batch(
+        params.llm_events,
+        params.tabs,
+        params.theme,
+        params.registry,
+    );
     apply_tool_queue(
         params.tabs,
         params.registry,
@@ -51,9 +63,37 @@ pub(crate) fn process_stream_updates(
         params.tx,
         tool_queue,
     );
+    spawn_pending_delegations(DelegationParams {
+        tabs: params.tabs,
+        registry: params.registry,
+        prompt_registry: params.prompt_registry,
+        args: params.args,
+        tx: params.tx,
+    });
     update_code_exec_results(params.tabs);
     maybe_auto_finalize(params.tabs, params.registry, params.args, params.tx);
     finalize_done_tabs(params.tabs, &done_tabs)?;
+    request_titles(params.tabs, &done_tabs, params.registry, params.tx);
+    record_compare_finish(params.tabs, &done_tabs);
+    settle_delegations(
+        DelegationParams {
+            tabs: params.tabs,
+            registry: params.registry,
+            prompt_registry: params.prompt_registry,
+            args: params.args,
+            tx: params.tx,
+        },
+        &done_tabs,
+    );
+    apply_compactions(
+        params.tabs,
+        &done_tabs,
+        params.registry,
+        params.args,
+        params.tx,
+    );
+    // 压缩结果与子任务回填都落定后再写盘，免得存下压缩前的历史
+    autosave_done_tabs(params.tabs, &done_tabs, params.active_tab);
     update_tab_widths(params.tabs, params.msg_width);
     preheat_inactive_tabs(
         params.tabs,
@@ -80,15 +120,21 @@ pub(crate) fn active_frame_data(params: ActiveFrameDataParams<'_>) -> ActiveFram
 
 pub(crate) fn handle_pending_line(
     pending_line: Option<String>,
-    tabs: &mut [TabState],
+    tabs: &mut Vec<TabState>,
     active_tab: usize,
     registry: &crate::model_registry::ModelRegistry,
     args: &Args,
     tx: &mpsc::Sender<RuntimeEvent>,
 ) {
-    if let Some(line) = pending_line
-        && let Some(tab_state) = tabs.get_mut(active_tab)
-    {
+    let Some(line) = pending_line else {
+        return;
+    };
+    let compare_mode = tabs
+        .get(active_tab)
+        .is_some_and(|tab| !tab.app.compare_models.is_empty());
+    if compare_mode {
+        start_compare(tabs, active_tab, line, registry, args, tx);
+    } else if let Some(tab_state) = tabs.get_mut(active_tab) {
         tab_state.app.pending_send = Some(line);
         crate::framework::widget_system::runtime_dispatch::start_pending_request(registry, args, tx, tab_state);
     }
@@ -141,4 +187,8 @@ fn sync_overlays_if_needed(
         sync_file_patch_overlay(tabs, active_tab, view);
     }
     sync_question_review_overlay(tabs, active_tab, view);
+    sync_workspace_changes_overlay(tabs, active_tab, view);
+    sync_compare_overlay(tabs, active_tab, view);
+    sync_tools_overlay(tabs, active_tab, view);
+    sync_tree_overlay(tabs, active_tab, view);
 }
@@ -2,12 +2,14 @@ use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEventKind};
 use std::time::{Duration, Instant};
 use crate::framework::widget_system::overlay::{OverlayKind, OverlayState};
 use crate::framework::widget_system::runtime::runtime_view_handlers::{
-    handle_help_key, handle_jump_key, handle_model_key, handle_prompt_key,
-    handle_question_review_key, handle_summary_key,
+    handle_compare_key, handle_help_key, handle_jump_key, handle_model_key, handle_prompt_key,
+    handle_question_review_key, handle_summary_key, handle_tools_key, handle_tree_key,
+    handle_workspace_changes_key,
 };
 use crate::framework::widget_system::interaction::selection_state::SelectionState;
 use crate::framework::widget_system::widgets::jump::jump_message_index;
 use crate::framework::widget_system::widgets::summary::SummarySort;
+use crate::framework::widget_system::widgets::tree::TreeNode;
 pub(crate) struct ViewState {
     pub(crate) overlay: OverlayState,
     pub(crate) summary: SelectionState,
@@ -18,11 +20,35 @@ pub(crate) struct ViewState {
     pub(crate) prompt: SelectionState,
     pub(crate) question_review: SelectionState,
     pub(crate) question_review_detail_scroll: usize,
+    pub(crate) workspace_changes: SelectionState,
+    pub(crate) workspace_changes_detail_scroll: usize,
     pub(crate) help: SelectionState,
+    pub(crate) tools: SelectionState,
+    pub(crate) tree: SelectionState,
+    /// 分支树各行对应的节点，渲染时填充
+    pub(crate) tree_nodes: Vec<TreeNode>,
+    /// 分支树中用 `m` 标记、准备对比的标签页
+    pub(crate) tree_mark: Option<usize>,
+    /// 正在对比的两个标签页
+    pub(crate) tree_diff: Option<(usize, usize)>,
+    pub(crate) tree_diff_scroll: u16,
+    pub(crate) compare_selected: usize,
+    pub(crate) compare_columns: usize,
+    pub(crate) compare_scroll: u16,
     pub(crate) fps: u32,
     fps_frames: u32,
     fps_window_start: Instant,
 }
+/// F5 提示词列表中的管理操作。
+#[derive(Copy, Clone)]
+pub(crate) enum PromptAction {
+    New,
+    Edit,
+    EditExternal,
+    Duplicate,
+    Rename,
+    Delete,
+}
 #[derive(Copy, Clone)]
 pub(crate) enum ViewAction {
     None,
@@ -33,10 +59,17 @@ pub(crate) enum ViewAction {
     CycleModel,
     CycleModelPrev,
     SelectPrompt(usize),
+    ManagePrompt(PromptAction, usize),
     QuestionReviewToggle(usize), QuestionReviewApprove(usize), QuestionReviewReject(usize),
     QuestionReviewApproveAll, QuestionReviewRejectAll, QuestionReviewNextModel(usize),
     QuestionReviewPrevModel(usize),
     QuestionReviewSetAllModel(usize), QuestionReviewSubmit, QuestionReviewCancel,
+    WorkspaceChangesToggle(usize), WorkspaceChangesToggleAll, WorkspaceChangesApply,
+    WorkspaceChangesClose,
+    CompareKeep(usize),
+    CompareDiscard,
+    ToggleTool(usize),
+    ResetTools,
 }
 pub(crate) fn apply_view_action(
     action: ViewAction,
@@ -53,13 +86,17 @@ pub(crate) fn apply_view_action(
         ViewAction::JumpTo(idx) => apply_jump_to(idx, show_system_prompt, tabs, *active_tab),
         ViewAction::ForkMessage(_) => false,
         ViewAction::SelectModel(_) | ViewAction::CycleModel | ViewAction::CycleModelPrev
-        | ViewAction::SelectPrompt(_) => false,
+        | ViewAction::SelectPrompt(_) | ViewAction::ManagePrompt(..) => false,
         ViewAction::QuestionReviewToggle(_) | ViewAction::QuestionReviewApprove(_)
         | ViewAction::QuestionReviewReject(_) | ViewAction::QuestionReviewApproveAll
         | ViewAction::QuestionReviewRejectAll | ViewAction::QuestionReviewNextModel(_)
         | ViewAction::QuestionReviewPrevModel(_)
         | ViewAction::QuestionReviewSetAllModel(_) | ViewAction::QuestionReviewSubmit
         | ViewAction::QuestionReviewCancel => false,
+        ViewAction::WorkspaceChangesToggle(_) | ViewAction::WorkspaceChangesToggleAll
+        | ViewAction::WorkspaceChangesApply | ViewAction::WorkspaceChangesClose => false,
+        ViewAction::CompareKeep(_) | ViewAction::CompareDiscard => false,
+        ViewAction::ToggleTool(_) | ViewAction::ResetTools => false,
         ViewAction::None => false,
     }
 }
@@ -75,7 +112,18 @@ impl ViewState {
             prompt: SelectionState::default(),
             question_review: SelectionState::default(),
             question_review_detail_scroll: 0,
+            workspace_changes: SelectionState::default(),
+            workspace_changes_detail_scroll: 0,
             help: SelectionState::default(),
+            tools: SelectionState::default(),
+            tree: SelectionState::default(),
+            tree_nodes: Vec::new(),
+            tree_mark: None,
+            tree_diff: None,
+            tree_diff_scroll: 0,
+            compare_selected: 0,
+            compare_columns: 0,
+            compare_scroll: 0,
             fps: 0,
             fps_frames: 0,
             fps_window_start: Instant::now(),
@@ -134,9 +182,13 @@ pub(crate) fn handle_view_key(
         Some(OverlayKind::Model) => handle_model_key(view, key),
         Some(OverlayKind::Prompt) => handle_prompt_key(view, key),
         Some(OverlayKind::QuestionReview) => handle_question_review_key(view, key),
+        Some(OverlayKind::WorkspaceChanges) => handle_workspace_changes_key(view, key),
         Some(OverlayKind::CodeExec | OverlayKind::FilePatch) => ViewAction::None,
         Some(OverlayKind::Terminal) => handle_terminal_key(view, key),
         Some(OverlayKind::Help) => handle_help_key(view, key),
+        Some(OverlayKind::Compare) => handle_compare_key(view, key),
+        Some(OverlayKind::Tools) => handle_tools_key(view, key),
+        Some(OverlayKind::Tree) => handle_tree_key(view, key),
     }
 }
 pub(crate) fn handle_view_mouse(
@@ -155,10 +207,17 @@ pub(crate) fn handle_view_mouse(
         Some(OverlayKind::Model) => handle_model_mouse(view, row, kind),
         Some(OverlayKind::Prompt) => handle_prompt_mouse(view, row, kind),
         Some(OverlayKind::QuestionReview) => handle_question_review_mouse(view, row, kind),
+        Some(OverlayKind::WorkspaceChanges) => handle_workspace_changes_mouse(view, row, kind),
         Some(OverlayKind::Help) => handle_help_mouse(view, row, kind),
-        Some(OverlayKind::CodeExec | OverlayKind::FilePatch | OverlayKind::Terminal) | None => {
-            ViewAction::None
-        }
+        Some(OverlayKind::Tools) => handle_tools_mouse(view, row, kind),
+        Some(OverlayKind::Tree) => handle_tree_mouse(view, row, kind),
+        Some(
+            OverlayKind::CodeExec
+            | OverlayKind::FilePatch
+            | OverlayKind::Terminal
+            | OverlayKind::Compare,
+        )
+        | None => ViewAction::None,
     }
 }
 fn handle_function_keys(
@@ -183,6 +242,7 @@ fn handle_function_keys(
         KeyCode::F(2) => Some(toggle_jump(view)),
         KeyCode::F(5) => Some(toggle_prompt(view)),
         KeyCode::F(7) => Some(toggle_overlay(view, OverlayKind::Terminal)),
+        KeyCode::F(11) => Some(toggle_overlay(view, OverlayKind::Tools)),
         _ => None,
     }
 }
@@ -317,9 +377,42 @@ fn handle_question_review_mouse(
     }
     ViewAction::None
 }
+fn handle_workspace_changes_mouse(
+    view: &mut ViewState,
+    row: usize,
+    kind: MouseEventKind,
+) -> ViewAction {
+    if matches!(kind, MouseEventKind::Down(_)) {
+        view.workspace_changes.select(row);
+        view.workspace_changes_detail_scroll = 0;
+    }
+    ViewAction::None
+}
 fn handle_help_mouse(view: &mut ViewState, row: usize, kind: MouseEventKind) -> ViewAction {
     if matches!(kind, MouseEventKind::Moved) {
         view.help.select(row);
     }
     ViewAction::None
 }
+fn handle_tools_mouse(view: &mut ViewState, row: usize, kind: MouseEventKind) -> ViewAction {
+    view.tools.select(row);
+    if matches!(kind, MouseEventKind::Down(_)) {
+        return ViewAction::ToggleTool(row);
+    }
+    ViewAction::None
+}
+fn handle_tree_mouse(view: &mut ViewState, row: usize, kind: MouseEventKind) -> ViewAction {
+    if view.tree_diff.is_some() {
+        return ViewAction::None;
+    }
+    view.tree.select(row);
+    let Some(node) = view.tree_nodes.get(row) else {
+        return ViewAction::None;
+    };
+    if matches!(kind, MouseEventKind::Down(_)) {
+        let tab_index = node.tab_index;
+        view.overlay.close();
+        return ViewAction::SwitchTab(tab_index);
+    }
+    ViewAction::None
+}
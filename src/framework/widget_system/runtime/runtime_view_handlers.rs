@@ -1,6 +1,6 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
-use crate::framework::widget_system::runtime::runtime_view::{ViewAction, ViewState};
+use crate::framework::widget_system::runtime::runtime_view::{PromptAction, ViewAction, ViewState};
 use crate::framework::widget_system::widgets::summary::SummarySort;
 
 const PAGE_STEP: usize = 5;
@@ -150,10 +150,146 @@ pub(crate) fn handle_prompt_key(view: &mut ViewState, key: KeyEvent) -> ViewActi
             view.overlay.close();
             ViewAction::SelectPrompt(view.prompt.selected)
         }
+        KeyCode::Char('d') => {
+            ViewAction::ManagePrompt(PromptAction::Duplicate, view.prompt.selected)
+        }
+        _ => prompt_manage_key(key).map_or(ViewAction::None, |action| {
+            view.overlay.close();
+            ViewAction::ManagePrompt(action, view.prompt.selected)
+        }),
+    }
+}
+
+/// 这些操作需要用到输入框或让出终端，执行前先关闭列表。
+fn prompt_manage_key(key: KeyEvent) -> Option<PromptAction> {
+    match key.code {
+        KeyCode::Char('n') => Some(PromptAction::New),
+        KeyCode::Char('e') => Some(PromptAction::Edit),
+        KeyCode::Char('E') => Some(PromptAction::EditExternal),
+        KeyCode::Char('r') => Some(PromptAction::Rename),
+        KeyCode::Delete => Some(PromptAction::Delete),
+        _ => None,
+    }
+}
+
+/// 工具开关即时生效，面板保持打开以便连续调整。
+pub(crate) fn handle_tools_key(view: &mut ViewState, key: KeyEvent) -> ViewAction {
+    match key.code {
+        KeyCode::Esc => {
+            view.overlay.close();
+            ViewAction::None
+        }
+        KeyCode::Up => {
+            view.tools.move_up();
+            ViewAction::None
+        }
+        KeyCode::Down => {
+            view.tools.move_down();
+            ViewAction::None
+        }
+        KeyCode::Enter | KeyCode::Char(' ') => ViewAction::ToggleTool(view.tools.selected),
+        KeyCode::Char('r') => ViewAction::ResetTools,
         _ => ViewAction::None,
     }
 }
 
+/// 分支树：打开对比视图时方向键改为滚动，Esc 回到树。
+pub(crate) fn handle_tree_key(view: &mut ViewState, key: KeyEvent) -> ViewAction {
+    if view.tree_diff.is_some() {
+        return handle_tree_diff_key(view, key);
+    }
+    match key.code {
+        KeyCode::Esc => close_overlay(view),
+        KeyCode::Up => {
+            view.tree.move_up();
+            ViewAction::None
+        }
+        KeyCode::Down => {
+            view.tree.move_down();
+            ViewAction::None
+        }
+        KeyCode::Enter => handle_tree_enter(view),
+        KeyCode::Char('[') => move_tree_sibling(view, false),
+        KeyCode::Char(']') => move_tree_sibling(view, true),
+        KeyCode::Char('m') => toggle_tree_mark(view),
+        KeyCode::Char('d') => open_tree_diff(view),
+        _ => ViewAction::None,
+    }
+}
+
+fn handle_tree_diff_key(view: &mut ViewState, key: KeyEvent) -> ViewAction {
+    let scroll = &mut view.tree_diff_scroll;
+    match key.code {
+        KeyCode::Esc => view.tree_diff = None,
+        KeyCode::Up => *scroll = scroll.saturating_sub(1),
+        KeyCode::Down => *scroll = scroll.saturating_add(1),
+        KeyCode::PageUp => *scroll = scroll.saturating_sub(PAGE_STEP as u16 * 2),
+        KeyCode::PageDown => *scroll = scroll.saturating_add(PAGE_STEP as u16 * 2),
+        _ => {}
+    }
+    ViewAction::None
+}
+
+fn handle_tree_enter(view: &mut ViewState) -> ViewAction {
+    let Some(node) = view.tree_nodes.get(view.tree.selected) else {
+        return ViewAction::None;
+    };
+    let tab_index = node.tab_index;
+    view.overlay.close();
+    ViewAction::SwitchTab(tab_index)
+}
+
+/// 跳到与当前行同一父对话的上一个 / 下一个分支。
+fn move_tree_sibling(view: &mut ViewState, forward: bool) -> ViewAction {
+    let selected = view.tree.selected;
+    let Some(parent) = view.tree_nodes.get(selected).map(|node| node.parent) else {
+        return ViewAction::None;
+    };
+    let mut siblings = view
+        .tree_nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| node.parent == parent)
+        .map(|(row, _)| row);
+    let target = if forward {
+        siblings.find(|&row| row > selected)
+    } else {
+        siblings.rev().find(|&row| row < selected)
+    };
+    if let Some(row) = target {
+        view.tree.select(row);
+    }
+    ViewAction::None
+}
+
+fn toggle_tree_mark(view: &mut ViewState) -> ViewAction {
+    let Some(node) = view.tree_nodes.get(view.tree.selected) else {
+        return ViewAction::None;
+    };
+    view.tree_mark = if view.tree_mark == Some(node.tab_index) {
+        None
+    } else {
+        Some(node.tab_index)
+    };
+    ViewAction::None
+}
+
+/// 有标记时对比标记的分支与当前行，否则对比当前行与它的父对话。
+fn open_tree_diff(view: &mut ViewState) -> ViewAction {
+    let Some(node) = view.tree_nodes.get(view.tree.selected).copied() else {
+        return ViewAction::None;
+    };
+    let other = view
+        .tree_mark
+        .filter(|&mark| mark != node.tab_index)
+        .or(node.parent);
+    if let Some(other) = other {
+        view.tree_diff = Some((other, node.tab_index));
+        view.tree_diff_scroll = 0;
+    }
+    ViewAction::None
+}
+
 pub(crate) fn handle_question_review_key(view: &mut ViewState, key: KeyEvent) -> ViewAction {
     match key.code {
         KeyCode::Esc => ViewAction::QuestionReviewCancel,
@@ -205,6 +341,38 @@ pub(crate) fn handle_question_review_key(view: &mut ViewState, key: KeyEvent) ->
     }
 }
 
+pub(crate) fn handle_workspace_changes_key(view: &mut ViewState, key: KeyEvent) -> ViewAction {
+    match key.code {
+        KeyCode::Esc => ViewAction::WorkspaceChangesClose,
+        KeyCode::Enter => ViewAction::WorkspaceChangesApply,
+        KeyCode::Up => {
+            view.workspace_changes.move_up();
+            view.workspace_changes_detail_scroll = 0;
+            ViewAction::None
+        }
+        KeyCode::Down => {
+            view.workspace_changes.move_down();
+            view.workspace_changes_detail_scroll = 0;
+            ViewAction::None
+        }
+        KeyCode::Char(' ') => ViewAction::WorkspaceChangesToggle(view.workspace_changes.selected),
+        KeyCode::Char('a') | KeyCode::Char('A') => ViewAction::WorkspaceChangesToggleAll,
+        KeyCode::PageUp => {
+            view.workspace_changes_detail_scroll = view
+                .workspace_changes_detail_scroll
+                .saturating_sub(PAGE_STEP);
+            ViewAction::None
+        }
+        KeyCode::PageDown => {
+            view.workspace_changes_detail_scroll = view
+                .workspace_changes_detail_scroll
+                .saturating_add(PAGE_STEP);
+            ViewAction::None
+        }
+        _ => ViewAction::None,
+    }
+}
+
 pub(crate) fn handle_help_key(view: &mut ViewState, key: KeyEvent) -> ViewAction {
     match key.code {
         KeyCode::Esc => {
@@ -230,3 +398,36 @@ pub(crate) fn handle_help_key(view: &mut ViewState, key: KeyEvent) -> ViewAction
         _ => ViewAction::None,
     }
 }
+
+pub(crate) fn handle_compare_key(view: &mut ViewState, key: KeyEvent) -> ViewAction {
+    let columns = view.compare_columns.max(1);
+    match key.code {
+        KeyCode::Esc => ViewAction::CompareDiscard,
+        KeyCode::Enter => ViewAction::CompareKeep(view.compare_selected),
+        KeyCode::Left | KeyCode::BackTab => {
+            view.compare_selected = (view.compare_selected + columns - 1) % columns;
+            ViewAction::None
+        }
+        KeyCode::Right | KeyCode::Tab => {
+            view.compare_selected = (view.compare_selected + 1) % columns;
+            ViewAction::None
+        }
+        KeyCode::Up => {
+            view.compare_scroll = view.compare_scroll.saturating_sub(1);
+            ViewAction::None
+        }
+        KeyCode::Down => {
+            view.compare_scroll = view.compare_scroll.saturating_add(1);
+            ViewAction::None
+        }
+        KeyCode::PageUp => {
+            view.compare_scroll = view.compare_scroll.saturating_sub(PAGE_STEP as u16);
+            ViewAction::None
+        }
+        KeyCode::PageDown => {
+            view.compare_scroll = view.compare_scroll.saturating_add(PAGE_STEP as u16);
+            ViewAction::None
+        }
+        _ => ViewAction::None,
+    }
+}
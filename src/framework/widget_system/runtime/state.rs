@@ -1,4 +1,4 @@
-use crate::types::{Message, ROLE_SYSTEM};
+use crate::types::{Message, ROLE_SYSTEM, ToolCall};
 use crate::framework::widget_system::commands::CommandSuggestion;
 use crate::framework::widget_system::interaction::selection_state::SelectionState;
 use std::collections::BTreeMap;
@@ -23,15 +23,57 @@ pub enum PendingCommand {
     DenyCodeExec,
     ExitCodeExec,
     StopCodeExec,
+    RestartKernel,
     ApplyFilePatch,
     CancelFilePatch,
     SubmitQuestionReview,
     CancelQuestionReview,
+    ShowWorkspaceChanges,
+    ApplyWorkspaceChanges,
+    CompactConversation,
+    SetCompareModels,
+    SetReasoningView,
+    ContinueAgent,
+    RegenerateAnswer,
     NewTab,
     NewCategory,
     OpenConversation,
 }
 
+/// 对 `prompts_dir` 中提示词文件的操作，由运行循环统一执行后重新加载提示词列表。
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PromptOp {
+    /// 以当前对话的系统提示词新建
+    New {
+        key: String,
+    },
+    /// 把提示词载入输入框，用内置编辑器修改
+    Edit {
+        key: String,
+    },
+    /// 保存内置编辑器中的内容
+    Save {
+        key: String,
+        content: String,
+    },
+    /// 在 `$VISUAL` / `$EDITOR` 中编辑
+    EditExternal {
+        key: String,
+    },
+    Duplicate {
+        key: String,
+        new_key: Option<String>,
+    },
+    Rename {
+        key: String,
+        new_key: String,
+    },
+    Delete {
+        key: String,
+    },
+    Reload,
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum CodeExecReasonTarget {
     Deny,
@@ -73,6 +115,7 @@ pub struct App {
     pub focus: Focus,
     pub busy: bool,
     pub pending_send: Option<String>,
+    pub pending_attachments: Vec<crate::types::Attachment>,
     pub pending_command: Option<PendingCommand>,
     pub active_request: Option<RequestHandle>,
     pub next_request_id: u64,
@@ -81,6 +124,42 @@ pub struct App {
     pub pending_reasoning: Option<usize>,
     pub stream_buffer: String,
     pub assistant_stats: BTreeMap<usize, String>,
+    /// 重新生成过的各轮回答，键为该轮提问的消息下标
+    pub answer_variants: BTreeMap<usize, crate::conversation::AnswerVariants>,
+    pub llm_retry: Option<LlmRetryStatus>,
+    pub compaction: Option<CompactionState>,
+    pub archived_messages: Vec<Message>,
+    pub compare_models: Vec<String>,
+    pub pending_compare_models: Option<String>,
+    pub compare: Option<CompareState>,
+    /// 思考过程展开/折叠；None 时沿用 `--show-reasoning`。
+    pub reasoning_expanded: Option<bool>,
+    pub pending_reasoning_view: Option<String>,
+    /// 本对话用 `/limits` 覆盖的工具循环上限，None 时沿用配置
+    pub agent_limits: Option<crate::llm::agent_limits::AgentLimits>,
+    pub agent_turn: AgentTurn,
+    pub pending_agent_steps: Option<String>,
+    pub pending_prompt_op: Option<PromptOp>,
+    /// 输入框正在内置编辑的提示词，Enter 时保存而不是发送
+    pub editing_prompt: Option<String>,
+    /// 本对话在工具面板或用 `/tools` 调整过的工具开关，None 时沿用 `--enable`
+    pub tool_flags: Option<crate::args::ToolFlags>,
+    /// `/tools` 不带参数时请求打开工具面板
+    pub pending_tools_overlay: bool,
+    /// `/tree` 或导航模式的 `t` 请求打开分支树
+    pub pending_tree_overlay: bool,
+    /// 本轮工具调用中待派出的子任务，由运行循环新建子对话
+    pub pending_delegations: Vec<DelegateRequest>,
+    /// 已派出的子任务；等待中的结果全部返回后继续本轮工具循环
+    pub delegations: Vec<Delegation>,
+    /// 由其他对话用 `delegate_task` 派出时记录来源
+    pub delegated_by: Option<DelegationLink>,
+    /// 从其他对话分叉而来时记录来源
+    pub fork_of: Option<crate::conversation::ForkOrigin>,
+    /// 第一轮问答后自动生成，或用 `/title` 手动设置
+    pub title: Option<String>,
+    /// 正在后台请求标题模型
+    pub title_pending: bool,
     pub scrollbar_dragging: bool,
     pub chat_selecting: bool,
     pub chat_selection: Option<crate::framework::widget_system::interaction::selection::Selection>,
@@ -105,6 +184,8 @@ pub struct App {
     pub code_exec_hover: Option<CodeExecHover>,
     pub code_exec_reason_target: Option<CodeExecReasonTarget>,
     pub code_exec_reason_input: TextArea<'static>,
+    pub code_exec_stdin_input: TextArea<'static>,
+    pub code_exec_stdin: Option<std::sync::mpsc::Sender<CodeExecStdin>>,
     pub code_exec_container_id: Option<String>,
     pub code_exec_run_id: Option<String>,
     pub code_exec_selecting: Option<CodeExecSelectionTarget>,
@@ -117,12 +198,15 @@ pub struct App {
     pub file_patch_selecting: bool,
     pub file_patch_selection: Option<crate::framework::widget_system::interaction::selection::Selection>,
     pub pending_question_review: Option<PendingQuestionReview>,
+    pub pending_workspace_changes: Option<PendingWorkspaceChanges>,
     pub pending_category_name: Option<String>,
     pub pending_open_conversation: Option<String>,
     pub terminal: Option<crate::framework::widget_system::widgets::terminal::TerminalSession>,
     pub total_prompt_tokens: u64,
     pub total_completion_tokens: u64,
     pub total_tokens: u64,
+    pub total_cost: Option<f64>,
+    pub usage_log: Vec<crate::conversation::UsageRecord>,
     pub dirty_indices: Vec<usize>,
     pub cache_shift: Option<usize>,
     pub notice: Option<Notice>,
@@ -130,6 +214,120 @@ pub struct App {
     pub command_select: SelectionState,
 }
 
+#[derive(Clone, Debug)]
+pub struct LlmRetryStatus {
+    pub attempt: u32,
+    pub max_attempts: u32,
+    pub resume_at: Instant,
+}
+
+/// 进行中的 `/compact` 请求：`keep_from` 之前的消息会被摘要替换，
+/// 摘要从 `summary_from` 开始流式写入。
+#[derive(Clone, Copy, Debug)]
+pub struct CompactionState {
+    pub keep_from: usize,
+    pub summary_from: usize,
+}
+
+/// 一次用户输入触发的工具循环进度，供护栏检查与统计显示。
+#[derive(Clone, Debug, Default)]
+pub struct AgentTurn {
+    pub started_at: Option<Instant>,
+    pub steps: u32,
+    pub tokens: u64,
+    /// `/continue` 放行后的步数上限，覆盖配置的 max_steps
+    pub step_cap: Option<u32>,
+    last_calls: Option<String>,
+    /// 连续调用完全相同的工具与参数的轮数
+    pub repeated: u32,
+    /// 因超出上限而暂停时的原因，等待 `/continue`
+    pub paused: Option<String>,
+}
+
+impl AgentTurn {
+    pub fn begin() -> Self {
+        Self {
+            started_at: Some(Instant::now()),
+            ..Self::default()
+        }
+    }
+
+    pub fn record_round(&mut self, calls: &[ToolCall]) {
+        self.steps = self.steps.saturating_add(1);
+        let signature = calls
+            .iter()
+            .map(|call| format!("{}:{}", call.function.name, call.function.arguments))
+            .collect::<Vec<_>>()
+            .join("\n");
+        if self.last_calls.as_deref() == Some(signature.as_str()) {
+            self.repeated = self.repeated.saturating_add(1);
+        } else {
+            self.repeated = 1;
+            self.last_calls = Some(signature);
+        }
+    }
+
+    pub fn add_tokens(&mut self, tokens: u64) {
+        self.tokens = self.tokens.saturating_add(tokens);
+    }
+
+    /// 放行 `extra_steps` 步：耗时、tokens 与重复计数重新开始计算。
+    pub fn resume(&mut self, extra_steps: u32) {
+        self.step_cap = Some(self.steps.saturating_add(extra_steps));
+        self.started_at = Some(Instant::now());
+        self.tokens = 0;
+        self.repeated = 0;
+        self.last_calls = None;
+        self.paused = None;
+    }
+}
+
+/// 进行中的多模型对比：每个模型在各自的标签页中回答同一个问题，
+/// 标签页复制了发起时的对话，`history_len` 之后是该模型的回答。
+#[derive(Clone, Debug)]
+pub struct CompareState {
+    pub question: String,
+    pub entries: Vec<CompareEntry>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CompareEntry {
+    pub model_key: String,
+    pub conversation_id: String,
+    pub history_len: usize,
+    pub started_at: Instant,
+    pub elapsed_ms: Option<u64>,
+}
+
+/// 模型调用 `delegate_task` 交出的子任务。
+#[derive(Clone, Debug)]
+pub struct DelegateRequest {
+    pub call_id: String,
+    pub task: String,
+    pub model: Option<String>,
+    pub prompt: Option<String>,
+    pub tools: Option<Vec<String>>,
+    /// true 时父对话等待结果再继续；false 时立即继续，结果稍后作为新消息发回
+    pub wait: bool,
+}
+
+/// 父对话中已派出的子任务，`result` 为子对话的最终回复或失败原因。
+#[derive(Clone, Debug)]
+pub struct Delegation {
+    pub call_id: String,
+    pub task: String,
+    pub conversation_id: String,
+    pub wait: bool,
+    pub result: Option<Result<String, String>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct DelegationLink {
+    pub parent_id: String,
+    /// 第几层子任务，由父对话直接派出的为 1
+    pub depth: u32,
+}
+
 #[derive(Clone, Debug)]
 pub struct PendingCodeExec {
     pub call_id: String,
@@ -148,6 +346,14 @@ pub struct CodeExecLive {
     pub stderr: String,
     pub exit_code: Option<i32>,
     pub done: bool,
+    pub stdin_log: String,
+    pub stdin_closed: bool,
+}
+
+#[derive(Clone, Debug)]
+pub enum CodeExecStdin {
+    Line(String),
+    Eof,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -198,6 +404,40 @@ pub struct PendingQuestionItem {
     pub model_key: String,
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum WorkspaceChangeKind {
+    Added,
+    Modified,
+    Deleted,
+}
+
+impl WorkspaceChangeKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            WorkspaceChangeKind::Added => "新增",
+            WorkspaceChangeKind::Modified => "修改",
+            WorkspaceChangeKind::Deleted => "删除",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PendingWorkspaceChanges {
+    pub items: Vec<WorkspaceChangeItem>,
+}
+
+#[derive(Clone, Debug)]
+pub struct WorkspaceChangeItem {
+    pub path: String,
+    pub kind: WorkspaceChangeKind,
+    pub added: usize,
+    pub removed: usize,
+    pub diff: String,
+    /// 宿主机上的文件在复制之后也被改动，同步会覆盖这些改动
+    pub conflict: bool,
+    pub selected: bool,
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum FilePatchHover {
     Apply,
@@ -224,6 +464,7 @@ impl App {
                     content: content.to_string(),
                     tool_call_id: None,
                     tool_calls: None,
+                    attachments: Vec::new(),
                 },
             );
             self.cache_shift = Some(0);
@@ -233,6 +474,10 @@ impl App {
     pub fn set_log_session_id(&mut self, id: &str) {
         self.log_session_id = id.to_string();
     }
+
+    pub fn reasoning_visible(&self, default: bool) -> bool {
+        self.reasoning_expanded.unwrap_or(default)
+    }
 }
 
 fn build_initial_messages(system_prompt: &str) -> Vec<Message> {
@@ -244,6 +489,7 @@ fn build_initial_messages(system_prompt: &str) -> Vec<Message> {
         content: system_prompt.to_string(),
         tool_call_id: None,
         tool_calls: None,
+        attachments: Vec::new(),
     }]
 }
 
@@ -1,3 +1,4 @@
+use crate::conversation::ForkOrigin;
 use crate::types::{ROLE_SYSTEM, ROLE_USER};
 use crate::framework::widget_system::notice::push_notice;
 use crate::framework::widget_system::runtime_dispatch::DispatchContext;
@@ -51,6 +52,7 @@ struct ForkSeed {
     prompts_dir: String,
     tavily_api_key: String,
     hooks: Vec<crate::hooks::HookSpec>,
+    origin: ForkOrigin,
 }
 
 enum ForkError {
@@ -85,6 +87,10 @@ fn build_fork_seed(ctx: &DispatchContext<'_>, msg_idx: usize) -> Result<ForkSeed
         prompts_dir: tab_state.app.prompts_dir.clone(),
         tavily_api_key: tab_state.app.tavily_api_key.clone(),
         hooks: tab_state.app.hooks.clone(),
+        origin: ForkOrigin {
+            parent_id: tab_state.conversation_id.clone(),
+            message_index: msg_idx,
+        },
     })
 }
 
@@ -138,6 +144,7 @@ fn build_fork_tab(ctx: &mut DispatchContext<'_>, seed: &ForkSeed) -> TabState {
     new_tab.app.prompts_dir = seed.prompts_dir.clone();
     new_tab.app.tavily_api_key = seed.tavily_api_key.clone();
     new_tab.app.hooks = seed.hooks.clone();
+    new_tab.app.fork_of = Some(seed.origin.clone());
     new_tab
 }
 
@@ -151,6 +158,7 @@ fn apply_fork_history(new_tab: &mut TabState, seed: &ForkSeed) {
                 content: seed.system_prompt.clone(),
                 tool_call_id: None,
                 tool_calls: None,
+                attachments: Vec::new(),
             },
         );
     }
@@ -2,12 +2,16 @@ use crate::framework::widget_system::runtime::runtime_view::{ViewAction, ViewSta
 use crossterm::event::{KeyCode, KeyEvent};
 
 use crate::framework::widget_system::runtime_dispatch::{
-    DispatchContext, LayoutContext, apply_model_selection, apply_prompt_selection, cycle_model,
-    cycle_model_prev, sync_model_selection, sync_prompt_selection,
+    DispatchContext, LayoutContext, apply_model_selection, apply_prompt_action,
+    apply_prompt_selection, cycle_model, cycle_model_prev, sync_model_selection,
+    sync_prompt_selection,
 };
 use crate::framework::widget_system::runtime::state::{PendingCommand, QuestionDecision};
 use crate::framework::widget_system::notice::push_notice;
+use crate::services::runtime_compare;
 use crate::services::runtime_question_review;
+use crate::services::runtime_tools;
+use crate::services::runtime_workspace_changes;
 
 pub(crate) fn handle_view_action_flow(
     ctx: &mut DispatchContext<'_>,
@@ -31,6 +35,15 @@ pub(crate) fn handle_view_action_flow(
     if handle_question_review_actions(ctx, action) {
         return true;
     }
+    if handle_workspace_changes_actions(ctx, action) {
+        return true;
+    }
+    if handle_compare_actions(ctx, view, action) {
+        return true;
+    }
+    if handle_tool_actions(ctx, action) {
+        return true;
+    }
     if handle_apply_view_action(ctx, view, action) {
         return true;
     }
@@ -89,6 +102,10 @@ fn handle_selection_actions(ctx: &mut DispatchContext<'_>, action: ViewAction) -
         apply_prompt_selection(ctx, idx);
         return true;
     }
+    if let ViewAction::ManagePrompt(prompt_action, idx) = action {
+        apply_prompt_action(ctx, prompt_action, idx);
+        return true;
+    }
     false
 }
 
@@ -141,6 +158,67 @@ fn handle_question_review_submit(tab_state: &mut crate::framework::widget_system
     true
 }
 
+fn handle_workspace_changes_actions(ctx: &mut DispatchContext<'_>, action: ViewAction) -> bool {
+    let Some(tab_state) = ctx.tabs.get_mut(*ctx.active_tab) else {
+        return false;
+    };
+    match action {
+        ViewAction::WorkspaceChangesToggle(idx) => {
+            runtime_workspace_changes::toggle_workspace_change(tab_state, idx)
+        }
+        ViewAction::WorkspaceChangesToggleAll => {
+            runtime_workspace_changes::toggle_all_workspace_changes(tab_state)
+        }
+        ViewAction::WorkspaceChangesApply => {
+            if !runtime_workspace_changes::any_workspace_change_selected(tab_state) {
+                push_notice(&mut tab_state.app, "未选择任何改动");
+                return true;
+            }
+            tab_state.app.pending_command = Some(PendingCommand::ApplyWorkspaceChanges);
+            true
+        }
+        ViewAction::WorkspaceChangesClose => {
+            tab_state.app.pending_workspace_changes = None;
+            true
+        }
+        _ => false,
+    }
+}
+
+pub(crate) fn handle_tool_actions(ctx: &mut DispatchContext<'_>, action: ViewAction) -> bool {
+    let Some(tab_state) = ctx.tabs.get_mut(*ctx.active_tab) else {
+        return false;
+    };
+    match action {
+        ViewAction::ToggleTool(idx) => runtime_tools::toggle_tool(&mut tab_state.app, idx),
+        ViewAction::ResetTools => runtime_tools::reset_tools(&mut tab_state.app),
+        _ => return false,
+    }
+    true
+}
+
+fn handle_compare_actions(
+    ctx: &mut DispatchContext<'_>,
+    view: &mut ViewState,
+    action: ViewAction,
+) -> bool {
+    match action {
+        ViewAction::CompareKeep(column) => {
+            runtime_compare::keep_compare_answer(ctx.tabs, ctx.active_tab, column)
+        }
+        ViewAction::CompareDiscard => runtime_compare::discard_compare(ctx.tabs, ctx.active_tab),
+        _ => return false,
+    }
+    let compare_open = ctx
+        .tabs
+        .get(*ctx.active_tab)
+        .is_some_and(|tab| tab.app.compare.is_some());
+    if !compare_open {
+        view.overlay.close();
+    }
+    true
+}
+
 fn handle_apply_view_action(
     ctx: &mut DispatchContext<'_>,
     view: &mut ViewState,
@@ -2,6 +2,6 @@ mod actions;
 mod overlays;
 mod shortcuts;
 
-pub(crate) use actions::handle_view_action_flow;
+pub(crate) use actions::{handle_tool_actions, handle_view_action_flow};
 pub(crate) use overlays::{handle_pre_key_actions, resolve_view_action};
 pub(crate) use shortcuts::is_quit_key;
@@ -3,6 +3,7 @@ use crate::framework::widget_system::overlay::OverlayKind;
 use crate::framework::widget_system::runtime_dispatch::DispatchContext;
 use crate::framework::widget_system::runtime::runtime_view::{ViewAction, ViewState, handle_view_key};
 use crate::framework::widget_system::widgets::jump::jump_len;
+use crate::services::runtime_code_exec::submit_code_exec_stdin;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use super::shortcuts::handle_global_shortcuts;
@@ -18,10 +19,10 @@ pub(crate) fn handle_pre_key_actions(
     if handle_code_exec_reason_input(ctx, view, key) {
         return true;
     }
-    if handle_stop_key(ctx, key) {
+    if handle_code_exec_stdin_input(ctx, view, key) {
         return true;
     }
-    if handle_prompt_lock_key(ctx, key) {
+    if handle_stop_key(ctx, key) {
         return true;
     }
     handle_nav_mode_key(ctx, view, key)
@@ -53,6 +54,45 @@ fn handle_code_exec_reason_input(
     false
 }
 
+fn handle_code_exec_stdin_input(
+    ctx: &mut DispatchContext<'_>,
+    view: &mut ViewState,
+    key: KeyEvent,
+) -> bool {
+    if !view.overlay.is(OverlayKind::CodeExec) || matches!(key.code, KeyCode::F(_) | KeyCode::Esc) {
+        return false;
+    }
+    let Some(tab_state) = ctx.tabs.get_mut(*ctx.active_tab) else {
+        return false;
+    };
+    if !code_exec_stdin_open(&tab_state.app) {
+        return false;
+    }
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Enter => submit_code_exec_stdin(&mut tab_state.app, false),
+        KeyCode::Char('d') if ctrl => submit_code_exec_stdin(&mut tab_state.app, true),
+        KeyCode::Char('c') if ctrl => return false,
+        KeyCode::Char('u') if ctrl => {
+            tab_state.app.code_exec_stdin_input = tui_textarea::TextArea::default();
+        }
+        _ => {
+            let _ = tab_state.app.code_exec_stdin_input.input(key);
+        }
+    }
+    true
+}
+
+fn code_exec_stdin_open(app: &crate::framework::widget_system::runtime::state::App) -> bool {
+    if app.code_exec_stdin.is_none() || app.code_exec_reason_target.is_some() {
+        return false;
+    }
+    app.code_exec_live
+        .as_ref()
+        .and_then(|live| live.lock().ok().map(|l| !l.done && !l.stdin_closed))
+        .unwrap_or(false)
+}
+
 fn handle_stop_key(ctx: &mut DispatchContext<'_>, key: KeyEvent) -> bool {
     if key.code != KeyCode::F(6) {
         return false;
@@ -61,25 +101,15 @@ fn handle_stop_key(ctx: &mut DispatchContext<'_>, key: KeyEvent) -> bool {
         if key.modifiers.contains(KeyModifiers::SHIFT) {
             crate::framework::widget_system::runtime::runtime_helpers::stop_and_edit(tab_state);
         } else {
-            crate::framework::widget_system::runtime::logic::stop_stream(&mut tab_state.app);
+            let app = &mut tab_state.app;
+            if !crate::framework::widget_system::runtime::logic::stop_stream(app) {
+                crate::services::runtime_delegation::stop_waiting_delegations(app);
+            }
         }
     }
     true
 }
 
-fn handle_prompt_lock_key(ctx: &mut DispatchContext<'_>, key: KeyEvent) -> bool {
-    if key.code != KeyCode::F(5) {
-        return false;
-    }
-    if let Some(tab_state) = ctx.tabs.get_mut(*ctx.active_tab)
-        && !crate::framework::widget_system::runtime_dispatch::can_change_prompt(&tab_state.app)
-    {
-        crate::framework::widget_system::runtime_dispatch::push_prompt_locked(tab_state);
-        return true;
-    }
-    false
-}
-
 fn handle_nav_mode_key(ctx: &mut DispatchContext<'_>, view: &ViewState, key: KeyEvent) -> bool {
     if !view.is_chat() {
         return false;
@@ -97,6 +127,12 @@ fn sync_overlay_state(ctx: &mut DispatchContext<'_>, view: &mut ViewState) {
     if view.overlay.is(OverlayKind::QuestionReview) {
         handle_question_review_overlay_key(ctx, view);
     }
+    if view.overlay.is(OverlayKind::WorkspaceChanges) {
+        handle_workspace_changes_overlay_key(ctx, view);
+    }
+    if view.overlay.is(OverlayKind::Compare) {
+        handle_compare_overlay_key(ctx, view);
+    }
 }
 
 fn jump_len_for_ctx(ctx: &DispatchContext<'_>) -> usize {
@@ -130,6 +166,22 @@ fn handle_question_review_overlay_key(ctx: &mut DispatchContext<'_>, view: &mut
     }
 }
 
+fn handle_workspace_changes_overlay_key(ctx: &mut DispatchContext<'_>, view: &mut ViewState) {
+    if let Some(tab_state) = ctx.tabs.get_mut(*ctx.active_tab)
+        && tab_state.app.pending_workspace_changes.is_none()
+    {
+        view.overlay.close();
+    }
+}
+
+fn handle_compare_overlay_key(ctx: &mut DispatchContext<'_>, view: &mut ViewState) {
+    if let Some(tab_state) = ctx.tabs.get_mut(*ctx.active_tab)
+        && tab_state.app.compare.is_none()
+    {
+        view.overlay.close();
+    }
+}
+
 fn handle_code_exec_reason_key(app: &mut crate::framework::widget_system::runtime::state::App, key: KeyEvent) {
     match key.code {
         KeyCode::Esc => {
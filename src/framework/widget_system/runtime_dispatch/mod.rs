@@ -8,6 +8,8 @@ use crate::framework::widget_system::widgets::overlay_table::state::{
     OverlayAreas, OverlayRowCounts, overlay_visible_rows,
 };
 use crate::framework::widget_system::runtime::runtime_helpers::TabState;
+use crate::framework::widget_system::runtime::runtime_view::PromptAction;
+use crate::framework::widget_system::runtime::state::PromptOp;
 use crate::services::runtime_requests::start_tab_request;
 use ratatui::layout::Rect;
 use std::sync::mpsc;
@@ -56,7 +58,9 @@ pub(crate) fn start_pending_request(
         api_key: &model.api_key,
         model: &model.model,
         max_tokens: model.max_tokens,
-        show_reasoning: args.show_reasoning,
+        context_window: model.context_window,
+        reasoning: model.reasoning,
+        timeouts: model.timeouts,
         tx,
         enable_web_search: args.web_search_enabled(),
         enable_code_exec: args.code_exec_enabled(),
@@ -64,6 +68,7 @@ pub(crate) fn start_pending_request(
         enable_read_code: args.read_code_enabled(),
         enable_modify_file: args.modify_file_enabled(),
         enable_ask_questions: args.ask_questions_enabled(),
+        enable_delegate_task: args.delegate_task_enabled(),
         log_requests: args.log_requests.clone(),
         log_session_id,
     });
@@ -131,12 +136,19 @@ fn overlay_counts(ctx: &DispatchContext<'_>) -> OverlayRowCounts {
         .and_then(|tab| tab.app.pending_question_review.as_ref())
         .map(|pending| pending.questions.len())
         .unwrap_or(0);
+    let workspace_changes = ctx
+        .tabs
+        .get(*ctx.active_tab)
+        .and_then(|tab| tab.app.pending_workspace_changes.as_ref())
+        .map(|pending| pending.items.len())
+        .unwrap_or(0);
     OverlayRowCounts {
         tabs: ctx.tabs.len(),
         jump: 0,
         models: ctx.registry.models.len(),
         prompts: ctx.prompt_registry.prompts.len(),
         question_reviews,
+        workspace_changes,
         help: crate::framework::widget_system::widgets::help::help_rows_len(),
     }
 }
@@ -156,6 +168,7 @@ pub(crate) fn apply_prompt_selection(ctx: &mut DispatchContext<'_>, idx: usize)
                 tab_state
                     .app
                     .set_system_prompt(&prompt.key, &prompt.content);
+                apply_prompt_model(tab_state, ctx.registry, prompt.meta.model.as_deref());
             }
         } else {
             push_prompt_locked(tab_state);
@@ -163,6 +176,69 @@ pub(crate) fn apply_prompt_selection(ctx: &mut DispatchContext<'_>, idx: usize)
     });
 }
 
+/// 提示词在 front-matter 中指定了模型时一并切换；模型不存在则提示并保留当前模型。
+fn apply_prompt_model(
+    tab_state: &mut TabState,
+    registry: &crate::model_registry::ModelRegistry,
+    model: Option<&str>,
+) {
+    let Some(model) = model else {
+        return;
+    };
+    if registry.get(model).is_some() {
+        tab_state.app.model_key = model.to_string();
+    } else {
+        push_notice(
+            &mut tab_state.app,
+            format!("提示词指定的模型不存在：{model}"),
+        );
+    }
+}
+
+/// F5 列表中的管理操作：需要名称或确认的操作预填 `/prompt` 命令，其余记录待运行循环执行。
+pub(crate) fn apply_prompt_action(ctx: &mut DispatchContext<'_>, action: PromptAction, idx: usize) {
+    let key = ctx.prompt_registry.prompts.get(idx).map(|p| p.key.clone());
+    with_active_tab(ctx, |tab_state| {
+        let app = &mut tab_state.app;
+        match (action, key) {
+            (PromptAction::New, _) => prefill_prompt_command(
+                app,
+                "/prompt new ".to_string(),
+                "输入新提示词的名称后按 Enter",
+            ),
+            (_, None) => {}
+            (PromptAction::Rename, Some(key)) => prefill_prompt_command(
+                app,
+                format!("/prompt rename {key} "),
+                "输入新名称后按 Enter",
+            ),
+            (PromptAction::Delete, Some(key)) => prefill_prompt_command(
+                app,
+                format!("/prompt delete {key}"),
+                "按 Enter 确认删除，Ctrl+U 取消",
+            ),
+            (PromptAction::Edit, Some(key)) => app.pending_prompt_op = Some(PromptOp::Edit { key }),
+            (PromptAction::EditExternal, Some(key)) => {
+                app.pending_prompt_op = Some(PromptOp::EditExternal { key })
+            }
+            (PromptAction::Duplicate, Some(key)) => {
+                app.pending_prompt_op = Some(PromptOp::Duplicate { key, new_key: None })
+            }
+        }
+    });
+}
+
+fn prefill_prompt_command(
+    app: &mut crate::framework::widget_system::runtime::state::App,
+    line: String,
+    hint: &str,
+) {
+    app.input = tui_textarea::TextArea::from([line]);
+    app.input.move_cursor(tui_textarea::CursorMove::End);
+    app.focus = crate::framework::widget_system::runtime::state::Focus::Input;
+    push_notice(app, hint);
+}
+
 pub(crate) fn push_prompt_locked(tab_state: &mut TabState) {
     push_notice(&mut tab_state.app, PROMPT_LOCKED_MSG);
 }
@@ -1,6 +1,7 @@
 use crate::framework::widget_system::runtime::state::{App, Focus};
 use crate::framework::widget_system::runtime_dispatch::DispatchContext;
 use crate::framework::widget_system::runtime_dispatch::tabs::{next_tab, prev_tab};
+use crate::services::runtime_variants::{cycle_variant, request_regenerate};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 pub(crate) fn handle_nav_key(ctx: &mut DispatchContext<'_>, key: KeyEvent) -> bool {
@@ -54,10 +55,28 @@ fn handle_nav_key_in_mode(ctx: &mut DispatchContext<'_>, key: KeyEvent) -> bool
             nav_prev(app);
             true
         }
+        KeyCode::Char('r') => {
+            let selected = Some(app.message_history.selected);
+            request_regenerate(app, selected);
+            true
+        }
+        KeyCode::Char('t') => {
+            app.pending_tree_overlay = true;
+            true
+        }
+        KeyCode::Char('[') => nav_cycle_variant(ctx, false),
+        KeyCode::Char(']') => nav_cycle_variant(ctx, true),
         _ => true,
     }
 }
 
+fn nav_cycle_variant(ctx: &mut DispatchContext<'_>, forward: bool) -> bool {
+    if let Some(tab_state) = ctx.tabs.get_mut(*ctx.active_tab) {
+        cycle_variant(tab_state, forward);
+    }
+    true
+}
+
 fn nav_next(app: &mut App) {
     if app.messages.is_empty() {
         return;
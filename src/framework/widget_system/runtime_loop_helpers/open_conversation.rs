@@ -73,6 +73,7 @@ fn push_assistant_message(tab_state: &mut TabState, content: String) {
         content,
         tool_call_id: None,
         tool_calls: None,
+        attachments: Vec::new(),
     });
     tab_state.app.dirty_indices.push(idx);
 }
@@ -154,6 +155,12 @@ fn apply_conversation_state(
 ) {
     tab.app.messages = conv.messages.clone();
     tab.app.code_exec_container_id = conv.code_exec_container_id.clone();
+    tab.app.archived_messages = conv.archived_messages.clone();
+    tab.app.agent_limits = conv.agent_limits;
+    tab.app.tool_flags = conv.tool_flags;
+    tab.app.title = conv.title.clone();
+    tab.app.answer_variants = conv.answer_variants.clone();
+    tab.app.fork_of = conv.fork_of.clone();
     ensure_system_prompt(tab, prompt_key, prompt_registry, args);
     tab.app.model_key = model_key.to_string();
     tab.app.prompt_key = prompt_key.to_string();
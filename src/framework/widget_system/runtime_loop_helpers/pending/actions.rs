@@ -2,19 +2,29 @@ use crate::args::Args;
 use crate::framework::widget_system::runtime::events::RuntimeEvent;
 use crate::services::runtime_code_exec::{
     handle_code_exec_approve, handle_code_exec_deny, handle_code_exec_exit, handle_code_exec_stop,
+    handle_kernel_restart,
 };
+use crate::services::runtime_agent_guard::handle_agent_continue;
+use crate::services::runtime_compact::handle_compact_command;
+use crate::services::runtime_compare::handle_compare_models_command;
 use crate::services::runtime_file_patch::{handle_file_patch_apply, handle_file_patch_cancel};
+use crate::services::runtime_variants::handle_regenerate;
 use crate::framework::widget_system::runtime::runtime_helpers::TabState;
 use crate::services::runtime_question_review::{
     QuestionReviewSubmitParams, handle_question_review_cancel, handle_question_review_submit,
 };
-use crate::framework::widget_system::runtime::state::PendingCommand;
+use crate::framework::widget_system::notice::push_notice;
+use crate::framework::widget_system::runtime::state::{App, PendingCommand};
+use crate::services::runtime_workspace_changes::{
+    handle_workspace_changes_apply, handle_workspace_changes_show,
+};
 
 enum CodeExecAction {
     Approve,
     Deny,
     Exit,
     Stop,
+    RestartKernel,
 }
 
 enum FilePatchAction {
@@ -22,6 +32,11 @@ enum FilePatchAction {
     Cancel,
 }
 
+enum WorkspaceChangesAction {
+    Show,
+    Apply,
+}
+
 enum QuestionReviewAction {
     Submit,
     Cancel,
@@ -52,6 +67,7 @@ pub(crate) fn handle_code_exec_command(
         PendingCommand::DenyCodeExec => Some(CodeExecAction::Deny),
         PendingCommand::ExitCodeExec => Some(CodeExecAction::Exit),
         PendingCommand::StopCodeExec => Some(CodeExecAction::Stop),
+        PendingCommand::RestartKernel => Some(CodeExecAction::RestartKernel),
         _ => None,
     };
     if let Some(action) = action {
@@ -81,6 +97,131 @@ pub(crate) fn handle_file_patch_command(
     false
 }
 
+pub(crate) fn handle_workspace_changes_command(
+    pending: PendingCommand,
+    tabs: &mut [TabState],
+    active_tab: usize,
+    args: &Args,
+) -> bool {
+    let action = match pending {
+        PendingCommand::ShowWorkspaceChanges => Some(WorkspaceChangesAction::Show),
+        PendingCommand::ApplyWorkspaceChanges => Some(WorkspaceChangesAction::Apply),
+        _ => None,
+    };
+    let Some(action) = action else {
+        return false;
+    };
+    if let Some(tab_state) = tabs.get_mut(active_tab) {
+        match action {
+            WorkspaceChangesAction::Show => handle_workspace_changes_show(tab_state, args),
+            WorkspaceChangesAction::Apply => handle_workspace_changes_apply(tab_state, args),
+        }
+    }
+    true
+}
+
+pub(crate) fn handle_compact_conversation_command(
+    pending: PendingCommand,
+    tabs: &mut [TabState],
+    active_tab: usize,
+    registry: &crate::model_registry::ModelRegistry,
+    args: &Args,
+    tx: &std::sync::mpsc::Sender<RuntimeEvent>,
+) -> bool {
+    if pending != PendingCommand::CompactConversation {
+        return false;
+    }
+    if let Some(tab_state) = tabs.get_mut(active_tab) {
+        handle_compact_command(tab_state, registry, args, tx);
+    }
+    true
+}
+
+pub(crate) fn handle_compare_models_pending(
+    pending: PendingCommand,
+    tabs: &mut [TabState],
+    active_tab: usize,
+    registry: &crate::model_registry::ModelRegistry,
+) -> bool {
+    if pending != PendingCommand::SetCompareModels {
+        return false;
+    }
+    if let Some(tab_state) = tabs.get_mut(active_tab) {
+        handle_compare_models_command(tab_state, registry);
+    }
+    true
+}
+
+pub(crate) fn handle_continue_agent_command(
+    pending: PendingCommand,
+    tabs: &mut [TabState],
+    active_tab: usize,
+    registry: &crate::model_registry::ModelRegistry,
+    args: &Args,
+    tx: &std::sync::mpsc::Sender<RuntimeEvent>,
+) -> bool {
+    if pending != PendingCommand::ContinueAgent {
+        return false;
+    }
+    if let Some(tab_state) = tabs.get_mut(active_tab) {
+        handle_agent_continue(tab_state, registry, args, tx);
+    }
+    true
+}
+
+pub(crate) fn handle_regenerate_command(
+    pending: PendingCommand,
+    tabs: &mut [TabState],
+    active_tab: usize,
+    registry: &crate::model_registry::ModelRegistry,
+    args: &Args,
+    tx: &std::sync::mpsc::Sender<RuntimeEvent>,
+) -> bool {
+    if pending != PendingCommand::RegenerateAnswer {
+        return false;
+    }
+    if let Some(tab_state) = tabs.get_mut(active_tab) {
+        handle_regenerate(tab_state, registry, args, tx);
+    }
+    true
+}
+
+pub(crate) fn handle_reasoning_view_command(
+    pending: PendingCommand,
+    tabs: &mut [TabState],
+    active_tab: usize,
+    args: &Args,
+) -> bool {
+    if pending != PendingCommand::SetReasoningView {
+        return false;
+    }
+    if let Some(tab_state) = tabs.get_mut(active_tab) {
+        set_reasoning_view(&mut tab_state.app, args);
+    }
+    true
+}
+
+/// `/reasoning [show|hide]`：不带参数时切换当前状态。
+fn set_reasoning_view(app: &mut App, args: &Args) {
+    let arg = app.pending_reasoning_view.take().unwrap_or_default();
+    let expanded = match arg.as_str() {
+        "" => !app.reasoning_visible(args.show_reasoning),
+        "show" => true,
+        "hide" => false,
+        _ => {
+            push_notice(app, "用法：/reasoning [show|hide]");
+            return;
+        }
+    };
+    app.reasoning_expanded = Some(expanded);
+    let text = if expanded {
+        "已展开思考过程"
+    } else {
+        "已折叠思考过程"
+    };
+    push_notice(app, text);
+}
+
 pub(crate) fn handle_question_review_command(params: QuestionReviewCommandParams<'_>) -> bool {
     let action = match params.pending {
         PendingCommand::SubmitQuestionReview => Some(QuestionReviewAction::Submit),
@@ -112,6 +253,7 @@ fn handle_code_exec_action(
         CodeExecAction::Deny => handle_code_exec_deny(tab_state, registry, args, tx),
         CodeExecAction::Exit => handle_code_exec_exit(tab_state, registry, args, tx),
         CodeExecAction::Stop => handle_code_exec_stop(tab_state),
+        CodeExecAction::RestartKernel => handle_kernel_restart(tab_state),
     }
 }
 
@@ -90,6 +90,60 @@ fn handled_by_actions(params: &mut HandlePendingCommandParams<'_>) -> bool {
     ) {
         return true;
     }
+    if actions::handle_workspace_changes_command(
+        params.pending,
+        params.tabs,
+        *params.active_tab,
+        params.args,
+    ) {
+        return true;
+    }
+    if actions::handle_compact_conversation_command(
+        params.pending,
+        params.tabs,
+        *params.active_tab,
+        params.registry,
+        params.args,
+        params.tx,
+    ) {
+        return true;
+    }
+    if actions::handle_compare_models_pending(
+        params.pending,
+        params.tabs,
+        *params.active_tab,
+        params.registry,
+    ) {
+        return true;
+    }
+    if actions::handle_continue_agent_command(
+        params.pending,
+        params.tabs,
+        *params.active_tab,
+        params.registry,
+        params.args,
+        params.tx,
+    ) {
+        return true;
+    }
+    if actions::handle_regenerate_command(
+        params.pending,
+        params.tabs,
+        *params.active_tab,
+        params.registry,
+        params.args,
+        params.tx,
+    ) {
+        return true;
+    }
+    if actions::handle_reasoning_view_command(
+        params.pending,
+        params.tabs,
+        *params.active_tab,
+        params.args,
+    ) {
+        return true;
+    }
     actions::handle_question_review_command(actions::QuestionReviewCommandParams {
         pending: params.pending,
         tabs: params.tabs,
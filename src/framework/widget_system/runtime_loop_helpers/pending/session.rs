@@ -77,6 +77,7 @@ fn push_assistant_message(tab_state: &mut TabState, content: String) {
         content,
         tool_call_id: None,
         tool_calls: None,
+        attachments: Vec::new(),
     });
     tab_state.app.dirty_indices.push(idx);
 }
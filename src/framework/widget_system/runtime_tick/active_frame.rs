@@ -1,11 +1,12 @@
-use crate::args::Args;
+use crate::args::{Args, ToolFlags};
 use crate::llm::prompt_manager::{augment_system, extract_system};
 use crate::llm::templates::RigTemplates;
+use crate::services::runtime_tools::effective_tools;
 use crate::render::{
     RenderTheme, SingleMessageRenderParams, message_to_viewport_text_cached,
     message_to_viewport_text_cached_with_layout,
 };
-use crate::types::{Message, ROLE_SYSTEM};
+use crate::types::{Message, ROLE_REASONING, ROLE_SYSTEM};
 use crate::framework::widget_system::interaction::input_click::update_input_view_top;
 use crate::framework::widget_system::runtime::logic::{build_label_suffixes, timer_text};
 use crate::framework::widget_system::runtime::runtime_helpers::TabState;
@@ -217,14 +218,18 @@ pub fn build_display_messages(app: &App, args: &Args) -> Vec<DisplayMessage> {
         return Vec::new();
     }
     let mut out = Vec::new();
+    let show_reasoning = app.reasoning_visible(args.show_reasoning);
     for (idx, msg) in app.messages.iter().enumerate() {
         if !args.show_system_prompt && msg.role == ROLE_SYSTEM {
             continue;
         }
         let mut message = msg.clone();
+        if !show_reasoning && msg.role == ROLE_REASONING {
+            message.content = collapsed_reasoning(&msg.content);
+        }
         if idx == 0
             && msg.role == ROLE_SYSTEM
-            && let Some(full) = build_full_prompt_for_display(&app.messages, &app.prompts_dir, args)
+            && let Some(full) = build_full_prompt_for_display(app)
             && !full.trim().is_empty()
         {
             message.content = full;
@@ -234,6 +239,13 @@ pub fn build_display_messages(app: &App, args: &Args) -> Vec<DisplayMessage> {
     out
 }
 
+fn collapsed_reasoning(content: &str) -> String {
+    format!(
+        "思考过程已折叠（{} 字，/reasoning 展开）",
+        content.chars().count()
+    )
+}
+
 pub fn select_visible_message(app: &mut App, messages: &[DisplayMessage]) -> Option<usize> {
     if messages.is_empty() {
         app.message_history.selected = 0;
@@ -268,43 +280,42 @@ fn find_display_message(
         .map(|msg| &msg.message)
 }
 
-fn build_full_prompt_for_display(
-    messages: &[Message],
-    prompts_dir: &str,
-    args: &Args,
-) -> Option<String> {
-    let templates = RigTemplates::load(prompts_dir).ok()?;
-    let enabled = enabled_tool_names(args);
+fn build_full_prompt_for_display(app: &App) -> Option<String> {
+    let templates = RigTemplates::load(&app.prompts_dir).ok()?;
+    let enabled = enabled_tool_names(effective_tools(app));
     let tools = templates.tool_defs().ok()?;
     let filtered = filter_tools_for_display(tools, &enabled);
-    let base_system = augment_system(&extract_system(messages));
+    let base_system = augment_system(&extract_system(&app.messages));
     if filtered.is_empty() {
         return Some(base_system);
     }
     templates.render_preamble(&base_system, &filtered).ok()
 }
 
-fn enabled_tool_names(args: &Args) -> Vec<&'static str> {
+fn enabled_tool_names(tools: ToolFlags) -> Vec<&'static str> {
     let mut out = Vec::new();
-    if args.web_search_enabled() {
+    if tools.web_search {
         out.push("web_search");
     }
-    if args.code_exec_enabled() {
+    if tools.code_exec {
         out.push("code_exec");
     }
-    if args.read_file_enabled() {
+    if tools.read_file {
         out.push("read_file");
         out.push("list_dir");
     }
-    if args.read_code_enabled() {
+    if tools.read_code {
         out.push("read_code");
     }
-    if args.modify_file_enabled() {
+    if tools.modify_file {
         out.push("modify_file");
     }
-    if args.ask_questions_enabled() {
+    if tools.ask_questions {
         out.push("ask_questions");
     }
+    if tools.delegate_task {
+        out.push("delegate_task");
+    }
     out
 }
 
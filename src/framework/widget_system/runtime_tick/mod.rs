@@ -12,7 +12,11 @@ pub use active_frame::{
 };
 pub use code_exec::update_code_exec_results;
 pub use exec_note::build_exec_header_note;
-pub use overlays::{sync_code_exec_overlay, sync_file_patch_overlay, sync_question_review_overlay};
+pub use overlays::{
+    sync_code_exec_overlay, sync_compare_overlay, sync_file_patch_overlay,
+    sync_question_review_overlay, sync_tools_overlay, sync_tree_overlay,
+    sync_workspace_changes_overlay,
+};
 pub use preheat::{apply_preheat_results, preheat_inactive_tabs};
 pub use stream::collect_stream_events_from_batch;
 pub use tabs::{finalize_done_tabs, update_tab_widths};
@@ -1,6 +1,8 @@
+use crate::framework::widget_system::interaction::selection_state::SelectionState;
 use crate::framework::widget_system::overlay::OverlayKind;
 use crate::framework::widget_system::runtime::runtime_helpers::TabState;
 use crate::framework::widget_system::runtime::runtime_view::ViewState;
+use crate::framework::widget_system::widgets::tree::build_tree_rows;
 
 pub fn sync_code_exec_overlay(tabs: &mut [TabState], active_tab: usize, view: &mut ViewState) {
     if let Some(tab_state) = tabs.get_mut(active_tab) {
@@ -39,3 +41,72 @@ pub fn sync_question_review_overlay(
         }
     }
 }
+
+pub fn sync_workspace_changes_overlay(
+    tabs: &mut [TabState],
+    active_tab: usize,
+    view: &mut ViewState,
+) {
+    if let Some(tab_state) = tabs.get_mut(active_tab) {
+        let has_pending = tab_state.app.pending_workspace_changes.is_some();
+        if has_pending && view.overlay.is_chat() {
+            view.overlay.open(OverlayKind::WorkspaceChanges);
+            view.workspace_changes = SelectionState::default();
+            view.workspace_changes_detail_scroll = 0;
+        } else if !has_pending && view.overlay.is(OverlayKind::WorkspaceChanges) {
+            view.overlay.close();
+        }
+    }
+}
+
+/// `/tools` 不带参数时打开工具面板。
+pub fn sync_tools_overlay(tabs: &mut [TabState], active_tab: usize, view: &mut ViewState) {
+    if let Some(tab_state) = tabs.get_mut(active_tab)
+        && std::mem::take(&mut tab_state.app.pending_tools_overlay)
+    {
+        view.tools = SelectionState::default();
+        view.overlay.open(OverlayKind::Tools);
+    }
+}
+
+/// `/tree` 或导航模式的 `t` 打开分支树，选中当前对话。
+pub fn sync_tree_overlay(tabs: &mut [TabState], active_tab: usize, view: &mut ViewState) {
+    let Some(tab_state) = tabs.get_mut(active_tab) else {
+        return;
+    };
+    if !std::mem::take(&mut tab_state.app.pending_tree_overlay) {
+        return;
+    }
+    view.tree_nodes = build_tree_rows(tabs)
+        .into_iter()
+        .map(|row| row.node)
+        .collect();
+    let selected = view
+        .tree_nodes
+        .iter()
+        .position(|node| node.tab_index == active_tab)
+        .unwrap_or(0);
+    view.tree = SelectionState::default();
+    view.tree.select(selected);
+    view.tree_mark = None;
+    view.tree_diff = None;
+    view.overlay.open(OverlayKind::Tree);
+}
+
+pub fn sync_compare_overlay(tabs: &mut [TabState], active_tab: usize, view: &mut ViewState) {
+    if let Some(tab_state) = tabs.get_mut(active_tab) {
+        let has_compare = tab_state.app.compare.is_some();
+        view.compare_columns = tab_state
+            .app
+            .compare
+            .as_ref()
+            .map_or(0, |compare| compare.entries.len());
+        if has_compare && view.overlay.is_chat() {
+            view.overlay.open(OverlayKind::Compare);
+            view.compare_selected = 0;
+            view.compare_scroll = 0;
+        } else if !has_compare && view.overlay.is(OverlayKind::Compare) {
+            view.overlay.close();
+        }
+    }
+}
@@ -2,7 +2,11 @@ use crate::render::RenderTheme;
 use crate::framework::widget_system::runtime::events::{LlmEvent, UiEvent};
 use crate::framework::widget_system::runtime::logic::{StreamAction, handle_stream_event};
 use crate::framework::widget_system::runtime::runtime_helpers::TabState;
-use crate::hooks::{EVENT_LLM_DONE, EVENT_LLM_ERROR, EVENT_LLM_TOOL_CALLS, run_hooks};
+use crate::llm::pricing::ModelPricing;
+use crate::model_registry::ModelRegistry;
+use crate::hooks::{
+    EVENT_LLM_DONE, EVENT_LLM_ERROR, EVENT_LLM_STALLED, EVENT_LLM_TOOL_CALLS, run_hooks,
+};
 
 type ToolQueue = Vec<(usize, Vec<crate::types::ToolCall>)>;
 pub(crate) type StreamCollectResult = (usize, Vec<usize>, ToolQueue);
@@ -11,12 +15,20 @@ pub fn collect_stream_events_from_batch(
     llm_events: &mut Vec<UiEvent>,
     tabs: &mut [TabState],
     theme: &RenderTheme,
+    registry: &ModelRegistry,
 ) -> StreamCollectResult {
     let processed = llm_events.len();
     let mut done_tabs: Vec<usize> = Vec::new();
     let mut tool_queue: ToolQueue = Vec::new();
     for event in llm_events.drain(..) {
-        handle_stream_event_for_tab(event, tabs, theme, &mut done_tabs, &mut tool_queue);
+        handle_stream_event_for_tab(
+            event,
+            tabs,
+            theme,
+            registry,
+            &mut done_tabs,
+            &mut tool_queue,
+        );
     }
     (processed, done_tabs, tool_queue)
 }
@@ -25,6 +37,7 @@ fn handle_stream_event_for_tab(
     ui_event: UiEvent,
     tabs: &mut [TabState],
     theme: &RenderTheme,
+    registry: &ModelRegistry,
     done_tabs: &mut Vec<usize>,
     tool_queue: &mut Vec<(usize, Vec<crate::types::ToolCall>)>,
 ) {
@@ -38,7 +51,12 @@ fn handle_stream_event_for_tab(
     if !is_active_request(tab_state, request_id) {
         return;
     }
-    apply_stream_event(tab_state, tab_idx, event, theme, done_tabs, tool_queue);
+    let pricing = registry
+        .get(&tab_state.app.model_key)
+        .and_then(|model| model.pricing);
+    apply_stream_event(
+        tab_state, tab_idx, event, theme, pricing, done_tabs, tool_queue,
+    );
 }
 
 fn tab_index_for(tab: &str, tabs: &[TabState]) -> Option<usize> {
@@ -50,18 +68,28 @@ fn apply_stream_event(
     tab_idx: usize,
     event: LlmEvent,
     theme: &RenderTheme,
+    pricing: Option<ModelPricing>,
     done_tabs: &mut Vec<usize>,
     tool_queue: &mut Vec<(usize, Vec<crate::types::ToolCall>)>,
 ) {
     let hook_call = collect_hook_call(tab_state, tab_idx, &event);
-    let call_done_on_error = matches!(&event, LlmEvent::Error(_));
+    let call_done_on_error = ends_with_failure(&event);
     let elapsed = elapsed_millis(tab_state);
-    let action = handle_stream_event(&mut tab_state.app, event, elapsed);
+    let action = handle_stream_event(&mut tab_state.app, event, elapsed, pricing.as_ref());
     apply_stream_action(action, tab_idx, done_tabs, tool_queue);
     tab_state.apply_cache_shift(theme);
     fire_llm_hooks(tab_state, hook_call, call_done_on_error);
 }
 
+/// 出错或停滞且不再重试时，请求就此结束，仍需补发 llm.done。
+fn ends_with_failure(event: &LlmEvent) -> bool {
+    match event {
+        LlmEvent::Error(_) => true,
+        LlmEvent::Stalled { will_retry, .. } => !will_retry,
+        _ => false,
+    }
+}
+
 fn collect_hook_call(
     tab_state: &TabState,
     tab_idx: usize,
@@ -133,6 +161,11 @@ fn hook_for_llm_event(
             vars.push(("HOOK_ERROR".to_string(), err.to_string()));
             Some((EVENT_LLM_ERROR, vars))
         }
+        LlmEvent::Stalled { reason, will_retry } => {
+            vars.push(("HOOK_STALL_REASON".to_string(), reason.clone()));
+            vars.push(("HOOK_STALL_RETRY".to_string(), will_retry.to_string()));
+            Some((EVENT_LLM_STALLED, vars))
+        }
         _ => None,
     }
 }
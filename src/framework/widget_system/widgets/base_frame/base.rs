@@ -57,7 +57,7 @@ impl BaseFrameWidget {
             FlexAxis::Vertical,
             (HeaderWidget, FlexParam::Fixed(1)),
             (body, FlexParam::Flex(1)),
-            (FooterWidget, FlexParam::Fixed(1)),
+            (FooterWidget::default(), FlexParam::Fixed(1)),
         );
         Self {
             global_keys: WidgetPod::new(GlobalKeyWidget),
@@ -8,8 +8,53 @@ use ratatui::layout::Rect;
 use ratatui::style::Style;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
+use crate::llm::context_budget::{
+    ContextBudget, ContextReport, context_report, estimate_tokens, system_prompt_tokens,
+};
+use crate::ui::state::App;
 
-pub(super) struct FooterWidget;
+#[derive(Default)]
+pub(super) struct FooterWidget {
+    context_cache: Option<(ContextKey, ContextReport)>,
+}
+
+/// 消息内容只会在末尾追加或整体替换，用条数+末条长度+输入长度判断是否需要重新估算。
+#[derive(Clone, PartialEq, Eq)]
+struct ContextKey {
+    model_key: String,
+    messages: usize,
+    last_len: usize,
+    input_len: usize,
+}
+
+impl FooterWidget {
+    fn context_report(
+        &mut self,
+        app: &App,
+        models: &[crate::model_registry::ModelProfile],
+    ) -> ContextReport {
+        let input = app.input.lines().join("\n");
+        let key = ContextKey {
+            model_key: app.model_key.clone(),
+            messages: app.messages.len(),
+            last_len: app.messages.last().map(|m| m.content.len()).unwrap_or(0),
+            input_len: input.len(),
+        };
+        if let Some((cached_key, report)) = &self.context_cache
+            && *cached_key == key
+        {
+            return *report;
+        }
+        let budget = models
+            .iter()
+            .find(|m| m.key == app.model_key)
+            .and_then(|m| ContextBudget::new(m.context_window, m.max_tokens));
+        let fixed = system_prompt_tokens(&app.messages) + estimate_tokens(&input);
+        let report = context_report(&app.messages, fixed, budget);
+        self.context_cache = Some((key, report));
+        report
+    }
+}
 
 impl Widget for FooterWidget {
     fn update(
@@ -39,35 +84,51 @@ impl Widget for FooterWidget {
         _update: &UpdateOutput,
         rect: ratatui::layout::Rect,
     ) -> Result<(), Box<dyn Error>> {
-        if let Some(app) = frame.state.active_app() {
-            draw_footer(
-                frame.frame,
-                rect,
-                frame.state.theme,
-                app.nav_mode,
-                app.follow,
-                frame.view.fps,
-            );
-        }
+        let models = frame.state.models;
+        let Some(app) = frame.state.active_app() else {
+            return Ok(());
+        };
+        let context = self.context_report(app, models);
+        draw_footer(
+            frame.frame,
+            rect,
+            frame.state.theme,
+            FooterStatus {
+                nav_mode: app.nav_mode,
+                follow: app.follow,
+                context,
+            },
+            frame.view.fps,
+        );
         Ok(())
     }
 }
 
+struct FooterStatus {
+    nav_mode: bool,
+    follow: bool,
+    context: ContextReport,
+}
+
 fn draw_footer(
     f: &mut ratatui::Frame<'_>,
     area: Rect,
     theme: &RenderTheme,
-    nav_mode: bool,
-    follow: bool,
+    status: FooterStatus,
     fps: u32,
 ) {
     let time = chrono::Local::now().format("%H:%M:%S").to_string();
     let mut parts = vec![time];
-    if nav_mode {
+    if status.nav_mode {
         parts.push("NAV".to_string());
     }
-    let follow_text = if follow { "追底:开" } else { "追底:关" };
+    let follow_text = if status.follow {
+        "追底:开"
+    } else {
+        "追底:关"
+    };
     parts.push(follow_text.to_string());
+    parts.push(format_context(&status.context));
     let left = parts.join("  ");
     let right = format!("FPS {fps}");
     let text = align_footer_text(&left, &right, area.width as usize);
@@ -77,6 +138,31 @@ fn draw_footer(
     f.render_widget(paragraph, area);
 }
 
+fn format_context(report: &ContextReport) -> String {
+    let used = format_tokens(report.estimated_tokens);
+    let mut text = match report.window {
+        Some(window) => format!("上下文 ≈{used}/{}", format_tokens(window)),
+        None => format!("上下文 ≈{used}"),
+    };
+    if report.dropped_messages > 0 {
+        text.push_str(&format!(" · 已截断 {} 条", report.dropped_messages));
+    } else if report.elided_tool_outputs > 0 {
+        text.push_str(&format!(
+            " · 已压缩 {} 条工具输出",
+            report.elided_tool_outputs
+        ));
+    }
+    text
+}
+
+fn format_tokens(tokens: usize) -> String {
+    if tokens >= 1000 {
+        format!("{:.1}k", tokens as f64 / 1000.0)
+    } else {
+        tokens.to_string()
+    }
+}
+
 fn align_footer_text(left: &str, right: &str, width: usize) -> String {
     if width == 0 {
         return String::new();
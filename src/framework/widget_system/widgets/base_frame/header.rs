@@ -2,9 +2,10 @@ use crate::render::RenderTheme;
 use crate::framework::widget_system::runtime::runtime_loop_steps::FrameLayout;
 use crate::framework::widget_system::context::{EventCtx, UpdateCtx, UpdateOutput, WidgetFrame};
 use crate::framework::widget_system::lifecycle::{EventResult, Widget};
+use crate::services::code_exec_container_env::host_backend_enabled;
 use std::error::Error;
 use ratatui::layout::{Alignment, Rect};
-use ratatui::style::{Color, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 
@@ -62,7 +63,17 @@ fn draw_header(
     } else {
         "deepchat".to_string()
     };
-    let line = Line::from(Span::styled(text, style));
+    let mut spans = vec![Span::styled(text, style)];
+    if host_backend_enabled() {
+        spans.push(Span::styled(
+            "  ·  ⚠ 本机执行模式：隔离较弱",
+            Style::default()
+                .bg(theme.bg)
+                .fg(Color::Red)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    let line = Line::from(spans);
     let paragraph = Paragraph::new(line).alignment(Alignment::Center);
     f.render_widget(paragraph, area);
 }
@@ -87,6 +87,8 @@ impl Widget for InputWidget {
                     busy: app.busy,
                     model_key: &app.model_key,
                     prompt_key: &app.prompt_key,
+                    attachments: &app.pending_attachments,
+                    editing_prompt: app.editing_prompt.as_deref(),
                 },
             );
         }
@@ -102,6 +104,8 @@ struct InputDrawParams<'a, 'b> {
     busy: bool,
     model_key: &'a str,
     prompt_key: &'a str,
+    attachments: &'a [crate::types::Attachment],
+    editing_prompt: Option<&'a str>,
 }
 
 fn draw_input<'a, 'b>(f: &mut ratatui::Frame<'_>, params: InputDrawParams<'a, 'b>) {
@@ -113,7 +117,18 @@ fn draw_input<'a, 'b>(f: &mut ratatui::Frame<'_>, params: InputDrawParams<'a, 'b
         params.model_key,
         params.prompt_key,
     );
-    let block = build_block(status, style, border_style);
+    let mut block = build_block(status, style, border_style);
+    if !params.attachments.is_empty() {
+        block = block.title_bottom(format!(
+            "{} · /image clear 清空",
+            crate::attachments::placeholders(params.attachments)
+        ));
+    }
+    if let Some(key) = params.editing_prompt {
+        block = block.title_bottom(format!(
+            "正在编辑提示词 {key} · Enter 保存 · 清空后 Enter 放弃"
+        ));
+    }
     params.input.set_block(block);
     params.input.set_style(style);
     params
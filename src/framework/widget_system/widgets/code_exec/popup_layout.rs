@@ -13,6 +13,7 @@ pub(crate) struct CodeExecPopupLayout {
     pub(crate) stdout_scrollbar_area: Rect,
     pub(crate) stderr_text_area: Rect,
     pub(crate) stderr_scrollbar_area: Rect,
+    pub(crate) stdin_input_area: Rect,
     pub(crate) reason_input_area: Rect,
     pub(crate) approve_btn: Rect,
     pub(crate) deny_btn: Rect,
@@ -32,6 +33,7 @@ pub(crate) fn code_exec_popup_layout(area: Rect, with_reason: bool) -> CodeExecP
         stdout_scrollbar_area,
         stderr_text_area,
         stderr_scrollbar_area,
+        stdin_input_area,
     ) = split_body(body);
     let (approve_btn, deny_btn, stop_btn, exit_btn) = action_buttons(actions_area);
     CodeExecPopupLayout {
@@ -42,6 +44,7 @@ pub(crate) fn code_exec_popup_layout(area: Rect, with_reason: bool) -> CodeExecP
         stdout_scrollbar_area,
         stderr_text_area,
         stderr_scrollbar_area,
+        stdin_input_area,
         reason_input_area: reason_area,
         approve_btn,
         deny_btn,
@@ -100,12 +103,14 @@ fn split_inner(inner: Rect, with_reason: bool) -> (Rect, Rect, Rect) {
     (body, reason_area, actions_area)
 }
 
-fn split_body(body: Rect) -> (Rect, Rect, Rect, Rect, Rect, Rect) {
+fn split_body(body: Rect) -> (Rect, Rect, Rect, Rect, Rect, Rect, Rect) {
     let body_cols =
         Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).split(body);
     let (code_text_area, code_scrollbar_area) = text_and_scrollbar(body_cols[0]);
+    let io_chunks =
+        Layout::vertical([Constraint::Min(2), Constraint::Length(3)]).split(body_cols[1]);
     let out_chunks = Layout::vertical([Constraint::Percentage(60), Constraint::Percentage(40)])
-        .split(body_cols[1]);
+        .split(io_chunks[0]);
     let (stdout_text_area, stdout_scrollbar_area) = text_and_scrollbar(out_chunks[0]);
     let (stderr_text_area, stderr_scrollbar_area) = text_and_scrollbar(out_chunks[1]);
     (
@@ -115,6 +120,7 @@ fn split_body(body: Rect) -> (Rect, Rect, Rect, Rect, Rect, Rect) {
         stdout_scrollbar_area,
         stderr_text_area,
         stderr_scrollbar_area,
+        io_chunks[1],
     )
 }
 
@@ -48,6 +48,7 @@ pub(super) fn render_code_exec_overlay(
     let hover = tab_state.app.code_exec_hover;
     let reason_target = tab_state.app.code_exec_reason_target;
     let mut reason_input = std::mem::take(&mut tab_state.app.code_exec_reason_input);
+    let mut stdin_input = std::mem::take(&mut tab_state.app.code_exec_stdin_input);
     {
     let mut params = build_params(
         rect,
@@ -56,10 +57,12 @@ pub(super) fn render_code_exec_overlay(
         &pending,
         live_snapshot.as_ref(),
             &mut reason_input,
+            &mut stdin_input,
         );
         draw_code_exec_popup_base(frame.frame, &mut params);
     }
     tab_state.app.code_exec_reason_input = reason_input;
+    tab_state.app.code_exec_stdin_input = stdin_input;
     render_buttons(
         widget,
         frame,
@@ -111,6 +114,7 @@ fn build_params<'a>(
     pending: &'a PendingCodeExec,
     live: Option<&'a crate::framework::widget_system::runtime::state::CodeExecLive>,
     reason_input: &'a mut tui_textarea::TextArea<'static>,
+    stdin_input: &'a mut tui_textarea::TextArea<'static>,
 ) -> CodeExecPopupParams<'a, 'static> {
     CodeExecPopupParams {
         area,
@@ -120,6 +124,7 @@ fn build_params<'a>(
         stderr_scroll: tab_state.app.code_exec_stderr_scroll,
         reason_target: tab_state.app.code_exec_reason_target,
         reason_input,
+        stdin_input,
         live,
         code_selection: tab_state.app.code_exec_code_selection,
         stdout_selection: tab_state.app.code_exec_stdout_selection,
@@ -192,6 +197,7 @@ struct CodeExecPopupParams<'a, 'b> {
     stderr_scroll: usize,
     reason_target: Option<CodeExecReasonTarget>,
     reason_input: &'a mut TextArea<'b>,
+    stdin_input: &'a mut TextArea<'b>,
     live: Option<&'a crate::framework::widget_system::runtime::state::CodeExecLive>,
     code_selection: Option<Selection>,
     stdout_selection: Option<Selection>,
@@ -206,6 +212,13 @@ fn draw_code_exec_popup_base<'a, 'b>(
     let layout = code_exec_popup_layout(params.area, params.reason_target.is_some());
     render_popup_base_layer(f, params, layout);
     render_panels(f, params, layout);
+    draw_stdin_input(
+        f,
+        layout.stdin_input_area,
+        params.stdin_input,
+        params.live,
+        params.theme,
+    );
     render_reason_if_needed(f, params, layout);
 }
 
@@ -507,10 +520,67 @@ fn draw_reason_input(
     f.render_widget(&*input, area);
 }
 
+fn draw_stdin_input(
+    f: &mut ratatui::Frame<'_>,
+    area: Rect,
+    input: &mut TextArea<'_>,
+    live: Option<&crate::framework::widget_system::runtime::state::CodeExecLive>,
+    theme: &crate::render::RenderTheme,
+) {
+    if area.width == 0 || area.height == 0 {
+        return;
+    }
+    let (title, placeholder) = stdin_title(live);
+    let style = base_style(theme);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title_top(Line::from(title))
+        .style(style);
+    input.set_block(block);
+    input.set_style(style);
+    input.set_cursor_line_style(Style::default());
+    input.set_cursor_style(if stdin_open(live) {
+        Style::default().add_modifier(Modifier::REVERSED)
+    } else {
+        style
+    });
+    input.set_placeholder_text(placeholder);
+    input.set_placeholder_style(Style::default().fg(base_fg(theme)));
+    f.render_widget(&*input, area);
+}
+
+fn stdin_open(live: Option<&crate::framework::widget_system::runtime::state::CodeExecLive>) -> bool {
+    live.is_some_and(|l| !l.done && !l.stdin_closed)
+}
+
+fn stdin_title(
+    live: Option<&crate::framework::widget_system::runtime::state::CodeExecLive>,
+) -> (String, &'static str) {
+    let Some(live) = live else {
+        return ("STDIN".to_string(), "执行后可在此输入");
+    };
+    let sent = live.stdin_log.lines().count();
+    if live.stdin_closed {
+        return (format!("STDIN · 已发送 {sent} 行 · 已 EOF"), "stdin 已关闭");
+    }
+    if live.done {
+        return (format!("STDIN · 已发送 {sent} 行"), "进程已结束");
+    }
+    (
+        format!("STDIN · 已发送 {sent} 行 · Enter 发送 · Ctrl+D EOF"),
+        "输入内容发送到进程 stdin",
+    )
+}
+
 fn build_title(live: Option<&crate::framework::widget_system::runtime::state::CodeExecLive>) -> String {
-    match live {
+    let title = match live {
         Some(live) => build_live_title(live),
         None => "代码执行确认 · 等待确认".to_string(),
+    };
+    if crate::services::code_exec_container_env::host_backend_enabled() {
+        format!("⚠ 本机执行（无容器隔离） · {title}")
+    } else {
+        title
     }
 }
 
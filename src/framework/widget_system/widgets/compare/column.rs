@@ -0,0 +1,165 @@
+use crate::framework::widget_system::context::{EventCtx, UpdateCtx, UpdateOutput, WidgetFrame};
+use crate::framework::widget_system::draw::style::{base_fg, base_style, focus_border_style};
+use crate::framework::widget_system::lifecycle::{EventResult, Widget};
+use crate::framework::widget_system::runtime::logic::format_timer;
+use crate::framework::widget_system::runtime::runtime_helpers::TabState;
+use crate::framework::widget_system::runtime::runtime_loop_steps::FrameLayout;
+use crate::framework::widget_system::runtime::state::CompareEntry;
+use crate::llm::pricing::format_cost;
+use crate::render::{RenderTheme, render_markdown_lines};
+use crate::types::ROLE_ASSISTANT;
+use crossterm::event::{MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use std::error::Error;
+
+const SCROLL_STEP: u16 = 3;
+
+/// 对比弹窗中的一列：展示某个模型在对比标签页中的回答。
+pub(crate) struct CompareColumnWidget {
+    index: usize,
+}
+
+impl CompareColumnWidget {
+    pub(crate) fn new(index: usize) -> Self {
+        Self { index }
+    }
+}
+
+impl Widget for CompareColumnWidget {
+    fn update(
+        &mut self,
+        _ctx: &mut UpdateCtx<'_>,
+        _layout: &FrameLayout,
+        _update: &UpdateOutput,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    fn event(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        event: &crossterm::event::Event,
+        _layout: &FrameLayout,
+        _update: &UpdateOutput,
+        _rect: Rect,
+    ) -> Result<EventResult, Box<dyn Error>> {
+        let crossterm::event::Event::Mouse(MouseEvent { kind, .. }) = event else {
+            return Ok(EventResult::ignored());
+        };
+        let view = &mut *ctx.view;
+        match kind {
+            MouseEventKind::Down(_) => view.compare_selected = self.index,
+            MouseEventKind::ScrollUp => {
+                view.compare_scroll = view.compare_scroll.saturating_sub(SCROLL_STEP)
+            }
+            MouseEventKind::ScrollDown => {
+                view.compare_scroll = view.compare_scroll.saturating_add(SCROLL_STEP)
+            }
+            _ => return Ok(EventResult::ignored()),
+        }
+        Ok(EventResult::handled())
+    }
+
+    fn render(
+        &mut self,
+        frame: &mut WidgetFrame<'_, '_, '_, '_>,
+        _layout: &FrameLayout,
+        _update: &UpdateOutput,
+        rect: Rect,
+    ) -> Result<(), Box<dyn Error>> {
+        if rect.width < 3 || rect.height < 3 {
+            return Ok(());
+        }
+        let tabs = &*frame.state.tabs;
+        let Some(entry) = tabs
+            .get(frame.state.active_tab)
+            .and_then(|tab| tab.app.compare.as_ref())
+            .and_then(|compare| compare.entries.get(self.index))
+        else {
+            return Ok(());
+        };
+        let tab = tabs
+            .iter()
+            .find(|tab| tab.conversation_id == entry.conversation_id);
+        let theme = frame.state.theme;
+        let selected = frame.view.compare_selected == self.index;
+        let inner_width = rect.width.saturating_sub(2);
+        let mut lines = vec![
+            Line::from(Span::styled(
+                status_text(entry, tab),
+                Style::default().add_modifier(Modifier::DIM),
+            )),
+            Line::default(),
+        ];
+        lines.extend(render_markdown_lines(
+            &answer_text(entry, tab),
+            inner_width.max(1) as usize,
+            theme,
+            false,
+            false,
+        ));
+        let para = Paragraph::new(Text::from(lines))
+            .style(base_style(theme))
+            .scroll((frame.view.compare_scroll, 0))
+            .block(column_block(&entry.model_key, selected, theme));
+        frame.frame.render_widget(para, rect);
+        Ok(())
+    }
+}
+
+fn column_block<'a>(model_key: &'a str, selected: bool, theme: &RenderTheme) -> Block<'a> {
+    let mut title_style = Style::default().fg(base_fg(theme));
+    if selected {
+        title_style = title_style.add_modifier(Modifier::BOLD);
+    }
+    Block::default()
+        .borders(Borders::ALL)
+        .title_top(Line::from(Span::styled(model_key, title_style)))
+        .border_style(focus_border_style(theme, selected))
+}
+
+fn status_text(entry: &CompareEntry, tab: Option<&TabState>) -> String {
+    let Some(tab) = tab else {
+        return "标签页已关闭".to_string();
+    };
+    let app = &tab.app;
+    if app.pending_code_exec.is_some() || app.pending_file_patch.is_some() {
+        return "等待工具确认（切换到该标签页处理）".to_string();
+    }
+    match entry.elapsed_ms {
+        Some(ms) if !app.busy => {
+            let mut text = format!(
+                "用时 {} · tokens: {}/{}/{}",
+                format_timer(ms),
+                app.total_prompt_tokens,
+                app.total_completion_tokens,
+                app.total_tokens
+            );
+            if let Some(cost) = app.total_cost {
+                text.push_str(&format!(" · {}", format_cost(cost)));
+            }
+            text
+        }
+        _ => format!(
+            "生成中 {}",
+            format_timer(entry.started_at.elapsed().as_millis() as u64)
+        ),
+    }
+}
+
+fn answer_text(entry: &CompareEntry, tab: Option<&TabState>) -> String {
+    let Some(tab) = tab else {
+        return String::new();
+    };
+    tab.app
+        .messages
+        .iter()
+        .skip(entry.history_len)
+        .filter(|msg| msg.role == ROLE_ASSISTANT && !msg.content.trim().is_empty())
+        .map(|msg| msg.content.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
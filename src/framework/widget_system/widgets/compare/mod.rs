@@ -0,0 +1,161 @@
+mod column;
+
+use crate::framework::widget_system::BoxConstraints;
+use crate::framework::widget_system::draw::style::{base_fg, base_style};
+use crate::framework::widget_system::runtime::runtime_loop_steps::FrameLayout;
+use crate::framework::widget_system::widget_pod::WidgetPod;
+use crate::framework::widget_system::widgets::layout::{Flex2, Flex3, FlexAxis, FlexParam};
+use crate::render::RenderTheme;
+use ratatui::layout::{Rect, Size};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear};
+use std::error::Error;
+
+use super::super::bindings::bind_event;
+use super::super::context::{EventCtx, LayoutCtx, UpdateCtx, UpdateOutput, WidgetFrame};
+use super::super::lifecycle::{EventResult, Widget};
+use super::overlay_table::OverlayTableController;
+use column::CompareColumnWidget;
+
+const OUTER_MARGIN: u16 = 1;
+
+type ComparePair = Flex2<CompareColumnWidget, CompareColumnWidget>;
+type CompareTriple = Flex3<CompareColumnWidget, CompareColumnWidget, CompareColumnWidget>;
+
+pub(crate) struct CompareWidget {
+    pair: WidgetPod<ComparePair>,
+    triple: WidgetPod<CompareTriple>,
+}
+
+impl CompareWidget {
+    pub(crate) fn new() -> Self {
+        let pair = ComparePair::new(
+            FlexAxis::Horizontal,
+            (CompareColumnWidget::new(0), FlexParam::Flex(1)),
+            (CompareColumnWidget::new(1), FlexParam::Flex(1)),
+        );
+        let triple = CompareTriple::new(
+            FlexAxis::Horizontal,
+            (CompareColumnWidget::new(0), FlexParam::Flex(1)),
+            (CompareColumnWidget::new(1), FlexParam::Flex(1)),
+            (CompareColumnWidget::new(2), FlexParam::Flex(1)),
+        );
+        Self {
+            pair: WidgetPod::new(pair),
+            triple: WidgetPod::new(triple),
+        }
+    }
+}
+
+impl Widget for CompareWidget {
+    fn measure(
+        &mut self,
+        ctx: &mut LayoutCtx<'_>,
+        bc: BoxConstraints,
+    ) -> Result<Size, Box<dyn Error>> {
+        let inner = columns_area(Rect::new(0, 0, bc.max.width, bc.max.height));
+        let inner_bc = BoxConstraints::tight(Size {
+            width: inner.width,
+            height: inner.height,
+        });
+        let _ = self.pair.measure(ctx, inner_bc)?;
+        let _ = self.triple.measure(ctx, inner_bc)?;
+        Ok(bc.max)
+    }
+
+    fn place(
+        &mut self,
+        ctx: &mut LayoutCtx<'_>,
+        layout: &mut FrameLayout,
+        rect: Rect,
+    ) -> Result<(), Box<dyn Error>> {
+        let inner = columns_area(rect);
+        self.pair.place(ctx, layout, inner)?;
+        self.triple.place(ctx, layout, inner)?;
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        _ctx: &mut UpdateCtx<'_>,
+        _layout: &FrameLayout,
+        _update: &UpdateOutput,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    fn event(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        event: &crossterm::event::Event,
+        layout: &FrameLayout,
+        update: &UpdateOutput,
+        _rect: Rect,
+    ) -> Result<EventResult, Box<dyn Error>> {
+        if let crossterm::event::Event::Mouse(_) = event {
+            return if ctx.view.compare_columns >= 3 {
+                self.triple.event(ctx, event, layout, update)
+            } else {
+                self.pair.event(ctx, event, layout, update)
+            };
+        }
+        let binding = bind_event(ctx, layout, update);
+        let mut controller = OverlayTableController {
+            dispatch: binding.dispatch,
+            layout: binding.layout,
+            view: binding.view,
+        };
+        controller.handle_event(event)
+    }
+
+    fn render(
+        &mut self,
+        frame: &mut WidgetFrame<'_, '_, '_, '_>,
+        layout: &FrameLayout,
+        update: &UpdateOutput,
+        rect: Rect,
+    ) -> Result<(), Box<dyn Error>> {
+        render_popup_base(frame.frame, frame.state.theme, popup_area(rect));
+        if frame.view.compare_columns >= 3 {
+            self.triple.render(frame, layout, update)
+        } else {
+            self.pair.render(frame, layout, update)
+        }
+    }
+}
+
+fn popup_area(area: Rect) -> Rect {
+    Rect {
+        x: area.x.saturating_add(OUTER_MARGIN),
+        y: area.y.saturating_add(OUTER_MARGIN),
+        width: area.width.saturating_sub(OUTER_MARGIN.saturating_mul(2)),
+        height: area.height.saturating_sub(OUTER_MARGIN.saturating_mul(2)),
+    }
+}
+
+fn columns_area(area: Rect) -> Rect {
+    let popup = popup_area(area);
+    Rect {
+        x: popup.x.saturating_add(1),
+        y: popup.y.saturating_add(1),
+        width: popup.width.saturating_sub(2),
+        height: popup.height.saturating_sub(2),
+    }
+}
+
+fn render_popup_base(f: &mut ratatui::Frame<'_>, theme: &RenderTheme, popup: Rect) {
+    f.render_widget(Clear, popup);
+    let title = "模型对比 · ←/→ 选择 · ↑/↓ 滚动 · Enter 保留并继续 · Esc 放弃";
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title_top(Line::from(vec![Span::styled(
+            title,
+            Style::default()
+                .fg(base_fg(theme))
+                .add_modifier(Modifier::BOLD),
+        )]))
+        .style(base_style(theme))
+        .border_style(Style::default().fg(base_fg(theme)));
+    f.render_widget(block, popup);
+}
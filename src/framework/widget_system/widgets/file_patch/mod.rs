@@ -7,4 +7,5 @@ mod scroll;
 mod selection;
 mod widget;
 
+pub(crate) use popup_text::{build_patch_text, patch_max_scroll};
 pub(crate) use widget::FilePatchWidget;
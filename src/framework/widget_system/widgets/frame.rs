@@ -3,6 +3,8 @@ use crate::framework::widget_system::runtime::runtime_loop_steps::{
     prepare_categories, process_stream_updates, tab_labels_and_pos,
 };
 use crate::framework::widget_system::runtime_tick::{ActiveFrameData, apply_preheat_results};
+use crate::services::runtime_autosave::refresh_snapshot;
+use crate::services::runtime_titles::apply_title_events;
 use std::error::Error;
 
 use super::super::context::{UpdateCtx, UpdateOutput};
@@ -17,11 +19,13 @@ impl FrameLifecycle {
     ) -> Result<UpdateOutput, Box<dyn Error>> {
         apply_preheat_results(&mut ctx.events.preheat, ctx.tabs);
         crate::framework::widget_system::widgets::terminal::apply_terminal_events(&mut ctx.events.terminal, ctx.tabs);
+        apply_title_events(&mut ctx.events.titles, ctx.tabs);
         let tabs = prepare_tabs(ctx);
         run_stream_updates(ctx, layout)?;
         let active_data = build_active_data(ctx, layout);
         let header_note = header_note(ctx.tabs, ctx.categories);
         handle_pending_actions(ctx, &active_data);
+        refresh_snapshot(ctx.tabs, *ctx.active_tab);
         Ok(UpdateOutput {
             active_data,
             tab_labels: tabs.labels,
@@ -55,6 +59,7 @@ fn run_stream_updates(ctx: &mut UpdateCtx<'_>, layout: &FrameLayout) -> Result<(
         theme: ctx.theme,
         msg_width: layout.layout.msg_width,
         registry: ctx.registry,
+        prompt_registry: ctx.prompt_registry,
         args: ctx.args,
         tx: ctx.tx,
         preheat_tx: ctx.preheat_tx,
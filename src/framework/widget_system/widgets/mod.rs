@@ -1,6 +1,7 @@
 mod base_frame;
 mod button;
 mod code_exec;
+mod compare;
 mod file_patch;
 mod frame;
 pub(crate) mod help;
@@ -15,6 +16,9 @@ mod root;
 pub(crate) mod summary;
 pub(crate) mod tab_bar;
 pub(crate) mod terminal;
+mod tools;
+pub(crate) mod tree;
+mod workspace_changes;
 
 pub(crate) use frame::FrameLifecycle;
 pub(crate) use root::RootWidget;
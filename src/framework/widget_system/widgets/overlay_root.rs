@@ -13,6 +13,7 @@ use super::super::context::{EventCtx, LayoutCtx, UpdateCtx, UpdateOutput, Widget
 use crate::framework::widget_system::runtime::runtime_loop_steps::FrameLayout;
 
 use super::code_exec::CodeExecWidget;
+use super::compare::CompareWidget;
 use super::file_patch::FilePatchWidget;
 use super::help::HelpWidget;
 use super::jump::JumpWidget;
@@ -21,6 +22,9 @@ use super::prompt::PromptWidget;
 use super::question_review::QuestionReviewWidget;
 use super::summary::SummaryWidget;
 use super::terminal::TerminalWidget;
+use super::tools::ToolsWidget;
+use super::tree::TreeWidget;
+use super::workspace_changes::WorkspaceChangesWidget;
 
 pub(crate) struct OverlayRootWidget {
     summary: WidgetPod<SummaryWidget>,
@@ -28,10 +32,14 @@ pub(crate) struct OverlayRootWidget {
     model: WidgetPod<ModelWidget>,
     prompt: WidgetPod<PromptWidget>,
     question_review: WidgetPod<QuestionReviewWidget>,
+    workspace_changes: WidgetPod<WorkspaceChangesWidget>,
     code_exec: WidgetPod<CodeExecWidget>,
     file_patch: WidgetPod<FilePatchWidget>,
     terminal: WidgetPod<TerminalWidget>,
     help: WidgetPod<HelpWidget>,
+    compare: WidgetPod<CompareWidget>,
+    tools: WidgetPod<ToolsWidget>,
+    tree: WidgetPod<TreeWidget>,
 }
 
 impl OverlayRootWidget {
@@ -42,10 +50,14 @@ impl OverlayRootWidget {
             model: WidgetPod::new(ModelWidget::new()),
             prompt: WidgetPod::new(PromptWidget::new()),
             question_review: WidgetPod::new(QuestionReviewWidget::new()),
+            workspace_changes: WidgetPod::new(WorkspaceChangesWidget::new()),
             code_exec: WidgetPod::new(CodeExecWidget::new()),
             file_patch: WidgetPod::new(FilePatchWidget::new()),
             terminal: WidgetPod::new(TerminalWidget::new()),
             help: WidgetPod::new(HelpWidget::new()),
+            compare: WidgetPod::new(CompareWidget::new()),
+            tools: WidgetPod::new(ToolsWidget::new()),
+            tree: WidgetPod::new(TreeWidget::new()),
         }
     }
 
@@ -65,10 +77,14 @@ impl Widget for OverlayRootWidget {
         let _ = self.model.measure(ctx, bc)?;
         let _ = self.prompt.measure(ctx, bc)?;
         let _ = self.question_review.measure(ctx, bc)?;
+        let _ = self.workspace_changes.measure(ctx, bc)?;
         let _ = self.code_exec.measure(ctx, bc)?;
         let _ = self.file_patch.measure(ctx, bc)?;
         let _ = self.terminal.measure(ctx, bc)?;
         let _ = self.help.measure(ctx, bc)?;
+        let _ = self.compare.measure(ctx, bc)?;
+        let _ = self.tools.measure(ctx, bc)?;
+        let _ = self.tree.measure(ctx, bc)?;
         Ok(bc.max)
     }
 
@@ -83,10 +99,14 @@ impl Widget for OverlayRootWidget {
         self.model.place(ctx, layout, rect)?;
         self.prompt.place(ctx, layout, rect)?;
         self.question_review.place(ctx, layout, rect)?;
+        self.workspace_changes.place(ctx, layout, rect)?;
         self.code_exec.place(ctx, layout, rect)?;
         self.file_patch.place(ctx, layout, rect)?;
         self.terminal.place(ctx, layout, rect)?;
         self.help.place(ctx, layout, rect)?;
+        self.compare.place(ctx, layout, rect)?;
+        self.tools.place(ctx, layout, rect)?;
+        self.tree.place(ctx, layout, rect)?;
         Ok(())
     }
 
@@ -102,10 +122,16 @@ impl Widget for OverlayRootWidget {
             Some(OverlayKind::Model) => self.model.update(ctx, layout, update)?,
             Some(OverlayKind::Prompt) => self.prompt.update(ctx, layout, update)?,
             Some(OverlayKind::QuestionReview) => self.question_review.update(ctx, layout, update)?,
+            Some(OverlayKind::WorkspaceChanges) => {
+                self.workspace_changes.update(ctx, layout, update)?
+            }
             Some(OverlayKind::CodeExec) => self.code_exec.update(ctx, layout, update)?,
             Some(OverlayKind::FilePatch) => self.file_patch.update(ctx, layout, update)?,
             Some(OverlayKind::Terminal) => self.terminal.update(ctx, layout, update)?,
             Some(OverlayKind::Help) => self.help.update(ctx, layout, update)?,
+            Some(OverlayKind::Compare) => self.compare.update(ctx, layout, update)?,
+            Some(OverlayKind::Tools) => self.tools.update(ctx, layout, update)?,
+            Some(OverlayKind::Tree) => self.tree.update(ctx, layout, update)?,
             None => {}
         }
         Ok(())
@@ -138,10 +164,16 @@ impl Widget for OverlayRootWidget {
             Some(OverlayKind::Model) => self.model.render(frame, layout, update)?,
             Some(OverlayKind::Prompt) => self.prompt.render(frame, layout, update)?,
             Some(OverlayKind::QuestionReview) => self.question_review.render(frame, layout, update)?,
+            Some(OverlayKind::WorkspaceChanges) => {
+                self.workspace_changes.render(frame, layout, update)?
+            }
             Some(OverlayKind::CodeExec) => self.code_exec.render(frame, layout, update)?,
             Some(OverlayKind::FilePatch) => self.file_patch.render(frame, layout, update)?,
             Some(OverlayKind::Terminal) => self.terminal.render(frame, layout, update)?,
             Some(OverlayKind::Help) => self.help.render(frame, layout, update)?,
+            Some(OverlayKind::Compare) => self.compare.render(frame, layout, update)?,
+            Some(OverlayKind::Tools) => self.tools.render(frame, layout, update)?,
+            Some(OverlayKind::Tree) => self.tree.render(frame, layout, update)?,
             None => {}
         }
         Ok(())
@@ -189,6 +221,9 @@ fn dispatch_overlay_event(
         Some(OverlayKind::QuestionReview) => widget
             .question_review
             .event(ctx, event, layout, update),
+        Some(OverlayKind::WorkspaceChanges) => widget
+            .workspace_changes
+            .event(ctx, event, layout, update),
         Some(OverlayKind::CodeExec) => widget
             .code_exec
             .event(ctx, event, layout, update),
@@ -197,6 +232,9 @@ fn dispatch_overlay_event(
             .event(ctx, event, layout, update),
         Some(OverlayKind::Terminal) => widget.terminal.event(ctx, event, layout, update),
         Some(OverlayKind::Help) => widget.help.event(ctx, event, layout, update),
+        Some(OverlayKind::Compare) => widget.compare.event(ctx, event, layout, update),
+        Some(OverlayKind::Tools) => widget.tools.event(ctx, event, layout, update),
+        Some(OverlayKind::Tree) => widget.tree.event(ctx, event, layout, update),
         None => Ok(EventResult::ignored()),
     }
 }
@@ -5,7 +5,8 @@ pub(crate) use state::{OverlayAreas, OverlayRowCounts, with_active_table_handle}
 pub(crate) use table::{OverlayTable, centered_area, draw_overlay_table, header_style, row_at, visible_rows};
 
 use crate::framework::widget_system::runtime_dispatch::key_helpers::{
-    handle_pre_key_actions, handle_view_action_flow, is_quit_key, resolve_view_action,
+    handle_pre_key_actions, handle_tool_actions, handle_view_action_flow, is_quit_key,
+    resolve_view_action,
 };
 use crate::framework::widget_system::runtime_dispatch::{
     DispatchContext, LayoutContext, apply_model_selection, apply_prompt_selection,
@@ -83,6 +84,9 @@ impl<'a> OverlayTableController<'a> {
             apply_prompt_selection(&mut self.dispatch, idx);
             return;
         }
+        if handle_tool_actions(&mut self.dispatch, action) {
+            return;
+        }
         let _ = apply_view_action(
             action,
             self.dispatch.args.show_system_prompt,
@@ -134,12 +138,20 @@ impl<'a> OverlayTableController<'a> {
             .and_then(|tab| tab.app.pending_question_review.as_ref())
             .map(|pending| pending.questions.len())
             .unwrap_or(0);
+        let workspace_changes = self
+            .dispatch
+            .tabs
+            .get(*self.dispatch.active_tab)
+            .and_then(|tab| tab.app.pending_workspace_changes.as_ref())
+            .map(|pending| pending.items.len())
+            .unwrap_or(0);
         OverlayRowCounts {
             tabs: self.dispatch.tabs.len(),
             jump: self.jump_len(),
             models: self.dispatch.registry.models.len(),
             prompts: self.dispatch.prompt_registry.prompts.len(),
             question_reviews,
+            workspace_changes,
             help: help_rows_len(),
         }
     }
@@ -169,6 +181,12 @@ pub(crate) fn clamp_overlay_tables(view: &mut ViewState, state: &RenderState<'_>
             .and_then(|tab| tab.app.pending_question_review.as_ref())
             .map(|pending| pending.questions.len())
             .unwrap_or(0),
+        workspace_changes: state
+            .tabs
+            .get(state.active_tab)
+            .and_then(|tab| tab.app.pending_workspace_changes.as_ref())
+            .map(|pending| pending.items.len())
+            .unwrap_or(0),
         help: help_rows_len(),
     };
     let _ = with_active_table_handle(view, areas, counts, |mut handle| handle.clamp());
@@ -6,6 +6,8 @@ use crate::framework::widget_system::runtime::runtime_view::ViewState;
 use crate::framework::widget_system::interaction::scroll::max_scroll;
 use crate::framework::widget_system::interaction::selection_state::SelectionState;
 use crate::framework::widget_system::widgets::help::help_popup_area;
+use crate::framework::widget_system::widgets::tools::{tools_len, tools_popup_area};
+use crate::framework::widget_system::widgets::tree::tree_popup_area;
 use ratatui::layout::Rect;
 
 #[derive(Copy, Clone)]
@@ -21,6 +23,7 @@ pub(crate) struct OverlayRowCounts {
     pub(crate) models: usize,
     pub(crate) prompts: usize,
     pub(crate) question_reviews: usize,
+    pub(crate) workspace_changes: usize,
     pub(crate) help: usize,
 }
 
@@ -78,10 +81,14 @@ pub(crate) fn overlay_table_metrics(
         OverlayKind::Model => model_metrics(areas, counts),
         OverlayKind::Prompt => prompt_metrics(areas, counts),
         OverlayKind::QuestionReview => question_review_metrics(areas, counts),
-        OverlayKind::CodeExec | OverlayKind::FilePatch | OverlayKind::Terminal => {
-            empty_metrics(areas)
-        }
+        OverlayKind::WorkspaceChanges => workspace_changes_metrics(areas, counts),
+        OverlayKind::CodeExec
+        | OverlayKind::FilePatch
+        | OverlayKind::Terminal
+        | OverlayKind::Compare => empty_metrics(areas),
         OverlayKind::Help => help_metrics(areas, counts),
+        OverlayKind::Tools => tools_metrics(areas),
+        OverlayKind::Tree => tree_metrics(areas, counts),
     }
 }
 
@@ -123,6 +130,16 @@ fn question_review_metrics(areas: OverlayAreas, counts: OverlayRowCounts) -> Ove
     }
 }
 
+fn workspace_changes_metrics(areas: OverlayAreas, counts: OverlayRowCounts) -> OverlayTableMetrics {
+    OverlayTableMetrics {
+        area: crate::framework::widget_system::widgets::workspace_changes::workspace_changes_list_area(
+            areas.full,
+            counts.workspace_changes,
+        ),
+        rows: counts.workspace_changes,
+    }
+}
+
 fn help_metrics(areas: OverlayAreas, counts: OverlayRowCounts) -> OverlayTableMetrics {
     OverlayTableMetrics {
         area: help_popup_area(areas.full, counts.help),
@@ -130,6 +147,20 @@ fn help_metrics(areas: OverlayAreas, counts: OverlayRowCounts) -> OverlayTableMe
     }
 }
 
+fn tools_metrics(areas: OverlayAreas) -> OverlayTableMetrics {
+    OverlayTableMetrics {
+        area: tools_popup_area(areas.full, tools_len()),
+        rows: tools_len(),
+    }
+}
+
+fn tree_metrics(areas: OverlayAreas, counts: OverlayRowCounts) -> OverlayTableMetrics {
+    OverlayTableMetrics {
+        area: tree_popup_area(areas.full, counts.tabs),
+        rows: counts.tabs,
+    }
+}
+
 fn empty_metrics(areas: OverlayAreas) -> OverlayTableMetrics {
     OverlayTableMetrics {
         area: areas.msg,
@@ -155,7 +186,10 @@ pub(crate) fn with_active_table_handle<R>(
     let kind = view.overlay.active?;
     if matches!(
         kind,
-        OverlayKind::CodeExec | OverlayKind::FilePatch | OverlayKind::Terminal
+        OverlayKind::CodeExec
+            | OverlayKind::FilePatch
+            | OverlayKind::Terminal
+            | OverlayKind::Compare
     ) {
         return None;
     }
@@ -166,9 +200,12 @@ pub(crate) fn with_active_table_handle<R>(
         OverlayKind::Model => &mut view.model,
         OverlayKind::Prompt => &mut view.prompt,
         OverlayKind::QuestionReview => &mut view.question_review,
+        OverlayKind::WorkspaceChanges => &mut view.workspace_changes,
         OverlayKind::Terminal => &mut view.summary,
         OverlayKind::Help => &mut view.help,
-        OverlayKind::CodeExec | OverlayKind::FilePatch => &mut view.summary,
+        OverlayKind::Tools => &mut view.tools,
+        OverlayKind::Tree => &mut view.tree,
+        OverlayKind::CodeExec | OverlayKind::FilePatch | OverlayKind::Compare => &mut view.summary,
     };
     Some(f(OverlayTableHandle { metrics, selection }))
 }
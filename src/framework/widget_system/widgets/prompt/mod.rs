@@ -2,7 +2,7 @@ mod popup;
 
 pub(crate) use popup::prompt_popup_area;
 
-use crate::llm::prompts::SystemPrompt;
+use crate::llm::prompts::{SystemPrompt, parse_front_matter};
 use crate::render::RenderTheme;
 use crate::framework::widget_system::widgets::overlay_table::{OverlayTable, draw_overlay_table, header_style};
 use crate::framework::widget_system::interaction::text_utils::{collapse_text, truncate_to_width};
@@ -97,18 +97,20 @@ fn build_prompt_table<'a>(
 ) -> OverlayTable<'a> {
     let role_width = role_col_width(popup, prompts);
     let header =
-        Row::new(vec![Cell::from("角色"), Cell::from("系统提示词")]).style(header_style(theme));
+        Row::new(vec![Cell::from("角色"), Cell::from("说明")]).style(header_style(theme));
     let body = prompts.iter().map(|p| {
         Row::new(vec![
             Cell::from(p.key.clone()),
             Cell::from(truncate_to_width(
-                &collapse_text(&p.content),
+                &prompt_summary(p),
                 max_preview_width(popup, role_width),
             )),
         ])
     });
     OverlayTable {
-        title: Line::from("系统提示词 · Enter 确认 · Esc 取消"),
+        title: Line::from(
+            "系统提示词 · Enter 确认 · n 新建 · e/E 编辑 · d 复制 · r 重命名 · Del 删除 · Esc 取消",
+        ),
         header,
         rows: body.collect(),
         widths: vec![Constraint::Length(role_width), Constraint::Min(10)],
@@ -118,6 +120,21 @@ fn build_prompt_table<'a>(
     }
 }
 
+/// 优先显示 front-matter 中的标题与描述，没有时预览正文。
+fn prompt_summary(prompt: &SystemPrompt) -> String {
+    let meta = &prompt.meta;
+    match (meta.title.as_deref(), meta.description.as_deref()) {
+        (Some(title), Some(desc)) => format!("{title} · {}", collapse_text(desc)),
+        (Some(title), None) => title.to_string(),
+        (None, Some(desc)) => collapse_text(desc),
+        (None, None) => {
+            let body = parse_front_matter(&prompt.content)
+                .map_or(prompt.content.as_str(), |(_, body)| body);
+            collapse_text(body)
+        }
+    }
+}
+
 fn max_preview_width(area: Rect, role_width: u16) -> usize {
     area.width.saturating_sub(role_width).saturating_sub(4) as usize
 }
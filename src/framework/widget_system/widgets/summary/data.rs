@@ -5,10 +5,15 @@ pub struct SummaryRow {
     pub tab_index: usize,
     pub tab_id: usize,
     pub category: String,
+    pub title: String,
     pub message_count: usize,
     pub status: &'static str,
+    /// 与其他对话的子任务关系，如「←3」「派出 2」
+    pub relation: String,
     pub exec_pending: bool,
     pub exec_since: Option<Instant>,
+    pub total_tokens: u64,
+    pub cost: Option<f64>,
     pub latest_user: String,
 }
 
@@ -21,7 +26,7 @@ pub enum SummarySort {
 pub fn build_summary_rows(tabs: &[TabState], max_latest_width: usize) -> Vec<SummaryRow> {
     tabs.iter()
         .enumerate()
-        .map(|(idx, tab)| super::rows::build_summary_row(idx, tab, max_latest_width))
+        .map(|(idx, tab)| super::rows::build_summary_row(idx, tab, tabs, max_latest_width))
         .collect()
 }
 
@@ -3,6 +3,7 @@ mod rows;
 
 pub(crate) use data::{SummaryRow, SummarySort, build_summary_rows, sort_summary_rows};
 
+use crate::llm::pricing::format_cost;
 use crate::render::RenderTheme;
 use crate::framework::widget_system::draw::layout::{inner_area, layout_chunks};
 use crate::framework::widget_system::draw::style::base_fg;
@@ -206,7 +207,7 @@ fn draw_summary_layout_base<'a, 'b>(params: DrawSummaryLayoutParams<'a, 'b>) {
 }
 
 fn max_latest_question_width(body_area: Rect) -> usize {
-    inner_area(body_area, 1, 1).width.saturating_sub(40) as usize
+    inner_area(body_area, 1, 1).width.saturating_sub(92) as usize
 }
 
 fn draw_summary_table_base(
@@ -245,10 +246,14 @@ fn build_summary_table<'a>(
 fn summary_header(theme: &RenderTheme) -> Row<'static> {
     Row::new(vec![
         Cell::from("对话"),
+        Cell::from("标题"),
         Cell::from("分类"),
         Cell::from("消息数"),
         Cell::from("状态"),
+        Cell::from("子任务"),
         Cell::from("执行中"),
+        Cell::from("tokens"),
+        Cell::from("费用"),
         Cell::from("最新提问"),
     ])
     .style(header_style(theme))
@@ -260,10 +265,14 @@ fn summary_body<'a>(
     rows.iter().map(|row| {
         Row::new(vec![
             Cell::from(row.tab_id.to_string()),
+            Cell::from(row.title.clone()),
             Cell::from(row.category.clone()),
             Cell::from(row.message_count.to_string()),
             Cell::from(row.status),
+            Cell::from(row.relation.clone()),
             Cell::from(if row.exec_pending { "是" } else { "否" }),
+            Cell::from(row.total_tokens.to_string()),
+            Cell::from(row.cost.map(format_cost).unwrap_or_else(|| "-".to_string())),
             Cell::from(row.latest_user.clone()),
         ])
     })
@@ -272,10 +281,14 @@ fn summary_body<'a>(
 fn summary_widths() -> Vec<Constraint> {
     vec![
         Constraint::Length(6),
+        Constraint::Length(18),
         Constraint::Length(10),
         Constraint::Length(8),
         Constraint::Length(12),
+        Constraint::Length(10),
         Constraint::Length(8),
+        Constraint::Length(10),
+        Constraint::Length(10),
         Constraint::Min(10),
     ]
 }
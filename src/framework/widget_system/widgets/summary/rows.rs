@@ -1,11 +1,28 @@
 use crate::types::ROLE_USER;
 use crate::framework::widget_system::runtime::runtime_helpers::TabState;
-use crate::framework::widget_system::interaction::text_utils::truncate_to_width;
+use crate::framework::widget_system::interaction::text_utils::{
+    truncate_to_width, truncate_to_width_ellipsis_char,
+};
+use crate::services::runtime_delegation::waiting_for_delegations;
 
 use super::SummaryRow;
 
-pub(crate) fn build_summary_row(idx: usize, tab: &TabState, max_latest_width: usize) -> SummaryRow {
-    let status = if tab.app.busy { "generating" } else { "done" };
+/// 与「标题」列宽一致
+const TITLE_WIDTH: usize = 18;
+
+pub(crate) fn build_summary_row(
+    idx: usize,
+    tab: &TabState,
+    tabs: &[TabState],
+    max_latest_width: usize,
+) -> SummaryRow {
+    let status = if waiting_for_delegations(&tab.app) {
+        "waiting"
+    } else if tab.app.busy {
+        "generating"
+    } else {
+        "done"
+    };
     let exec_pending = tab.app.pending_code_exec.is_some() || tab.app.code_exec_live.is_some();
     let exec_since = tab.app.pending_code_exec.as_ref().map(|p| p.requested_at);
     let latest_user = latest_user_question(&tab.app.messages)
@@ -15,10 +32,19 @@ pub(crate) fn build_summary_row(idx: usize, tab: &TabState, max_latest_width: us
         tab_index: idx,
         tab_id: idx + 1,
         category: tab.category.clone(),
+        title: tab
+            .app
+            .title
+            .as_deref()
+            .map(|s| truncate_to_width_ellipsis_char(s, TITLE_WIDTH))
+            .unwrap_or_else(|| "-".to_string()),
         message_count: tab.app.messages.len(),
         status,
+        relation: delegation_relation(tab, tabs),
         exec_pending,
         exec_since,
+        total_tokens: tab.app.total_tokens,
+        cost: tab.app.total_cost,
         latest_user,
     }
 }
@@ -32,6 +58,31 @@ pub(crate) fn exec_time_sort_key(row: &SummaryRow) -> (u8, u64, u64) {
     (pending_rank, since, row.tab_index as u64)
 }
 
+/// 子对话显示派出它的对话编号，如「←3」；派出过子任务的对话显示仍打开的子对话数。
+fn delegation_relation(tab: &TabState, tabs: &[TabState]) -> String {
+    if let Some(link) = &tab.app.delegated_by {
+        let parent = tabs
+            .iter()
+            .position(|t| t.conversation_id == link.parent_id)
+            .map_or_else(|| "已关闭".to_string(), |idx| (idx + 1).to_string());
+        return format!("←{parent}");
+    }
+    let children = tabs
+        .iter()
+        .filter(|t| {
+            t.app
+                .delegated_by
+                .as_ref()
+                .is_some_and(|link| link.parent_id == tab.conversation_id)
+        })
+        .count();
+    if children == 0 {
+        "-".to_string()
+    } else {
+        format!("派出 {children}")
+    }
+}
+
 fn latest_user_question(messages: &[crate::types::Message]) -> Option<&str> {
     messages
         .iter()
@@ -0,0 +1,167 @@
+mod popup;
+
+pub(crate) use popup::tools_popup_area;
+
+use crate::args::ToolFlags;
+use crate::framework::widget_system::runtime::runtime_loop_steps::FrameLayout;
+use crate::framework::widget_system::widgets::overlay_table::{
+    OverlayTable, draw_overlay_table, header_style,
+};
+use crate::render::RenderTheme;
+use crate::services::runtime_tools::{effective_tools, is_dangerous_tool};
+use ratatui::layout::{Constraint, Rect};
+use ratatui::text::Line;
+use ratatui::widgets::{Cell, Row};
+use std::error::Error;
+
+use super::super::bindings::bind_event;
+use super::super::context::{EventCtx, UpdateCtx, UpdateOutput, WidgetFrame};
+use super::super::lifecycle::{EventResult, Widget};
+use super::overlay_table::{OverlayTableController, clamp_overlay_tables};
+
+pub(crate) struct ToolsWidget {
+    _private: (),
+}
+
+impl ToolsWidget {
+    pub(crate) fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+impl Widget for ToolsWidget {
+    fn update(
+        &mut self,
+        _ctx: &mut UpdateCtx<'_>,
+        _layout: &FrameLayout,
+        _update: &UpdateOutput,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    fn event(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        event: &crossterm::event::Event,
+        layout: &FrameLayout,
+        update: &UpdateOutput,
+        _rect: ratatui::layout::Rect,
+    ) -> Result<EventResult, Box<dyn Error>> {
+        let binding = bind_event(ctx, layout, update);
+        let mut controller = OverlayTableController {
+            dispatch: binding.dispatch,
+            layout: binding.layout,
+            view: binding.view,
+        };
+        controller.handle_event(event)
+    }
+
+    fn render(
+        &mut self,
+        frame: &mut WidgetFrame<'_, '_, '_, '_>,
+        _layout: &FrameLayout,
+        _update: &UpdateOutput,
+        rect: ratatui::layout::Rect,
+    ) -> Result<(), Box<dyn Error>> {
+        clamp_overlay_tables(frame.view, frame.state);
+        let Some(app) = frame.state.active_app() else {
+            return Ok(());
+        };
+        let tools = effective_tools(app);
+        let customized = app.tool_flags.is_some();
+        draw_tools_popup(
+            frame.frame,
+            rect,
+            tools,
+            customized,
+            frame.view.tools.selected,
+            frame.view.tools.scroll,
+            frame.state.theme,
+        );
+        Ok(())
+    }
+}
+
+pub(crate) fn tools_len() -> usize {
+    ToolFlags::NAMES.len()
+}
+
+fn draw_tools_popup(
+    f: &mut ratatui::Frame<'_>,
+    area: Rect,
+    tools: ToolFlags,
+    customized: bool,
+    selected: usize,
+    scroll: usize,
+    theme: &RenderTheme,
+) {
+    let popup = tools_popup_area(area, tools_len());
+    let popup_spec = OverlayTable {
+        title: Line::from(tools_title(customized)),
+        header: tools_header(theme),
+        rows: tools_body(tools),
+        widths: tools_widths(),
+        selected,
+        scroll,
+        theme,
+    };
+    draw_overlay_table(f, popup, popup_spec);
+}
+
+fn tools_header(theme: &RenderTheme) -> Row<'static> {
+    Row::new(vec![
+        Cell::from("工具"),
+        Cell::from("状态"),
+        Cell::from("说明"),
+    ])
+    .style(header_style(theme))
+}
+
+fn tools_body(tools: ToolFlags) -> Vec<Row<'static>> {
+    ToolFlags::NAMES
+        .iter()
+        .map(|name| {
+            let state = if tools.get(name) { "开启" } else { "关闭" };
+            Row::new(vec![
+                Cell::from(*name),
+                Cell::from(state),
+                Cell::from(tool_description(name)),
+            ])
+        })
+        .collect()
+}
+
+fn tool_description(name: &str) -> String {
+    let text = match name {
+        "web_search" => "联网搜索",
+        "code_exec" => "在容器中执行代码与 bash 命令",
+        "read_file" => "读取工作区文件、列出目录",
+        "read_code" => "按行读取代码文件",
+        "modify_file" => "修改工作区文件",
+        "ask_questions" => "向其他模型提问并汇总回答",
+        "delegate_task" => "把子任务交给新的子对话完成",
+        _ => "",
+    };
+    if is_dangerous_tool(name) {
+        format!("{text}（危险）")
+    } else {
+        text.to_string()
+    }
+}
+
+fn tools_widths() -> Vec<Constraint> {
+    vec![
+        Constraint::Length(15),
+        Constraint::Length(6),
+        Constraint::Min(10),
+    ]
+}
+
+fn tools_title(customized: bool) -> String {
+    let source = if customized {
+        "本对话已调整"
+    } else {
+        "沿用 --enable"
+    };
+    format!("工具开关（{source}）· Space/Enter 切换 · r 恢复默认 · Esc 关闭")
+}
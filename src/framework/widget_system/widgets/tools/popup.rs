@@ -0,0 +1,8 @@
+use crate::framework::widget_system::widgets::overlay_table::centered_area;
+use ratatui::layout::Rect;
+
+const POPUP_MAX_HEIGHT: u16 = 12;
+
+pub fn tools_popup_area(area: Rect, rows: usize) -> Rect {
+    centered_area(area, 70, rows, POPUP_MAX_HEIGHT)
+}
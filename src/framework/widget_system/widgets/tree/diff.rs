@@ -0,0 +1,157 @@
+use crate::framework::widget_system::draw::style::{base_fg, base_style};
+use crate::framework::widget_system::runtime::runtime_helpers::TabState;
+use crate::render::{RenderTheme, label_for_role, render_markdown_lines};
+use crate::types::{Message, ROLE_ASSISTANT, ROLE_USER};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use super::rows::TreeNode;
+
+const OUTER_MARGIN: u16 = 1;
+
+/// 两个分支自共同祖先之后的对比：上方说明分叉点，下方左右两列各自之后的提问与回答。
+pub(crate) struct TreeDiff<'a> {
+    pub(crate) tabs: &'a [TabState],
+    pub(crate) nodes: &'a [TreeNode],
+    pub(crate) pair: (usize, usize),
+    pub(crate) scroll: u16,
+    pub(crate) theme: &'a RenderTheme,
+}
+
+pub(crate) fn draw_tree_diff(f: &mut ratatui::Frame<'_>, area: Rect, diff: TreeDiff<'_>) {
+    let (left, right) = diff.pair;
+    let (Some(left_tab), Some(right_tab)) = (diff.tabs.get(left), diff.tabs.get(right)) else {
+        return;
+    };
+    let popup = popup_area(area);
+    f.render_widget(Clear, popup);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title_top(Line::from(Span::styled(
+            "分支对比 · ↑/↓ PgUp/PgDn 滚动 · Esc 返回分支树",
+            bold_style(diff.theme),
+        )))
+        .style(base_style(diff.theme))
+        .border_style(Style::default().fg(base_fg(diff.theme)));
+    let inner = block.inner(popup);
+    f.render_widget(block, popup);
+    let rows = Layout::vertical([Constraint::Length(2), Constraint::Min(1)]).split(inner);
+    let cols =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).split(rows[1]);
+    let common = common_prefix_len(&left_tab.app.messages, &right_tab.app.messages);
+    let header = ancestor_text(diff.nodes, diff.pair, common);
+    f.render_widget(
+        Paragraph::new(header).style(base_style(diff.theme)),
+        rows[0],
+    );
+    draw_branch_column(f, cols[0], left, left_tab, common, &diff);
+    draw_branch_column(f, cols[1], right, right_tab, common, &diff);
+}
+
+fn draw_branch_column(
+    f: &mut ratatui::Frame<'_>,
+    area: Rect,
+    tab_index: usize,
+    tab: &TabState,
+    common: usize,
+    diff: &TreeDiff<'_>,
+) {
+    let width = area.width.saturating_sub(2).max(1) as usize;
+    let lines = branch_lines(&tab.app.messages[common..], width, diff.theme);
+    let title = match tab.app.title.as_deref() {
+        Some(title) => format!("对话 {} · {title}", tab_index + 1),
+        None => format!("对话 {}", tab_index + 1),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title_top(Line::from(Span::styled(title, bold_style(diff.theme))))
+        .border_style(Style::default().fg(base_fg(diff.theme)));
+    let para = Paragraph::new(Text::from(lines))
+        .style(base_style(diff.theme))
+        .scroll((diff.scroll, 0))
+        .block(block);
+    f.render_widget(para, area);
+}
+
+/// 分叉之后的提问与回答；工具调用与思考过程不参与对比。
+fn branch_lines(messages: &[Message], width: usize, theme: &RenderTheme) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for msg in messages {
+        if !(msg.role == ROLE_USER || msg.role == ROLE_ASSISTANT) || msg.content.trim().is_empty() {
+            continue;
+        }
+        let label = label_for_role(&msg.role, None).unwrap_or_default();
+        lines.push(Line::from(Span::styled(label, bold_style(theme))));
+        lines.extend(render_markdown_lines(
+            &msg.content,
+            width,
+            theme,
+            false,
+            false,
+        ));
+        lines.push(Line::default());
+    }
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "（分叉后没有新的消息）",
+            Style::default().add_modifier(Modifier::DIM),
+        )));
+    }
+    lines
+}
+
+fn ancestor_text(nodes: &[TreeNode], pair: (usize, usize), common: usize) -> String {
+    match common_ancestor(nodes, pair) {
+        Some(ancestor) => format!("共同祖先：对话 {} · 前 {common} 条消息相同", ancestor + 1),
+        None => format!("两个对话没有分叉关系 · 前 {common} 条消息相同"),
+    }
+}
+
+/// 两个分支沿父链向上最先相遇的对话（可以是其中一方）。
+fn common_ancestor(nodes: &[TreeNode], (left, right): (usize, usize)) -> Option<usize> {
+    let left_chain = ancestor_chain(nodes, left);
+    ancestor_chain(nodes, right)
+        .into_iter()
+        .find(|idx| left_chain.contains(idx))
+}
+
+fn ancestor_chain(nodes: &[TreeNode], tab_index: usize) -> Vec<usize> {
+    let mut chain = vec![tab_index];
+    let mut current = tab_index;
+    while let Some(parent) = nodes
+        .iter()
+        .find(|node| node.tab_index == current)
+        .and_then(|node| node.parent)
+    {
+        if chain.contains(&parent) {
+            break;
+        }
+        chain.push(parent);
+        current = parent;
+    }
+    chain
+}
+
+fn common_prefix_len(left: &[Message], right: &[Message]) -> usize {
+    left.iter()
+        .zip(right)
+        .take_while(|(a, b)| a.role == b.role && a.content == b.content)
+        .count()
+}
+
+fn bold_style(theme: &RenderTheme) -> Style {
+    Style::default()
+        .fg(base_fg(theme))
+        .add_modifier(Modifier::BOLD)
+}
+
+fn popup_area(area: Rect) -> Rect {
+    Rect {
+        x: area.x.saturating_add(OUTER_MARGIN),
+        y: area.y.saturating_add(OUTER_MARGIN),
+        width: area.width.saturating_sub(OUTER_MARGIN.saturating_mul(2)),
+        height: area.height.saturating_sub(OUTER_MARGIN.saturating_mul(2)),
+    }
+}
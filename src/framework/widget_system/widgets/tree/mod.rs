@@ -0,0 +1,163 @@
+mod diff;
+mod popup;
+mod rows;
+
+pub(crate) use popup::tree_popup_area;
+pub(crate) use rows::{TreeNode, build_tree_rows};
+
+use crate::framework::widget_system::runtime::runtime_loop_steps::FrameLayout;
+use crate::framework::widget_system::widgets::overlay_table::{
+    OverlayTable, draw_overlay_table, header_style,
+};
+use crate::render::RenderTheme;
+use crossterm::event::{MouseEvent, MouseEventKind};
+use ratatui::layout::{Constraint, Rect};
+use ratatui::text::Line;
+use ratatui::widgets::{Cell, Row};
+use std::error::Error;
+
+use super::super::bindings::bind_event;
+use super::super::context::{EventCtx, UpdateCtx, UpdateOutput, WidgetFrame};
+use super::super::lifecycle::{EventResult, Widget};
+use super::overlay_table::{OverlayTableController, clamp_overlay_tables};
+use diff::{TreeDiff, draw_tree_diff};
+use rows::TreeRow;
+
+const DIFF_SCROLL_STEP: u16 = 3;
+
+/// 对话分支树：按分叉关系列出打开的对话，可在分支间跳转并对比两个分支。
+pub(crate) struct TreeWidget {
+    _private: (),
+}
+
+impl TreeWidget {
+    pub(crate) fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+impl Widget for TreeWidget {
+    fn update(
+        &mut self,
+        _ctx: &mut UpdateCtx<'_>,
+        _layout: &FrameLayout,
+        _update: &UpdateOutput,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    fn event(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        event: &crossterm::event::Event,
+        layout: &FrameLayout,
+        update: &UpdateOutput,
+        _rect: Rect,
+    ) -> Result<EventResult, Box<dyn Error>> {
+        if ctx.view.tree_diff.is_some()
+            && let crossterm::event::Event::Mouse(MouseEvent { kind, .. }) = event
+        {
+            let view = &mut *ctx.view;
+            match kind {
+                MouseEventKind::ScrollUp => {
+                    view.tree_diff_scroll = view.tree_diff_scroll.saturating_sub(DIFF_SCROLL_STEP)
+                }
+                MouseEventKind::ScrollDown => {
+                    view.tree_diff_scroll = view.tree_diff_scroll.saturating_add(DIFF_SCROLL_STEP)
+                }
+                _ => {}
+            }
+            return Ok(EventResult::handled());
+        }
+        let binding = bind_event(ctx, layout, update);
+        let mut controller = OverlayTableController {
+            dispatch: binding.dispatch,
+            layout: binding.layout,
+            view: binding.view,
+        };
+        controller.handle_event(event)
+    }
+
+    fn render(
+        &mut self,
+        frame: &mut WidgetFrame<'_, '_, '_, '_>,
+        _layout: &FrameLayout,
+        _update: &UpdateOutput,
+        rect: Rect,
+    ) -> Result<(), Box<dyn Error>> {
+        clamp_overlay_tables(frame.view, frame.state);
+        let rows = build_tree_rows(frame.state.tabs());
+        frame.view.tree_nodes = rows.iter().map(|row| row.node).collect();
+        if frame
+            .view
+            .tree_diff
+            .is_some_and(|(left, right)| left.max(right) >= rows.len())
+        {
+            frame.view.tree_diff = None;
+        }
+        if let Some(pair) = frame.view.tree_diff {
+            let diff = TreeDiff {
+                tabs: frame.state.tabs(),
+                nodes: &frame.view.tree_nodes,
+                pair,
+                scroll: frame.view.tree_diff_scroll,
+                theme: frame.state.theme,
+            };
+            draw_tree_diff(frame.frame, rect, diff);
+            return Ok(());
+        }
+        let popup = tree_popup_area(rect, rows.len());
+        let table = OverlayTable {
+            title: Line::from(tree_title(frame.view.tree_mark)),
+            header: tree_header(frame.state.theme),
+            rows: tree_body(&rows, frame.view.tree_mark),
+            widths: tree_widths(),
+            selected: frame.view.tree.selected,
+            scroll: frame.view.tree.scroll,
+            theme: frame.state.theme,
+        };
+        draw_overlay_table(frame.frame, popup, table);
+        Ok(())
+    }
+}
+
+fn tree_header(theme: &RenderTheme) -> Row<'static> {
+    Row::new(vec![
+        Cell::from(""),
+        Cell::from("对话"),
+        Cell::from("分叉自"),
+        Cell::from("消息数"),
+    ])
+    .style(header_style(theme))
+}
+
+fn tree_body(rows: &[TreeRow], mark: Option<usize>) -> Vec<Row<'static>> {
+    rows.iter()
+        .map(|row| {
+            let marked = mark == Some(row.node.tab_index);
+            Row::new(vec![
+                Cell::from(if marked { "◆" } else { "" }),
+                Cell::from(row.label.clone()),
+                Cell::from(row.origin.clone()),
+                Cell::from(row.message_count.to_string()),
+            ])
+        })
+        .collect()
+}
+
+fn tree_widths() -> Vec<Constraint> {
+    vec![
+        Constraint::Length(2),
+        Constraint::Min(20),
+        Constraint::Length(28),
+        Constraint::Length(8),
+    ]
+}
+
+fn tree_title(mark: Option<usize>) -> String {
+    let keys = "Enter 进入 · [ / ] 同级分支 · m 标记 · d 对比 · Esc 关闭";
+    match mark {
+        Some(idx) => format!("分支树（已标记对话 {}）· {keys}", idx + 1),
+        None => format!("分支树 · {keys}"),
+    }
+}
@@ -0,0 +1,113 @@
+use crate::framework::widget_system::interaction::text_utils::truncate_to_width_ellipsis_char;
+use crate::framework::widget_system::runtime::runtime_helpers::TabState;
+
+/// 与「对话」列宽一致，超出的标题截断
+const LABEL_WIDTH: usize = 40;
+
+/// 分支树中的一个节点；`parent` 为父对话所在的标签页，未打开或没有来源时为 None。
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct TreeNode {
+    pub(crate) tab_index: usize,
+    pub(crate) parent: Option<usize>,
+}
+
+pub(crate) struct TreeRow {
+    pub(crate) node: TreeNode,
+    pub(crate) label: String,
+    pub(crate) origin: String,
+    pub(crate) message_count: usize,
+}
+
+/// 按分叉关系排出树形行：父对话在前，子分支缩进在下；
+/// 父对话未打开或链路成环的对话作为根节点。
+pub(crate) fn build_tree_rows(tabs: &[TabState]) -> Vec<TreeRow> {
+    let parents: Vec<Option<usize>> = (0..tabs.len()).map(|idx| parent_tab(tabs, idx)).collect();
+    let mut visited = vec![false; tabs.len()];
+    let mut rows = Vec::with_capacity(tabs.len());
+    for (idx, parent) in parents.iter().enumerate() {
+        if parent.is_none() {
+            walk(tabs, &parents, idx, None, &mut visited, &mut rows);
+        }
+    }
+    while let Some(idx) = visited.iter().position(|seen| !seen) {
+        walk(tabs, &parents, idx, None, &mut visited, &mut rows);
+    }
+    rows
+}
+
+/// 前序遍历；`prefix` 为 None 表示根节点，否则是祖先各层的竖线缩进与本节点是否为末个子节点。
+fn walk(
+    tabs: &[TabState],
+    parents: &[Option<usize>],
+    idx: usize,
+    prefix: Option<(&str, bool)>,
+    visited: &mut [bool],
+    rows: &mut Vec<TreeRow>,
+) {
+    visited[idx] = true;
+    let (branch, child_indent) = match prefix {
+        None => (String::new(), String::new()),
+        Some((indent, last)) => {
+            let (branch, pad) = if last {
+                ("└─ ", "   ")
+            } else {
+                ("├─ ", "│  ")
+            };
+            (format!("{indent}{branch}"), format!("{indent}{pad}"))
+        }
+    };
+    let parent = if prefix.is_some() { parents[idx] } else { None };
+    rows.push(tree_row(
+        tabs,
+        TreeNode {
+            tab_index: idx,
+            parent,
+        },
+        &branch,
+    ));
+    let children: Vec<usize> = (0..tabs.len())
+        .filter(|&child| parents[child] == Some(idx) && !visited[child])
+        .collect();
+    for (pos, &child) in children.iter().enumerate() {
+        let last = pos + 1 == children.len();
+        walk(
+            tabs,
+            parents,
+            child,
+            Some((&child_indent, last)),
+            visited,
+            rows,
+        );
+    }
+}
+
+fn tree_row(tabs: &[TabState], node: TreeNode, branch: &str) -> TreeRow {
+    let app = &tabs[node.tab_index].app;
+    let title = app.title.as_deref().unwrap_or("未命名对话");
+    let label = format!("{branch}{} {title}", node.tab_index + 1);
+    TreeRow {
+        node,
+        label: truncate_to_width_ellipsis_char(&label, LABEL_WIDTH),
+        origin: fork_origin_text(tabs, node.tab_index),
+        message_count: app.messages.len(),
+    }
+}
+
+fn fork_origin_text(tabs: &[TabState], idx: usize) -> String {
+    let Some(origin) = tabs[idx].app.fork_of.as_ref() else {
+        return "-".to_string();
+    };
+    let point = origin.message_index + 1;
+    match parent_tab(tabs, idx) {
+        Some(parent) => format!("对话 {} 第 {point} 条", parent + 1),
+        None => format!("{} 第 {point} 条（未打开）", origin.parent_id),
+    }
+}
+
+/// 父对话所在的标签页；父对话未打开时为 None。
+pub(crate) fn parent_tab(tabs: &[TabState], idx: usize) -> Option<usize> {
+    let parent_id = &tabs.get(idx)?.app.fork_of.as_ref()?.parent_id;
+    tabs.iter()
+        .position(|tab| &tab.conversation_id == parent_id)
+        .filter(|&parent| parent != idx)
+}
@@ -0,0 +1,215 @@
+mod popup;
+
+pub(crate) use popup::{workspace_changes_list_area, workspace_changes_popup_area};
+
+use crate::framework::widget_system::draw::style::base_style;
+use crate::framework::widget_system::interaction::text_utils::truncate_to_width;
+use crate::framework::widget_system::runtime::runtime_loop_steps::FrameLayout;
+use crate::framework::widget_system::runtime::state::{
+    PendingWorkspaceChanges, WorkspaceChangeKind,
+};
+use crate::framework::widget_system::widgets::overlay_table::{
+    OverlayTable, draw_overlay_table, header_style,
+};
+use crate::render::RenderTheme;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Cell, Clear, Paragraph, Row};
+use std::error::Error;
+use unicode_width::UnicodeWidthStr;
+
+use super::super::bindings::bind_event;
+use super::super::context::{EventCtx, UpdateCtx, UpdateOutput, WidgetFrame};
+use super::super::lifecycle::{EventResult, Widget};
+use super::file_patch::{build_patch_text, patch_max_scroll};
+use super::overlay_table::{OverlayTableController, clamp_overlay_tables};
+use popup::workspace_changes_layout;
+
+pub(crate) struct WorkspaceChangesWidget {
+    _private: (),
+}
+
+impl WorkspaceChangesWidget {
+    pub(crate) fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+impl Widget for WorkspaceChangesWidget {
+    fn update(
+        &mut self,
+        _ctx: &mut UpdateCtx<'_>,
+        _layout: &FrameLayout,
+        _update: &UpdateOutput,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    fn event(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        event: &crossterm::event::Event,
+        layout: &FrameLayout,
+        update: &UpdateOutput,
+        _rect: ratatui::layout::Rect,
+    ) -> Result<EventResult, Box<dyn Error>> {
+        let binding = bind_event(ctx, layout, update);
+        let mut controller = OverlayTableController {
+            dispatch: binding.dispatch,
+            layout: binding.layout,
+            view: binding.view,
+        };
+        controller.handle_event(event)
+    }
+
+    fn render(
+        &mut self,
+        frame: &mut WidgetFrame<'_, '_, '_, '_>,
+        _layout: &FrameLayout,
+        _update: &UpdateOutput,
+        rect: ratatui::layout::Rect,
+    ) -> Result<(), Box<dyn Error>> {
+        clamp_overlay_tables(frame.view, frame.state);
+        let Some(app) = frame.state.active_app() else {
+            return Ok(());
+        };
+        let Some(pending) = app.pending_workspace_changes.as_ref() else {
+            return Ok(());
+        };
+        draw_workspace_changes_popup(
+            frame.frame,
+            rect,
+            pending,
+            frame.view.workspace_changes.selected,
+            frame.view.workspace_changes.scroll,
+            &mut frame.view.workspace_changes_detail_scroll,
+            frame.state.theme,
+        );
+        Ok(())
+    }
+}
+
+fn draw_workspace_changes_popup(
+    f: &mut ratatui::Frame<'_>,
+    area: Rect,
+    pending: &PendingWorkspaceChanges,
+    selected: usize,
+    list_scroll: usize,
+    detail_scroll: &mut usize,
+    theme: &RenderTheme,
+) {
+    let popup = workspace_changes_popup_area(area, pending.items.len());
+    let (list_area, detail_area) = workspace_changes_layout(popup);
+    f.render_widget(Clear, popup);
+    f.render_widget(Block::default().style(base_style(theme)), popup);
+    let table = build_workspace_changes_table(pending, selected, list_scroll, theme, list_area);
+    draw_overlay_table(f, list_area, table);
+    draw_change_detail(f, detail_area, pending, selected, detail_scroll, theme);
+}
+
+fn build_workspace_changes_table<'a>(
+    pending: &PendingWorkspaceChanges,
+    selected: usize,
+    list_scroll: usize,
+    theme: &'a RenderTheme,
+    area: Rect,
+) -> OverlayTable<'a> {
+    let mark_width = 5u16;
+    let kind_width = kind_col_width();
+    let path_width = area
+        .width
+        .saturating_sub(mark_width)
+        .saturating_sub(kind_width)
+        .saturating_sub(6) as usize;
+    OverlayTable {
+        title: Line::from(table_title(pending)),
+        header: table_header(theme),
+        rows: table_rows(pending, path_width),
+        widths: vec![
+            Constraint::Length(mark_width),
+            Constraint::Length(kind_width),
+            Constraint::Min(10),
+        ],
+        selected,
+        scroll: list_scroll,
+        theme,
+    }
+}
+
+fn table_header(theme: &RenderTheme) -> Row<'static> {
+    Row::new(vec![
+        Cell::from("同步"),
+        Cell::from("类型"),
+        Cell::from("文件"),
+    ])
+    .style(header_style(theme))
+}
+
+fn table_rows(pending: &PendingWorkspaceChanges, path_width: usize) -> Vec<Row<'static>> {
+    pending
+        .items
+        .iter()
+        .map(|item| {
+            let mark = if item.selected { "[x]" } else { "[ ]" };
+            let flag = if item.conflict { "! " } else { "" };
+            let label = format!("{flag}{} +{} -{}", item.path, item.added, item.removed);
+            Row::new(vec![
+                Cell::from(mark),
+                Cell::from(item.kind.label()),
+                Cell::from(truncate_to_width(&label, path_width)),
+            ])
+        })
+        .collect()
+}
+
+fn draw_change_detail(
+    f: &mut ratatui::Frame<'_>,
+    area: Rect,
+    pending: &PendingWorkspaceChanges,
+    selected: usize,
+    detail_scroll: &mut usize,
+    theme: &RenderTheme,
+) {
+    let Some(item) = pending.items.get(selected) else {
+        return;
+    };
+    let inner_width = area.width.saturating_sub(2);
+    let inner_height = area.height.saturating_sub(1);
+    let max_scroll = patch_max_scroll(&item.diff, inner_width, inner_height, theme);
+    if *detail_scroll > max_scroll {
+        *detail_scroll = max_scroll;
+    }
+    let (text, _) = build_patch_text(&item.diff, inner_width, inner_height, *detail_scroll, theme);
+    let note = if item.conflict {
+        " · 宿主机上的文件也已改动，同步会覆盖"
+    } else {
+        ""
+    };
+    let block = Block::default().borders(Borders::ALL).title(format!(
+        "{} · {}{note}",
+        item.kind.label(),
+        item.path
+    ));
+    let paragraph = Paragraph::new(text).block(block).style(base_style(theme));
+    f.render_widget(paragraph, area);
+}
+
+fn table_title(pending: &PendingWorkspaceChanges) -> String {
+    let total = pending.items.len();
+    let chosen = pending.items.iter().filter(|item| item.selected).count();
+    format!(
+        "隔离工作区改动 · 已选 {chosen}/{total} · ! 为冲突 · Space 选择 · A 全选 · PgUp/PgDn 滚动详情 · Enter 同步到宿主机 · Esc 关闭"
+    )
+}
+
+fn kind_col_width() -> u16 {
+    let mut max = "类型".width();
+    for kind in [
+        WorkspaceChangeKind::Added,
+        WorkspaceChangeKind::Modified,
+        WorkspaceChangeKind::Deleted,
+    ] {
+        max = max.max(kind.label().width());
+    }
+    (max + 2) as u16
+}
@@ -0,0 +1,31 @@
+use crate::framework::widget_system::widgets::overlay_table::centered_area;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+
+const POPUP_MAX_HEIGHT: u16 = 30;
+const LIST_PERCENT: u16 = 34;
+const MIN_LIST_WIDTH: u16 = 28;
+const MIN_DETAIL_WIDTH: u16 = 40;
+
+pub fn workspace_changes_popup_area(area: Rect, rows: usize) -> Rect {
+    centered_area(area, 96, rows, POPUP_MAX_HEIGHT)
+}
+
+pub fn workspace_changes_list_area(area: Rect, rows: usize) -> Rect {
+    let popup = workspace_changes_popup_area(area, rows);
+    workspace_changes_layout(popup).0
+}
+
+pub(super) fn workspace_changes_layout(area: Rect) -> (Rect, Rect) {
+    let list_width = list_width(area.width);
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(list_width), Constraint::Min(10)])
+        .split(area);
+    (chunks[0], chunks[1])
+}
+
+fn list_width(total: u16) -> u16 {
+    let desired = total.saturating_mul(LIST_PERCENT) / 100;
+    let max_list = total.saturating_sub(MIN_DETAIL_WIDTH).max(MIN_LIST_WIDTH);
+    desired.clamp(MIN_LIST_WIDTH, max_list)
+}
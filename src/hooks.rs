@@ -11,6 +11,7 @@ pub struct HookSpec {
 
 pub const EVENT_LLM_DONE: &str = "llm.done";
 pub const EVENT_LLM_ERROR: &str = "llm.error";
+pub const EVENT_LLM_STALLED: &str = "llm.stalled";
 pub const EVENT_LLM_TOOL_CALLS: &str = "llm.tool_calls";
 pub const EVENT_TOOL_BEFORE: &str = "tool.before";
 pub const EVENT_TOOL_AFTER: &str = "tool.after";
@@ -12,6 +12,11 @@ pub mod args;
 /// 处理应用程序配置的加载、解析和管理。
 pub mod config;
 
+/// 附件模块
+///
+/// 处理消息附带图片的保存、读取与占位显示。
+pub mod attachments;
+
 /// 调试工具模块
 ///
 /// 提供用于调试的辅助功能，如等待调试器 attach。
@@ -42,6 +47,16 @@ pub mod model_registry;
 /// 处理问题集的加载和管理。
 pub mod question_set;
 
+/// 落盘模块
+///
+/// 原子写入对话与会话文件，隔离损坏的文件，计算内容摘要。
+pub mod persist;
+
+/// 崩溃恢复模块
+///
+/// panic 时写出打开对话的快照，下次启动时提供恢复。
+pub mod recovery;
+
 /// 渲染模块
 ///
 /// 负责将消息和内容渲染为终端可显示的格式，包括 Markdown 渲染、布局管理等。
@@ -67,6 +82,11 @@ pub mod types;
 /// 终端用户界面的实现，包括运行时、事件处理、渲染等。
 pub mod ui;
 
+/// 子命令模块
+///
+/// 实现 `model`、`sandbox`、`usage` 等不进入界面的子命令。
+pub mod cli;
+
 /// 服务模块
 ///
 /// 承载非 UI 的服务逻辑，如网络请求、工具执行、容器管理等。
@@ -0,0 +1,84 @@
+//! 智能体工具循环的上限
+//!
+//! 一次用户输入可能触发多轮"调用工具 → 回传结果 → 再次请求模型"。这里限制单轮的工具轮次、耗时与 tokens，
+//! 并设定连续重复相同工具调用的次数；各项为 0 表示不限制。`delegate_task` 的嵌套层数例外，0 表示不允许派出子任务。
+
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_MAX_STEPS: u32 = 25;
+const DEFAULT_MAX_REPEATED_CALLS: u32 = 3;
+const DEFAULT_MAX_DELEGATION_DEPTH: u32 = 2;
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct AgentLimits {
+    /// 单轮最多的工具轮次
+    pub max_steps: u32,
+    /// 单轮最长耗时（秒）
+    pub max_turn_secs: u64,
+    /// 单轮最多消耗的 tokens（输入 + 输出）
+    pub max_turn_tokens: u64,
+    /// 连续多少轮调用完全相同的工具与参数时视为陷入循环
+    pub max_repeated_calls: u32,
+    /// 子任务最多嵌套几层：1 表示子对话不能再派出子任务
+    pub max_delegation_depth: u32,
+}
+
+impl Default for AgentLimits {
+    fn default() -> Self {
+        Self {
+            max_steps: DEFAULT_MAX_STEPS,
+            max_turn_secs: 0,
+            max_turn_tokens: 0,
+            max_repeated_calls: DEFAULT_MAX_REPEATED_CALLS,
+            max_delegation_depth: DEFAULT_MAX_DELEGATION_DEPTH,
+        }
+    }
+}
+
+impl AgentLimits {
+    /// 按 `/limits` 的参数（如 `steps=30 time=600 tokens=200000 repeat=3 depth=2`）生成新的上限。
+    pub fn with_overrides(self, arg: &str) -> Result<Self, String> {
+        let mut next = self;
+        for item in arg.split_whitespace() {
+            let (key, value) = item
+                .split_once('=')
+                .ok_or_else(|| format!("参数格式应为 key=value：{item}"))?;
+            let value: u32 = value
+                .parse()
+                .map_err(|_| format!("{key} 的值不是非负整数：{value}"))?;
+            match key {
+                "steps" => next.max_steps = value,
+                "time" => next.max_turn_secs = u64::from(value),
+                "tokens" => next.max_turn_tokens = u64::from(value),
+                "repeat" => next.max_repeated_calls = value,
+                "depth" => next.max_delegation_depth = value,
+                _ => {
+                    return Err(format!(
+                        "未知的限制项：{key}（可用 steps/time/tokens/repeat/depth）"
+                    ));
+                }
+            }
+        }
+        Ok(next)
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "工具轮次 {} · 耗时 {} · tokens {} · 重复调用 {} · 子任务嵌套 {} 层",
+            show_limit(u64::from(self.max_steps), " 步"),
+            show_limit(self.max_turn_secs, " 秒"),
+            show_limit(self.max_turn_tokens, ""),
+            show_limit(u64::from(self.max_repeated_calls), " 轮"),
+            self.max_delegation_depth,
+        )
+    }
+}
+
+fn show_limit(value: u64, unit: &str) -> String {
+    if value == 0 {
+        "不限".to_string()
+    } else {
+        format!("{value}{unit}")
+    }
+}
@@ -0,0 +1,284 @@
+//! 上下文窗口预算
+//!
+//! 本地估算请求的 token 数；超出模型上下文窗口时先截断较早的超长工具输出，
+//! 再按轮次丢弃最早的历史，始终保留系统提示词与最近的对话。
+
+use crate::types::{Attachment, Message, ROLE_REASONING, ROLE_SYSTEM, ROLE_TOOL, ROLE_USER};
+
+/// 每条消息的角色/分隔符开销（估算值）。
+const MESSAGE_OVERHEAD_TOKENS: usize = 4;
+/// 未配置 max_tokens 时为模型输出预留的 token 数。
+const DEFAULT_OUTPUT_RESERVE: usize = 4096;
+/// 无论预算如何都保留的最近消息条数。
+const KEEP_RECENT_MESSAGES: usize = 6;
+/// 每张图片按该 token 数估算（各家按分辨率计费，这里取常见截图的量级）。
+const IMAGE_TOKENS: usize = 1000;
+/// 工具输出超过该 token 数时才会被截断。
+const TOOL_OUTPUT_ELIDE_MIN_TOKENS: usize = 1024;
+const TOOL_OUTPUT_HEAD_CHARS: usize = 1500;
+const TOOL_OUTPUT_TAIL_CHARS: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContextBudget {
+    pub window: usize,
+    pub reserve: usize,
+}
+
+impl ContextBudget {
+    pub fn new(context_window: Option<u64>, max_tokens: Option<u64>) -> Option<Self> {
+        let window = context_window? as usize;
+        if window == 0 {
+            return None;
+        }
+        let reserve = max_tokens
+            .map(|v| v as usize)
+            .unwrap_or(DEFAULT_OUTPUT_RESERVE)
+            .min(window / 2);
+        Some(Self { window, reserve })
+    }
+
+    fn history_limit(&self, fixed_tokens: usize) -> usize {
+        self.window
+            .saturating_sub(self.reserve)
+            .saturating_sub(fixed_tokens)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContextReport {
+    /// 下一次请求预计占用的输入 token 数（含系统提示词与工具定义）。
+    pub estimated_tokens: usize,
+    pub window: Option<usize>,
+    /// 因超出窗口被省略的历史消息条数。
+    pub dropped_messages: usize,
+    /// 被截断的超长工具输出条数。
+    pub elided_tool_outputs: usize,
+}
+
+impl ContextReport {
+    pub fn truncated(&self) -> bool {
+        self.dropped_messages > 0 || self.elided_tool_outputs > 0
+    }
+}
+
+/// 粗略的本地 token 估算：ASCII 约 4 字符 1 token，CJK 等非 ASCII 字符约 1 字符 1 token。
+pub fn estimate_tokens(text: &str) -> usize {
+    let mut ascii = 0usize;
+    let mut other = 0usize;
+    for ch in text.chars() {
+        if ch.is_ascii() {
+            ascii += 1;
+        } else {
+            other += 1;
+        }
+    }
+    ascii.div_ceil(4) + other
+}
+
+/// 常见模型系列的默认上下文窗口；未知模型返回 None（不做裁剪）。
+pub fn default_context_window(model: &str) -> Option<u64> {
+    let model = model.to_ascii_lowercase();
+    let window = if model.starts_with("claude") {
+        200_000
+    } else if model.starts_with("gpt-4.1") || model.starts_with("gemini") {
+        1_000_000
+    } else if model.starts_with("deepseek")
+        || model.starts_with("gpt-4o")
+        || model.starts_with("gpt-5")
+        || model.starts_with("o1")
+        || model.starts_with("o3")
+        || model.starts_with("o4")
+        || model.starts_with("qwen")
+    {
+        128_000
+    } else {
+        return None;
+    };
+    Some(window)
+}
+
+/// UI 侧的固定开销估算：只计系统提示词（工具定义在请求时才确定）。
+pub fn system_prompt_tokens(messages: &[Message]) -> usize {
+    messages
+        .iter()
+        .filter(|m| m.role == ROLE_SYSTEM)
+        .map(|m| estimate_tokens(&m.content))
+        .sum()
+}
+
+/// 只计算预算结果，不复制消息；供 UI 每帧展示使用。
+pub fn context_report(
+    messages: &[Message],
+    fixed_tokens: usize,
+    budget: Option<ContextBudget>,
+) -> ContextReport {
+    plan_context(messages, fixed_tokens, budget).report
+}
+
+/// 按预算裁剪消息列表；被省略的部分在第一条保留的用户消息前加注说明。
+pub fn fit_history(
+    messages: &[Message],
+    fixed_tokens: usize,
+    budget: Option<ContextBudget>,
+) -> (Vec<Message>, ContextReport) {
+    let plan = plan_context(messages, fixed_tokens, budget);
+    if !plan.report.truncated() {
+        return (messages.to_vec(), plan.report);
+    }
+    let mut out = Vec::with_capacity(messages.len());
+    let mut note_pending = plan.report.dropped_messages > 0;
+    for (idx, msg) in messages.iter().enumerate() {
+        if msg.role == ROLE_SYSTEM {
+            out.push(msg.clone());
+            continue;
+        }
+        if idx < plan.keep_from {
+            continue;
+        }
+        let mut msg = msg.clone();
+        if plan.elided.contains(&idx) {
+            msg.content = elide_tool_output(&msg.content);
+        }
+        if note_pending && msg.role == ROLE_USER {
+            msg.content = format!(
+                "[较早的 {} 条消息已省略以适应上下文窗口]\n\n{}",
+                plan.report.dropped_messages, msg.content
+            );
+            note_pending = false;
+        }
+        out.push(msg);
+    }
+    (out, plan.report)
+}
+
+struct ContextPlan {
+    /// 下标小于该值的非系统消息被省略。
+    keep_from: usize,
+    elided: Vec<usize>,
+    report: ContextReport,
+}
+
+fn plan_context(
+    messages: &[Message],
+    fixed_tokens: usize,
+    budget: Option<ContextBudget>,
+) -> ContextPlan {
+    let costs: Vec<usize> = messages.iter().map(message_tokens).collect();
+    let history_total: usize = costs.iter().sum();
+    let mut plan = ContextPlan {
+        keep_from: 0,
+        elided: Vec::new(),
+        report: ContextReport {
+            estimated_tokens: fixed_tokens + history_total,
+            window: budget.map(|b| b.window),
+            dropped_messages: 0,
+            elided_tool_outputs: 0,
+        },
+    };
+    let Some(budget) = budget else {
+        return plan;
+    };
+    let limit = budget.history_limit(fixed_tokens);
+    if history_total <= limit {
+        return plan;
+    }
+    let protected_from = protected_start(messages);
+    let mut total = history_total;
+    for idx in 0..protected_from {
+        if total <= limit {
+            break;
+        }
+        let msg = &messages[idx];
+        if msg.role != ROLE_TOOL || costs[idx] <= TOOL_OUTPUT_ELIDE_MIN_TOKENS {
+            continue;
+        }
+        let elided_cost =
+            estimate_tokens(&elide_tool_output(&msg.content)) + MESSAGE_OVERHEAD_TOKENS;
+        total = total - costs[idx] + elided_cost;
+        plan.elided.push(idx);
+    }
+    let mut keep_from = 0;
+    while total > limit && keep_from < protected_from {
+        total -= current_cost(messages, &costs, &plan.elided, keep_from);
+        keep_from += 1;
+    }
+    // 按轮次丢弃：保留部分必须从一条用户消息开始，否则会留下找不到对应 tool_call 的工具结果。
+    // 保护区内的用户消息也可以作为起点，宁可多丢一些也不能切在一轮中间。
+    if keep_from > 0
+        && let Some(offset) = messages[keep_from..]
+            .iter()
+            .position(|m| m.role == ROLE_USER)
+    {
+        for idx in keep_from..keep_from + offset {
+            total -= current_cost(messages, &costs, &plan.elided, idx);
+        }
+        keep_from += offset;
+    }
+    plan.elided.retain(|idx| *idx >= keep_from);
+    let dropped = messages[..keep_from]
+        .iter()
+        .filter(|m| m.role != ROLE_SYSTEM && m.role != ROLE_REASONING)
+        .count();
+    plan.keep_from = keep_from;
+    plan.report.estimated_tokens = fixed_tokens + total;
+    plan.report.dropped_messages = dropped;
+    plan.report.elided_tool_outputs = plan.elided.len();
+    plan
+}
+
+fn current_cost(messages: &[Message], costs: &[usize], elided: &[usize], idx: usize) -> usize {
+    if elided.contains(&idx) {
+        return estimate_tokens(&elide_tool_output(&messages[idx].content))
+            + MESSAGE_OVERHEAD_TOKENS;
+    }
+    costs[idx]
+}
+
+/// 最后一条用户消息及其之后的内容、以及最近若干条消息不参与裁剪。
+fn protected_start(messages: &[Message]) -> usize {
+    let recent = messages.len().saturating_sub(KEEP_RECENT_MESSAGES);
+    let last_user = messages
+        .iter()
+        .rposition(|m| m.role == ROLE_USER)
+        .unwrap_or(messages.len());
+    recent.min(last_user)
+}
+
+/// 系统提示词由 preamble 单独计入，思考过程不会发送给模型。
+fn message_tokens(msg: &Message) -> usize {
+    if msg.role == ROLE_SYSTEM || msg.role == ROLE_REASONING {
+        return 0;
+    }
+    let attachments: usize = msg.attachments.iter().map(attachment_tokens).sum();
+    estimate_tokens(&msg.content) + attachments + MESSAGE_OVERHEAD_TOKENS
+}
+
+/// 引用文件按快照大小粗略估算（约 4 字节一个 token）。
+fn attachment_tokens(attachment: &Attachment) -> usize {
+    if crate::attachments::is_image(attachment) {
+        IMAGE_TOKENS
+    } else {
+        (crate::attachments::stored_size(attachment) as usize).div_ceil(4)
+    }
+}
+
+fn elide_tool_output(content: &str) -> String {
+    let total = content.chars().count();
+    if total <= TOOL_OUTPUT_HEAD_CHARS + TOOL_OUTPUT_TAIL_CHARS {
+        return content.to_string();
+    }
+    let head: String = content.chars().take(TOOL_OUTPUT_HEAD_CHARS).collect();
+    let tail: String = content
+        .chars()
+        .skip(total - TOOL_OUTPUT_TAIL_CHARS)
+        .collect();
+    let omitted: String = content
+        .chars()
+        .skip(TOOL_OUTPUT_HEAD_CHARS)
+        .take(total - TOOL_OUTPUT_HEAD_CHARS - TOOL_OUTPUT_TAIL_CHARS)
+        .collect();
+    format!(
+        "{head}\n…（工具输出过长，已省略约 {} tokens）…\n{tail}",
+        estimate_tokens(&omitted)
+    )
+}
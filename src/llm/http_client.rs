@@ -1,16 +1,26 @@
 use bytes::Bytes;
+use futures::StreamExt;
+use rig::http_client::sse::BoxedStream;
 use rig::http_client::{
-    HeaderMap, HeaderValue, HttpClientExt, LazyBody, MultipartForm, Request, Response, Result,
-    StreamingResponse,
+    Error, HeaderMap, HeaderValue, HttpClientExt, LazyBody, MultipartForm, Request, Response,
+    Result, StreamingResponse,
 };
 use rig::wasm_compat::WasmCompatSend;
 use std::future::Future;
 
+/// 所有提供方共用的 HTTP 客户端：流式请求补上 JSON 的 content-type，
+/// 失败响应的 Retry-After 头附在错误信息里，供重试退避读取。
 #[derive(Clone, Debug, Default)]
 pub struct JsonStreamingClient {
     inner: reqwest::Client,
 }
 
+impl JsonStreamingClient {
+    pub fn new(inner: reqwest::Client) -> Self {
+        Self { inner }
+    }
+}
+
 fn ensure_json_content_type(headers: &mut HeaderMap) {
     if headers.contains_key("content-type") {
         return;
@@ -18,6 +28,50 @@ fn ensure_json_content_type(headers: &mut HeaderMap) {
     headers.insert("content-type", HeaderValue::from_static("application/json"));
 }
 
+fn build_request<T: Into<Bytes>>(
+    client: &reqwest::Client,
+    req: Request<T>,
+) -> reqwest::RequestBuilder {
+    let (parts, body) = req.into_parts();
+    client
+        .request(parts.method, parts.uri.to_string())
+        .headers(parts.headers)
+        .body(body.into())
+}
+
+fn instance_error(err: reqwest::Error) -> Error {
+    Error::Instance(err.into())
+}
+
+/// 非 2xx 响应转成错误；429、503 带 Retry-After 头时把等待秒数附在错误信息末尾。
+async fn status_error(response: reqwest::Response) -> Error {
+    let status = response.status();
+    let retry_after = matches!(status.as_u16(), 429 | 503)
+        .then(|| retry_after_secs(response.headers()))
+        .flatten();
+    let text = response.text().await.unwrap_or_default();
+    let message = match retry_after {
+        Some(secs) => format!("{text}（retry-after: {secs}s）"),
+        None => text,
+    };
+    Error::InvalidStatusCodeWithMessage(status, message)
+}
+
+/// Retry-After 可以是秒数，也可以是 HTTP 日期。
+fn retry_after_secs(headers: &reqwest::header::HeaderMap) -> Option<u64> {
+    let value = headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(secs);
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let secs = (at.with_timezone(&chrono::Utc) - chrono::Utc::now()).num_seconds();
+    Some(secs.max(0) as u64)
+}
+
 impl HttpClientExt for JsonStreamingClient {
     fn send<T, U>(
         &self,
@@ -29,7 +83,22 @@ impl HttpClientExt for JsonStreamingClient {
         U: From<Bytes>,
         U: WasmCompatSend + 'static,
     {
-        HttpClientExt::send(&self.inner, req)
+        let req = build_request(&self.inner, req);
+        async move {
+            let response = req.send().await.map_err(instance_error)?;
+            if !response.status().is_success() {
+                return Err(status_error(response).await);
+            }
+            let mut res = Response::builder().status(response.status());
+            if let Some(headers) = res.headers_mut() {
+                *headers = response.headers().clone();
+            }
+            let body: LazyBody<U> = Box::pin(async {
+                let bytes = response.bytes().await.map_err(instance_error)?;
+                Ok(U::from(bytes))
+            });
+            res.body(body).map_err(Error::Protocol)
+        }
     }
 
     fn send_multipart<U>(
@@ -51,6 +120,24 @@ impl HttpClientExt for JsonStreamingClient {
         T: Into<Bytes>,
     {
         ensure_json_content_type(req.headers_mut());
-        HttpClientExt::send_streaming(&self.inner, req)
+        let req = build_request(&self.inner, req);
+        async move {
+            let response = req.send().await.map_err(instance_error)?;
+            if !response.status().is_success() {
+                return Err(status_error(response).await);
+            }
+            let mut res = Response::builder()
+                .status(response.status())
+                .version(response.version());
+            if let Some(headers) = res.headers_mut() {
+                *headers = response.headers().clone();
+            }
+            let stream: BoxedStream = Box::pin(
+                response
+                    .bytes_stream()
+                    .map(|chunk| chunk.map_err(instance_error)),
+            );
+            res.body(stream).map_err(Error::Protocol)
+        }
     }
 }
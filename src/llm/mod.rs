@@ -1,5 +1,11 @@
+pub mod agent_limits;
+pub mod context_budget;
 pub mod prompt_manager;
+pub mod prompt_store;
 pub mod prompts;
 pub mod rig;
 pub mod http_client;
+pub mod pricing;
+pub mod reasoning;
 pub mod templates;
+pub mod timeouts;
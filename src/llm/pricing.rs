@@ -0,0 +1,49 @@
+//! 按模型单价估算费用
+//!
+//! 单价以「美元 / 百万 tokens」配置；命中缓存的输入 tokens 按缓存价计费，未配置缓存价时按普通输入价。
+
+use crate::types::Usage;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPricing {
+    pub input: f64,
+    pub output: f64,
+    pub cached_input: Option<f64>,
+}
+
+impl ModelPricing {
+    /// 输入/输出单价都未配置时返回 None，费用显示为未知而不是 0。
+    pub fn from_prices(
+        input: Option<f64>,
+        output: Option<f64>,
+        cached_input: Option<f64>,
+    ) -> Option<Self> {
+        if input.is_none() && output.is_none() {
+            return None;
+        }
+        Some(Self {
+            input: input.unwrap_or(0.0),
+            output: output.unwrap_or(0.0),
+            cached_input,
+        })
+    }
+
+    pub fn cost(&self, usage: &Usage) -> f64 {
+        let prompt = usage.prompt_tokens.unwrap_or(0);
+        let completion = usage.completion_tokens.unwrap_or(0);
+        let cached = usage.cached_tokens.unwrap_or(0).min(prompt);
+        let cached_price = self.cached_input.unwrap_or(self.input);
+        let per_token = |tokens: u64, price: f64| tokens as f64 * price / 1_000_000.0;
+        per_token(prompt - cached, self.input)
+            + per_token(cached, cached_price)
+            + per_token(completion, self.output)
+    }
+}
+
+pub fn format_cost(cost: f64) -> String {
+    if cost >= 1.0 {
+        format!("${cost:.2}")
+    } else {
+        format!("${cost:.4}")
+    }
+}
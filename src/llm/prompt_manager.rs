@@ -1,6 +1,8 @@
 use crate::llm::templates::RigTemplates;
 use crate::types::Message as UiMessage;
+use rig::OneOrMany;
 use rig::completion::Message;
+use rig::completion::message::{ImageMediaType, UserContent};
 
 pub fn extract_system(messages: &[UiMessage]) -> String {
     messages
@@ -14,23 +16,25 @@ pub fn augment_system(base: &str) -> String {
     base.trim().to_string()
 }
 
+/// `include_images` 为 false 时（模型不支持图片输入）图片以文字占位代替。
 pub fn build_history_and_prompt(
     messages: &[UiMessage],
     templates: &RigTemplates,
-) -> Result<(Vec<Message>, String), String> {
+    include_images: bool,
+) -> Result<(Vec<Message>, Message), String> {
     let last_user_idx = find_last_user_index(messages);
     if let Some(idx) = last_user_idx {
         if has_tool_after(messages, idx) {
-            let history = build_history(messages, templates, None)?;
+            let history = build_history(messages, templates, None, include_images)?;
             let prompt = templates.render_followup()?;
-            return Ok((history, prompt));
+            return Ok((history, Message::user(prompt)));
         }
-        let history = build_history(messages, templates, Some(idx))?;
-        return Ok((history, messages[idx].content.clone()));
+        let history = build_history(messages, templates, Some(idx), include_images)?;
+        return Ok((history, user_message(&messages[idx], include_images)));
     }
-    let history = build_history(messages, templates, None)?;
+    let history = build_history(messages, templates, None, include_images)?;
     let prompt = templates.render_followup()?;
-    Ok((history, prompt))
+    Ok((history, Message::user(prompt)))
 }
 
 fn find_last_user_index(messages: &[UiMessage]) -> Option<usize> {
@@ -53,6 +57,7 @@ fn build_history(
     messages: &[UiMessage],
     templates: &RigTemplates,
     end: Option<usize>,
+    include_images: bool,
 ) -> Result<Vec<Message>, String> {
     let slice = match end {
         Some(end) => &messages[..end],
@@ -60,7 +65,7 @@ fn build_history(
     };
     let mut history = Vec::new();
     for msg in slice {
-        if let Some(entry) = map_history_message(msg, templates)? {
+        if let Some(entry) = map_history_message(msg, templates, include_images)? {
             history.push(entry);
         }
     }
@@ -70,6 +75,7 @@ fn build_history(
 fn map_history_message(
     msg: &UiMessage,
     templates: &RigTemplates,
+    include_images: bool,
 ) -> Result<Option<Message>, String> {
     if msg.role == crate::types::ROLE_SYSTEM {
         return Ok(None);
@@ -77,6 +83,12 @@ fn map_history_message(
     if msg.role == crate::types::ROLE_REASONING {
         return Ok(None);
     }
+    if msg.role == crate::types::ROLE_SUMMARY {
+        return Ok(Some(Message::user(format!(
+            "[此前对话的摘要]\n{}",
+            msg.content
+        ))));
+    }
     if msg.role == crate::types::ROLE_TOOL {
         let wrapped =
             templates.render_tool_result("tool", &serde_json::Value::Null, &msg.content)?;
@@ -84,6 +96,61 @@ fn map_history_message(
     }
     Ok(Some(match msg.role.as_str() {
         crate::types::ROLE_ASSISTANT => Message::assistant(msg.content.clone()),
-        _ => Message::user(msg.content.clone()),
+        _ => user_message(msg, include_images),
     }))
 }
+
+fn user_message(msg: &UiMessage, include_images: bool) -> Message {
+    if msg.attachments.is_empty() {
+        return Message::user(msg.content.clone());
+    }
+    let mut text = msg.content.clone();
+    let mut images = Vec::new();
+    for attachment in &msg.attachments {
+        if !crate::attachments::is_image(attachment) {
+            text.push_str("\n\n");
+            text.push_str(&crate::attachments::fenced_block(attachment));
+            continue;
+        }
+        let data = if include_images {
+            crate::attachments::load_base64(attachment).ok()
+        } else {
+            None
+        };
+        match data {
+            Some(data) => images.push(UserContent::image_base64(
+                data,
+                image_media_type(&attachment.mime_type),
+                None,
+            )),
+            None => text.push_str(&format!(
+                "\n[{}：{}]",
+                if include_images {
+                    "图片文件缺失"
+                } else {
+                    "当前模型不支持图片输入，已省略图片"
+                },
+                attachment.name
+            )),
+        }
+    }
+    if images.is_empty() {
+        return Message::user(text);
+    }
+    let mut content = vec![UserContent::text(text)];
+    content.extend(images);
+    match OneOrMany::many(content) {
+        Ok(content) => Message::User { content },
+        Err(_) => Message::user(msg.content.clone()),
+    }
+}
+
+fn image_media_type(mime_type: &str) -> Option<ImageMediaType> {
+    match mime_type {
+        "image/png" => Some(ImageMediaType::PNG),
+        "image/jpeg" => Some(ImageMediaType::JPEG),
+        "image/gif" => Some(ImageMediaType::GIF),
+        "image/webp" => Some(ImageMediaType::WEBP),
+        _ => None,
+    }
+}
@@ -0,0 +1,145 @@
+//! 在 `prompts_dir` 中新建、保存、复制、重命名与删除提示词文件
+//!
+//! 这里只改文件，不更新内存中的列表；调用方操作完成后用 [`PromptRegistry::reload`] 重新加载。
+
+use crate::llm::prompts::PromptRegistry;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+const PROMPT_EXT: &str = "txt";
+
+impl PromptRegistry {
+    /// 新建提示词文件；同名提示词已存在时报错。
+    pub fn create(&self, key: &str, content: &str) -> Result<PathBuf, String> {
+        validate_key(key)?;
+        if self.get(key).is_some() {
+            return Err(format!("提示词已存在：{key}"));
+        }
+        self.create_file(key, content)
+    }
+
+    /// 覆盖保存提示词；来自 `--system` 的内置提示词会落盘为新文件。
+    pub fn save(&self, key: &str, content: &str) -> Result<PathBuf, String> {
+        let path = self.ensure_file(key)?;
+        write_prompt(&path, content)?;
+        Ok(path)
+    }
+
+    /// 提示词对应的文件；内置提示词先按原内容落盘。
+    pub fn ensure_file(&self, key: &str) -> Result<PathBuf, String> {
+        let prompt = self
+            .get(key)
+            .ok_or_else(|| format!("提示词不存在：{key}"))?;
+        match &prompt.path {
+            Some(path) => Ok(path.clone()),
+            None => self.create_file(key, &prompt.content),
+        }
+    }
+
+    /// 复制为新提示词；未给出新名称时生成 `<原名>-copy`、`<原名>-copy2`……
+    pub fn duplicate(&self, key: &str, new_key: Option<&str>) -> Result<String, String> {
+        let source = self
+            .get(key)
+            .ok_or_else(|| format!("提示词不存在：{key}"))?;
+        let new_key = match new_key {
+            Some(new_key) => new_key.to_string(),
+            None => self.unused_key(&format!("{key}-copy")),
+        };
+        self.create(&new_key, &source.content)?;
+        Ok(new_key)
+    }
+
+    pub fn rename(&self, key: &str, new_key: &str) -> Result<(), String> {
+        validate_key(new_key)?;
+        if self.get(new_key).is_some() {
+            return Err(format!("提示词已存在：{new_key}"));
+        }
+        let path = self.file_of(key)?;
+        let ext = path
+            .extension()
+            .map_or(PROMPT_EXT.into(), |ext| ext.to_string_lossy());
+        let target = self.dir.join(format!("{new_key}.{ext}"));
+        if target.exists() {
+            return Err(existing_file(&target));
+        }
+        fs::rename(&path, &target).map_err(|e| format!("重命名提示词失败：{e}"))
+    }
+
+    pub fn delete(&self, key: &str) -> Result<(), String> {
+        let path = self.file_of(key)?;
+        fs::remove_file(&path).map_err(|e| format!("删除提示词失败：{e}"))
+    }
+
+    /// 提示词对应的文件；内置提示词没有文件时报错。
+    pub fn file_of(&self, key: &str) -> Result<PathBuf, String> {
+        let prompt = self
+            .get(key)
+            .ok_or_else(|| format!("提示词不存在：{key}"))?;
+        prompt
+            .path
+            .clone()
+            .ok_or_else(|| format!("提示词 {key} 来自命令行，没有对应的文件"))
+    }
+
+    /// 只新建不覆盖：解析失败而未列出的提示词文件仍占着这个名字。
+    fn create_file(&self, key: &str, content: &str) -> Result<PathBuf, String> {
+        let path = self.prompt_path(key);
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .map_err(|e| match e.kind() {
+                ErrorKind::AlreadyExists => existing_file(&path),
+                _ => format!("保存提示词失败：{}：{e}", path.display()),
+            })?;
+        file.write_all(with_trailing_newline(content).as_bytes())
+            .map_err(|e| format!("保存提示词失败：{}：{e}", path.display()))?;
+        Ok(path)
+    }
+
+    fn prompt_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.{PROMPT_EXT}"))
+    }
+
+    fn unused_key(&self, base: &str) -> String {
+        let mut key = base.to_string();
+        let mut n = 2;
+        while self.get(&key).is_some() || self.prompt_path(&key).exists() {
+            key = format!("{base}{n}");
+            n += 1;
+        }
+        key
+    }
+}
+
+/// 名称即文件名（不含扩展名），只允许出现在 `prompts_dir` 这一层。
+fn validate_key(key: &str) -> Result<(), String> {
+    if key.trim().is_empty() {
+        return Err("提示词名称不能为空".to_string());
+    }
+    let invalid = |c: char| matches!(c, '/' | '\\' | ':') || c.is_whitespace() || c.is_control();
+    if key.starts_with('.') || key.chars().any(invalid) {
+        return Err(format!(
+            "提示词名称不能以 . 开头，也不能包含空白或 / \\ : 等字符：{key}"
+        ));
+    }
+    Ok(())
+}
+
+fn write_prompt(path: &Path, content: &str) -> Result<(), String> {
+    fs::write(path, with_trailing_newline(content))
+        .map_err(|e| format!("保存提示词失败：{}：{e}", path.display()))
+}
+
+fn with_trailing_newline(content: &str) -> String {
+    let mut text = content.to_string();
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text
+}
+
+fn existing_file(path: &Path) -> String {
+    format!("文件已存在（可能是未能加载的提示词）：{}", path.display())
+}
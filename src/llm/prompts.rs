@@ -1,22 +1,83 @@
+use serde::Deserialize;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+const FRONT_MATTER_FENCE: &str = "---";
+
+/// 提示词文件中以 `---` 包围的 YAML 头部。
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PromptMeta {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// 选用该提示词时切换到的模型 key
+    pub model: Option<String>,
+    /// 允许使用的工具（如 web_search、code_exec）；不填则沿用命令行开关
+    pub tools: Option<Vec<String>>,
+    pub temperature: Option<f64>,
+}
+
+impl PromptMeta {
+    pub fn allows_tool(&self, name: &str) -> bool {
+        self.tools
+            .as_ref()
+            .is_none_or(|tools| tools.iter().any(|t| t == name))
+    }
+}
+
+/// `content` 保留文件原文（含 front-matter），发送时再解析与渲染，这样对话里保存的系统提示词自带这些设置。
 #[derive(Clone)]
 pub struct SystemPrompt {
     pub key: String,
     pub content: String,
+    pub meta: PromptMeta,
+    /// 对应的文件；来自 `--system` 的内置提示词没有文件
+    pub path: Option<PathBuf>,
 }
 
 #[derive(Clone)]
 pub struct PromptRegistry {
     pub default_key: String,
     pub prompts: Vec<SystemPrompt>,
+    /// 头部解析失败而被跳过的文件及原因
+    pub skipped: Vec<String>,
+    pub(crate) dir: PathBuf,
+    builtin_key: String,
+    builtin_content: String,
 }
 
 impl PromptRegistry {
     pub fn get(&self, key: &str) -> Option<&SystemPrompt> {
         self.prompts.iter().find(|p| p.key == key)
     }
+
+    /// 有文件被跳过时给出提示，启动与重新加载后显示。
+    pub fn skipped_notice(&self) -> Option<String> {
+        if self.skipped.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "已跳过 {} 个提示词文件：{}",
+            self.skipped.len(),
+            self.skipped.join("；")
+        ))
+    }
+
+    /// 重新读取提示词目录；原默认提示词仍存在时保持不变。
+    pub fn reload(&mut self) -> Result<(), String> {
+        let mut next = load_prompts(
+            &self.dir.to_string_lossy(),
+            &self.builtin_key,
+            &self.builtin_content,
+        )
+        .map_err(|e| format!("重新加载提示词失败：{e}"))?;
+        crate::llm::templates::clear_template_cache();
+        if next.get(&self.default_key).is_some() {
+            next.default_key = self.default_key.clone();
+        }
+        *self = next;
+        Ok(())
+    }
 }
 
 pub fn load_prompts(
@@ -26,13 +87,17 @@ pub fn load_prompts(
 ) -> Result<PromptRegistry, Box<dyn std::error::Error>> {
     let dir_path = PathBuf::from(dir);
     ensure_prompts_dir(&dir_path)?;
-    let mut prompts = read_prompts_from_dir(&dir_path)?;
+    let (mut prompts, skipped) = read_prompts_from_dir(&dir_path)?;
     prompts.sort_by(|a, b| a.key.cmp(&b.key));
     inject_default_prompt(&mut prompts, default_key, default_content);
-    let default_key = select_default_key(&prompts, default_key);
+    let selected = select_default_key(&prompts, default_key);
     Ok(PromptRegistry {
-        default_key,
+        default_key: selected,
         prompts,
+        skipped,
+        dir: dir_path,
+        builtin_key: default_key.to_string(),
+        builtin_content: default_content.to_string(),
     })
 }
 
@@ -46,18 +111,26 @@ fn ensure_prompts_dir(dir_path: &Path) -> Result<(), Box<dyn std::error::Error>>
     Ok(())
 }
 
-fn read_prompts_from_dir(dir_path: &Path) -> Result<Vec<SystemPrompt>, Box<dyn std::error::Error>> {
+/// 单个文件的头部有误时跳过它并记下原因，不影响其余提示词。
+fn read_prompts_from_dir(
+    dir_path: &Path,
+) -> Result<(Vec<SystemPrompt>, Vec<String>), Box<dyn std::error::Error>> {
     let mut prompts = Vec::new();
+    let mut skipped = Vec::new();
     let entries = fs::read_dir(dir_path)?;
     for entry in entries.flatten() {
         let path = entry.path();
-        if path.is_file()
-            && let Some(prompt) = read_prompt_file(&path)
-        {
-            prompts.push(prompt);
+        if !path.is_file() {
+            continue;
+        }
+        match read_prompt_file(&path) {
+            Ok(Some(prompt)) => prompts.push(prompt),
+            Ok(None) => {}
+            Err(err) => skipped.push(err),
         }
     }
-    Ok(prompts)
+    skipped.sort();
+    Ok((prompts, skipped))
 }
 
 fn inject_default_prompt(prompts: &mut Vec<SystemPrompt>, key: &str, content: &str) {
@@ -70,6 +143,8 @@ fn inject_default_prompt(prompts: &mut Vec<SystemPrompt>, key: &str, content: &s
         SystemPrompt {
             key: key.to_string(),
             content: content.to_string(),
+            meta: PromptMeta::default(),
+            path: None,
         },
     );
 }
@@ -84,11 +159,50 @@ fn select_default_key(prompts: &[SystemPrompt], key: &str) -> String {
         .unwrap_or_else(|| key.to_string())
 }
 
-fn read_prompt_file(path: &PathBuf) -> Option<SystemPrompt> {
-    let content = fs::read_to_string(path).ok()?;
-    let key = path.file_stem()?.to_string_lossy().to_string();
+fn read_prompt_file(path: &PathBuf) -> Result<Option<SystemPrompt>, String> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Ok(None);
+    };
+    let Some(key) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+        return Ok(None);
+    };
     if key.is_empty() {
-        return None;
+        return Ok(None);
     }
-    Some(SystemPrompt { key, content })
+    let (meta, _) = parse_front_matter(&content)
+        .map_err(|e| format!("提示词头部解析失败：{}：{e}", path.display()))?;
+    Ok(Some(SystemPrompt {
+        key,
+        content,
+        meta: meta.unwrap_or_default(),
+        path: Some(path.clone()),
+    }))
+}
+
+/// 拆出 YAML 头部与正文；没有头部时返回 `(None, 原文)`。
+pub fn parse_front_matter(text: &str) -> Result<(Option<PromptMeta>, &str), String> {
+    let Some(rest) = strip_fence_line(text) else {
+        return Ok((None, text));
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == FRONT_MATTER_FENCE {
+            let header = &rest[..offset];
+            let body = &rest[offset + line.len()..];
+            let meta = if header.trim().is_empty() {
+                PromptMeta::default()
+            } else {
+                serde_norway::from_str(header).map_err(|e| e.to_string())?
+            };
+            return Ok((Some(meta), body));
+        }
+        offset += line.len();
+    }
+    Err("缺少结束的 ---".to_string())
+}
+
+fn strip_fence_line(text: &str) -> Option<&str> {
+    let rest = text.strip_prefix(FRONT_MATTER_FENCE)?;
+    rest.strip_prefix("\r\n")
+        .or_else(|| rest.strip_prefix('\n'))
 }
@@ -0,0 +1,50 @@
+//! 推理（思考）参数
+//!
+//! 按模型配置：OpenAI 兼容接口发送 `reasoning_effort`，Anthropic 开启 extended thinking 并设置 `budget_tokens`；
+//! DeepSeek 是否推理由模型决定（deepseek-reasoner 总会返回 reasoning_content）。
+
+use serde::{Deserialize, Serialize};
+
+/// Anthropic extended thinking 允许的最小预算。
+pub const MIN_THINKING_BUDGET: u64 = 1024;
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReasoningEffort {
+    Low,
+    Medium,
+    High,
+}
+
+impl ReasoningEffort {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+
+    /// 未单独配置 thinking_budget 时，Anthropic 按档位换算的思考预算。
+    fn thinking_budget(self) -> u64 {
+        match self {
+            Self::Low => 2048,
+            Self::Medium => 8192,
+            Self::High => 24576,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ReasoningSettings {
+    pub effort: Option<ReasoningEffort>,
+    pub thinking_budget: Option<u64>,
+}
+
+impl ReasoningSettings {
+    pub fn anthropic_thinking_budget(&self) -> Option<u64> {
+        self.thinking_budget
+            .or_else(|| self.effort.map(ReasoningEffort::thinking_budget))
+            .map(|budget| budget.max(MIN_THINKING_BUDGET))
+    }
+}
@@ -1,3 +1,4 @@
+use crate::llm::context_budget::{ContextBudget, ContextReport, estimate_tokens, fit_history};
 use crate::llm::prompt_manager::{augment_system, build_history_and_prompt, extract_system};
 use crate::llm::http_client::JsonStreamingClient;
 use crate::llm::templates::RigTemplates;
@@ -6,42 +7,60 @@ use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderValue};
 use rig::completion::{CompletionModel, CompletionRequestBuilder, Message, ToolDefinition};
 use rig::prelude::CompletionClient;
 use rig::providers::{anthropic, deepseek, openai};
+use std::sync::Arc;
 
 pub struct RigRequestContext {
     pub preamble: String,
     pub history: Vec<Message>,
-    pub prompt: String,
+    pub prompt: Message,
     pub tools: Vec<ToolDefinition>,
+    pub context: ContextReport,
 }
 
 pub enum CompletionModelChoice {
-    OpenAi(openai::completion::CompletionModel),
+    OpenAi(openai::completion::CompletionModel<JsonStreamingClient>),
     DeepSeek(deepseek::CompletionModel<JsonStreamingClient>),
-    Anthropic(anthropic::completion::CompletionModel),
+    Anthropic(anthropic::completion::CompletionModel<JsonStreamingClient>),
 }
 
 pub fn prepare_rig_context(
     messages: &[UiMessage],
     prompts_dir: &str,
     enabled_tools: &[&str],
-) -> Result<(RigRequestContext, RigTemplates), String> {
+    budget: Option<ContextBudget>,
+    include_images: bool,
+) -> Result<(RigRequestContext, Arc<RigTemplates>), String> {
     let templates = RigTemplates::load(prompts_dir)?;
     let tools = filter_tools(templates.tool_defs()?, enabled_tools);
     let base_system = augment_system(&extract_system(messages));
     let preamble = build_preamble(&templates, &base_system, &tools)?;
-    let (history, prompt) = build_history_and_prompt(messages, &templates)?;
     let tool_defs = build_tool_defs(&tools);
+    let fixed_tokens = estimate_tokens(&preamble) + tool_defs_tokens(&tool_defs);
+    let (messages, context) = fit_history(messages, fixed_tokens, budget);
+    let (history, prompt) = build_history_and_prompt(&messages, &templates, include_images)?;
     Ok((
         RigRequestContext {
             preamble,
             history,
             prompt,
             tools: tool_defs,
+            context,
         },
         templates,
     ))
 }
 
+fn tool_defs_tokens(tools: &[ToolDefinition]) -> usize {
+    tools
+        .iter()
+        .map(|t| {
+            estimate_tokens(&t.name)
+                + estimate_tokens(&t.description)
+                + estimate_tokens(&t.parameters.to_string())
+        })
+        .sum()
+}
+
 fn build_preamble(
     templates: &RigTemplates,
     base_system: &str,
@@ -69,7 +88,7 @@ pub fn build_completion_request<M: CompletionModel>(
     ctx: &RigRequestContext,
 ) -> CompletionRequestBuilder<M> {
     model
-        .completion_request(Message::user(ctx.prompt.clone()))
+        .completion_request(ctx.prompt.clone())
         .preamble(ctx.preamble.clone())
         .messages(ctx.history.clone())
         .tools(ctx.tools.clone())
@@ -95,10 +114,10 @@ fn openai_completion_model(
     base_url: &str,
     api_key: &str,
     model: &str,
-) -> Result<openai::completion::CompletionModel, String> {
+) -> Result<openai::completion::CompletionModel<JsonStreamingClient>, String> {
     let url = normalize_openai_base_url(base_url);
     let http_client = build_http_client()?;
-    let client = openai::CompletionsClient::<reqwest::Client>::builder()
+    let client = openai::CompletionsClient::<JsonStreamingClient>::builder()
         .api_key(api_key)
         .base_url(&url)
         .http_client(http_client)
@@ -113,10 +132,11 @@ fn deepseek_completion_model(
     model: &str,
 ) -> Result<deepseek::CompletionModel<JsonStreamingClient>, String> {
     let url = normalize_deepseek_base_url(base_url);
+    let http_client = build_http_client()?;
     let client = deepseek::Client::<JsonStreamingClient>::builder()
         .api_key(api_key)
         .base_url(&url)
-        .http_client(JsonStreamingClient::default())
+        .http_client(http_client)
         .build()
         .map_err(|e| format!("初始化 DeepSeek 客户端失败：{e}"))?;
     Ok(client.completion_model(model))
@@ -126,10 +146,10 @@ fn anthropic_completion_model(
     base_url: &str,
     api_key: &str,
     model: &str,
-) -> Result<anthropic::completion::CompletionModel, String> {
+) -> Result<anthropic::completion::CompletionModel<JsonStreamingClient>, String> {
     let url = normalize_anthropic_base_url(base_url);
     let http_client = build_http_client()?;
-    let client = anthropic::Client::<reqwest::Client>::builder()
+    let client = anthropic::Client::<JsonStreamingClient>::builder()
         .api_key(api_key)
         .base_url(&url)
         .http_client(http_client)
@@ -138,12 +158,13 @@ fn anthropic_completion_model(
     Ok(client.completion_model(model))
 }
 
-fn build_http_client() -> Result<reqwest::Client, String> {
+fn build_http_client() -> Result<JsonStreamingClient, String> {
     let mut headers = HeaderMap::new();
     headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
     reqwest::Client::builder()
         .default_headers(headers)
         .build()
+        .map(JsonStreamingClient::new)
         .map_err(|e| format!("初始化 HTTP 客户端失败：{e}"))
 }
 
@@ -166,6 +187,11 @@ fn normalize_anthropic_base_url(base_url: &str) -> String {
     trimmed.strip_suffix("/v1").unwrap_or(trimmed).to_string()
 }
 
+/// DeepSeek 接口不接受图片内容；其余提供方按多模态处理，由模型自行拒绝不支持的输入。
+pub fn supports_image_input(base_url: &str, model: &str) -> bool {
+    !is_deepseek_provider(base_url, model)
+}
+
 fn is_deepseek_provider(base_url: &str, model: &str) -> bool {
     let base = base_url.to_ascii_lowercase();
     if base.contains("deepseek") {
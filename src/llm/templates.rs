@@ -1,5 +1,7 @@
 use minijinja::{Environment, Value, context};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
 
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
 pub struct ToolSchema {
@@ -8,13 +10,45 @@ pub struct ToolSchema {
     pub parameters: serde_json::Value,
 }
 
+/// 单个项目文件插入系统提示词的上限
+const PROJECT_FILE_MAX_BYTES: u64 = 64 * 1024;
+
+/// 系统提示词模板在发送时可用的变量；另有 `date`、`time`、`os` 及函数 `project_file(path)`。
+pub struct PromptVars {
+    pub workspace: Option<PathBuf>,
+    pub category: String,
+    pub model: String,
+}
+
 pub struct RigTemplates {
     env: Environment<'static>,
     root: PathBuf,
+    tools: OnceLock<Result<Vec<ToolSchema>, String>>,
+}
+
+/// 按提示词目录缓存的模板；模板与工具定义在首次用到时从磁盘读取，之后各请求共用。
+static TEMPLATES: Mutex<Option<HashMap<String, Arc<RigTemplates>>>> = Mutex::new(None);
+
+/// 提示词目录的文件有变动时丢弃缓存，下次请求重新读取。
+pub fn clear_template_cache() {
+    if let Ok(mut cache) = TEMPLATES.lock() {
+        *cache = None;
+    }
 }
 
 impl RigTemplates {
-    pub fn load(prompts_dir: &str) -> Result<Self, String> {
+    pub fn load(prompts_dir: &str) -> Result<Arc<Self>, String> {
+        let mut cache = TEMPLATES.lock().map_err(|_| "模板缓存不可用".to_string())?;
+        let cache = cache.get_or_insert_with(HashMap::new);
+        if let Some(templates) = cache.get(prompts_dir) {
+            return Ok(templates.clone());
+        }
+        let templates = Arc::new(Self::open(prompts_dir)?);
+        cache.insert(prompts_dir.to_string(), templates.clone());
+        Ok(templates)
+    }
+
+    fn open(prompts_dir: &str) -> Result<Self, String> {
         let root = Path::new(prompts_dir).join("rig");
         if !root.exists() {
             return Err(format!("缺少工具模板目录：{}", root.display()));
@@ -25,14 +59,17 @@ impl RigTemplates {
             "tojson",
             |value: Value| -> Result<String, minijinja::Error> { Ok(value.to_string()) },
         );
-        Ok(Self { env, root })
+        Ok(Self {
+            env,
+            root,
+            tools: OnceLock::new(),
+        })
     }
 
     pub fn tool_defs(&self) -> Result<Vec<ToolSchema>, String> {
-        let path = self.root.join("tools.json");
-        let text = std::fs::read_to_string(&path)
-            .map_err(|e| format!("读取工具定义失败：{} ({e})", path.display()))?;
-        serde_json::from_str(&text).map_err(|e| format!("解析工具定义失败：{e}"))
+        self.tools
+            .get_or_init(|| read_tool_defs(&self.root))
+            .clone()
     }
 
     pub fn render_preamble(
@@ -70,4 +107,62 @@ impl RigTemplates {
         tmpl.render(context! {})
             .map_err(|e| format!("渲染模板失败：{e}"))
     }
+
+    pub fn render_system(&self, source: &str, vars: &PromptVars) -> Result<String, String> {
+        let mut env = self.env.clone();
+        let root = vars.workspace.clone();
+        env.add_function(
+            "project_file",
+            move |path: String| -> Result<String, minijinja::Error> {
+                read_project_file(root.as_deref(), &path)
+                    .map_err(|e| minijinja::Error::new(minijinja::ErrorKind::InvalidOperation, e))
+            },
+        );
+        let now = chrono::Local::now();
+        let workspace = vars
+            .workspace
+            .as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_default();
+        env.render_str(
+            source,
+            context! {
+                date => now.format("%Y-%m-%d").to_string(),
+                time => now.format("%H:%M").to_string(),
+                os => std::env::consts::OS,
+                workspace => workspace,
+                category => vars.category,
+                model => vars.model,
+            },
+        )
+        .map_err(|e| format!("渲染系统提示词失败：{e}"))
+    }
+}
+
+fn read_tool_defs(root: &Path) -> Result<Vec<ToolSchema>, String> {
+    let path = root.join("tools.json");
+    let text = std::fs::read_to_string(&path)
+        .map_err(|e| format!("读取工具定义失败：{} ({e})", path.display()))?;
+    serde_json::from_str(&text).map_err(|e| format!("解析工具定义失败：{e}"))
+}
+
+/// 只允许读取工作区内的文件，过大的文件拒绝插入。
+fn read_project_file(root: Option<&Path>, path: &str) -> Result<String, String> {
+    let root = root.ok_or_else(|| "未配置工作区，无法读取项目文件".to_string())?;
+    let full = root
+        .join(path)
+        .canonicalize()
+        .map_err(|e| format!("读取项目文件失败：{path}：{e}"))?;
+    if !full.starts_with(root) {
+        return Err(format!("项目文件不在工作区内：{path}"));
+    }
+    let size = std::fs::metadata(&full)
+        .map_err(|e| format!("读取项目文件失败：{path}：{e}"))?
+        .len();
+    if size > PROJECT_FILE_MAX_BYTES {
+        return Err(format!(
+            "项目文件过大：{path}（{size} 字节，上限 {PROJECT_FILE_MAX_BYTES}）"
+        ));
+    }
+    std::fs::read_to_string(&full).map_err(|e| format!("读取项目文件失败：{path}：{e}"))
 }
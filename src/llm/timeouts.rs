@@ -0,0 +1,48 @@
+//! 流式响应的超时设置
+//!
+//! 按模型分别限制建立连接、等待首个 token、相邻两个片段之间的时间；配置为 0 表示不限制。
+
+use std::time::Duration;
+
+const DEFAULT_CONNECT_SECS: u64 = 30;
+/// 推理模型首个 token 可能较慢，默认放宽
+const DEFAULT_FIRST_TOKEN_SECS: u64 = 180;
+const DEFAULT_IDLE_SECS: u64 = 90;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StreamTimeouts {
+    pub connect: Option<Duration>,
+    pub first_token: Option<Duration>,
+    pub idle: Option<Duration>,
+    /// 超时后是否自动重新发起请求
+    pub retry_on_stall: bool,
+}
+
+impl StreamTimeouts {
+    pub fn from_config(
+        connect_secs: Option<u64>,
+        first_token_secs: Option<u64>,
+        idle_secs: Option<u64>,
+        retry_on_stall: Option<bool>,
+    ) -> Self {
+        Self {
+            connect: limit(connect_secs, DEFAULT_CONNECT_SECS),
+            first_token: limit(first_token_secs, DEFAULT_FIRST_TOKEN_SECS),
+            idle: limit(idle_secs, DEFAULT_IDLE_SECS),
+            retry_on_stall: retry_on_stall.unwrap_or(true),
+        }
+    }
+}
+
+impl Default for StreamTimeouts {
+    fn default() -> Self {
+        Self::from_config(None, None, None, None)
+    }
+}
+
+fn limit(secs: Option<u64>, default_secs: u64) -> Option<Duration> {
+    match secs.unwrap_or(default_secs) {
+        0 => None,
+        secs => Some(Duration::from_secs(secs)),
+    }
+}
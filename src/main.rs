@@ -1,4 +1,5 @@
 mod args;
+mod attachments;
 mod config;
 mod conversation;
 mod debug;
@@ -6,6 +7,8 @@ mod hooks;
 mod llm;
 mod model_registry;
 mod question_set;
+mod persist;
+mod recovery;
 mod render;
 mod session;
 mod types;
@@ -14,9 +17,9 @@ mod framework;
 mod services;
 
 mod cli;
-use args::{Args, Cli, Command, ModelCommand};
+use args::{Args, Cli, Command, ModelCommand, SandboxCommand};
 use clap::Parser;
-use config::{Config, default_config_path, load_config};
+use config::{Config, ExecBackend, default_config_path, load_config};
 use question_set::{list_question_sets, question_sets_dir};
 use render::theme_from_config;
 use std::env;
@@ -30,15 +33,16 @@ fn run_with_args(
         debug::wait_for_gdb_attach()?;
     }
     apply_env_from_args(&args);
-    crate::services::workspace::resolve_workspace(&args)
-        .map_err(|e| format!("workspace 校验失败：{e}"))?;
     if maybe_list_question_sets(&args)? {
         return Ok(());
     }
     let cfg_path = config_path_from_cli(cfg_override)?;
     let cfg = load_config_with_path(&cfg_path)?;
+    apply_env_from_config(&cfg);
+    let args = recovery::offer_recovery(args)?;
+    let startup_notice = cli::sandbox::startup_notice(&args, &cfg);
     let theme = theme_from_config(&cfg)?;
-    ui::run(args, cfg, &theme)?;
+    ui::run(args, cfg, &theme, startup_notice)?;
     Ok(())
 }
 
@@ -54,6 +58,19 @@ fn apply_env_from_args(args: &Args) {
             env::set_var("DEEPCHAT_READ_ONLY", "1");
         }
     }
+    if args.python_kernel_enabled() && env::var("DEEPCHAT_CODE_EXEC_KERNEL").is_err() {
+        unsafe {
+            env::set_var("DEEPCHAT_CODE_EXEC_KERNEL", "1");
+        }
+    }
+}
+
+fn apply_env_from_config(cfg: &Config) {
+    if cfg.exec_backend == ExecBackend::Host && env::var("DEEPCHAT_EXEC_BACKEND").is_err() {
+        unsafe {
+            env::set_var("DEEPCHAT_EXEC_BACKEND", "host");
+        }
+    }
 }
 
 fn maybe_list_question_sets(args: &Args) -> Result<bool, Box<dyn std::error::Error>> {
@@ -96,6 +113,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         Some(Command::Model { command }) => match command {
             ModelCommand::Add => cli::model::run_add(cli.config.as_deref()),
         },
+        Some(Command::Sandbox { command }) => match command {
+            SandboxCommand::Build {
+                dockerfile,
+                no_cache,
+            } => cli::sandbox::run_build(cli.config.as_deref(), dockerfile.as_deref(), no_cache),
+            SandboxCommand::Status => cli::sandbox::run_status(cli.config.as_deref()),
+            SandboxCommand::Prune => cli::sandbox::run_prune(),
+        },
+        Some(Command::Usage { days }) => cli::usage::run_report(days),
         None => run_with_args(cli.args, cli.config.as_deref()),
     }
 }
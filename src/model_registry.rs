@@ -1,4 +1,8 @@
 use crate::config::Config;
+use crate::llm::context_budget::default_context_window;
+use crate::llm::pricing::ModelPricing;
+use crate::llm::reasoning::ReasoningSettings;
+use crate::llm::timeouts::StreamTimeouts;
 
 #[derive(Clone)]
 pub struct ModelProfile {
@@ -7,6 +11,10 @@ pub struct ModelProfile {
     pub api_key: String,
     pub model: String,
     pub max_tokens: Option<u64>,
+    pub context_window: Option<u64>,
+    pub pricing: Option<ModelPricing>,
+    pub reasoning: ReasoningSettings,
+    pub timeouts: StreamTimeouts,
 }
 
 #[derive(Clone)]
@@ -62,6 +70,20 @@ pub fn build_model_registry(cfg: &Config) -> ModelRegistry {
         .iter()
         .cloned()
         .map(|m| ModelProfile {
+            context_window: m
+                .context_window
+                .or_else(|| default_context_window(&m.model)),
+            pricing: ModelPricing::from_prices(m.input_price, m.output_price, m.cached_input_price),
+            reasoning: ReasoningSettings {
+                effort: m.reasoning_effort,
+                thinking_budget: m.thinking_budget,
+            },
+            timeouts: StreamTimeouts::from_config(
+                m.connect_timeout_secs,
+                m.first_token_timeout_secs,
+                m.idle_timeout_secs,
+                m.retry_on_stall,
+            ),
             key: m.key,
             base_url: m.base_url.trim_end_matches('/').to_string(),
             api_key: m.api_key,
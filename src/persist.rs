@@ -0,0 +1,74 @@
+//! 文件落盘
+//!
+//! 对话与会话文件先写入同目录下的临时文件，再改名覆盖原文件，写到一半时崩溃或断电也不会留下截断的
+//! JSON。读到无法解析的文件时改名隔离，避免每次启动都因同一个坏文件失败。
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 写入临时文件并同步到磁盘后改名覆盖 `path`，再同步所在目录让改名本身落盘；
+/// 失败时清理临时文件，原文件保持不变。
+pub fn write_atomic(path: &Path, text: &str) -> io::Result<()> {
+    let tmp = sibling_path(path, ".", &format!(".tmp-{}", std::process::id()));
+    let result = write_and_sync(&tmp, text).and_then(|()| fs::rename(&tmp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result?;
+    sync_parent_dir(path)
+}
+
+fn write_and_sync(path: &Path, text: &str) -> io::Result<()> {
+    let mut file = File::create(path)?;
+    file.write_all(text.as_bytes())?;
+    file.sync_all()
+}
+
+#[cfg(unix)]
+fn sync_parent_dir(path: &Path) -> io::Result<()> {
+    match path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        Some(dir) => File::open(dir)?.sync_all(),
+        None => File::open(".")?.sync_all(),
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_parent_dir(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+/// 把损坏的文件改名为 `<原名>.corrupt-<时间戳>`，返回新路径。
+pub fn quarantine(path: &Path) -> io::Result<PathBuf> {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let target = sibling_path(path, "", &format!(".corrupt-{secs}"));
+    fs::rename(path, &target)?;
+    Ok(target)
+}
+
+/// JSON 语法错误或文件被截断时才算损坏；字段不匹配可能来自其他版本，不隔离。
+pub fn is_corrupted_json(err: &serde_json::Error) -> bool {
+    err.is_syntax() || err.is_eof()
+}
+
+/// FNV-1a 64 位内容摘要，用于按内容命名或比较文件；跨版本、跨进程保持稳定。
+pub fn content_hash(bytes: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("{hash:016x}")
+}
+
+fn sibling_path(path: &Path, prefix: &str, suffix: &str) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!("{prefix}{name}{suffix}"))
+}
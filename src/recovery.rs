@@ -0,0 +1,216 @@
+//! 崩溃恢复
+//!
+//! 运行时在对话变化（发送问题、追加工具结果、回复完成）或开关标签页后记下所有打开对话的快照；程序 panic 时由钩子把快照写入 recovery.json。
+//! 下次启动发现该文件时，先把其中的对话写回对话目录，再询问是否重新打开它们。
+
+use crate::args::Args;
+use crate::conversation::{ConversationData, load_conversation, save_conversation};
+use crate::types::ROLE_SYSTEM;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::PathBuf;
+use std::sync::{Mutex, TryLockError};
+
+#[derive(Serialize, Deserialize)]
+pub struct RecoveryData {
+    #[serde(default)]
+    pub active_conversation: Option<String>,
+    #[serde(default)]
+    pub conversations: Vec<ConversationData>,
+}
+
+static SNAPSHOT: Mutex<Option<RecoveryData>> = Mutex::new(None);
+
+pub fn recovery_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let home = env::var("HOME").map_err(|_| "无法确定 HOME")?;
+    Ok(PathBuf::from(home)
+        .join(".local")
+        .join("share")
+        .join("deepseek")
+        .join("recovery.json"))
+}
+
+/// 刷新内存中的快照，供 panic 时写出：`changed` 替换快照中的同一对话，已关闭的对话移出，
+/// 快照里还没有的打开对话由 `missing` 补上，其余对话沿用上次的副本。
+pub fn update_snapshot(
+    active_conversation: Option<String>,
+    open: &[String],
+    changed: Vec<ConversationData>,
+    missing: impl Fn(&str) -> Option<ConversationData>,
+) {
+    let Ok(mut snapshot) = SNAPSHOT.lock() else {
+        return;
+    };
+    let mut known: HashMap<String, ConversationData> = snapshot
+        .take()
+        .map(|data| data.conversations)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|conv| (conv.id.clone(), conv))
+        .collect();
+    for conv in changed {
+        known.insert(conv.id.clone(), conv);
+    }
+    let conversations = open
+        .iter()
+        .filter_map(|id| known.remove(id).or_else(|| missing(id)))
+        .collect();
+    *snapshot = Some(RecoveryData {
+        active_conversation,
+        conversations,
+    });
+}
+
+/// 快照中各对话的消息数（含系统提示词与归档消息），用来判断打开的对话是否已有变化。
+pub fn snapshot_lengths() -> HashMap<String, usize> {
+    let Ok(snapshot) = SNAPSHOT.lock() else {
+        return HashMap::new();
+    };
+    snapshot
+        .iter()
+        .flat_map(|data| &data.conversations)
+        .map(|conv| {
+            (
+                conv.id.clone(),
+                conv.messages.len() + conv.archived_messages.len(),
+            )
+        })
+        .collect()
+}
+
+/// 正常退出时清空快照。
+pub fn clear_snapshot() {
+    if let Ok(mut snapshot) = SNAPSHOT.lock() {
+        *snapshot = None;
+    }
+}
+
+/// 把快照写入恢复文件，返回写入的路径；没有快照或写入失败时返回 None。
+/// 在 panic 钩子中调用：锁已被毒化时仍读取其中的数据，恰好在更新快照时 panic 则放弃。
+pub fn dump_snapshot() -> Option<PathBuf> {
+    let snapshot = match SNAPSHOT.try_lock() {
+        Ok(guard) => guard,
+        Err(TryLockError::Poisoned(err)) => err.into_inner(),
+        Err(TryLockError::WouldBlock) => return None,
+    };
+    let data = snapshot.as_ref()?;
+    let path = recovery_path().ok()?;
+    fs::create_dir_all(path.parent()?).ok()?;
+    let text = serde_json::to_string_pretty(data).ok()?;
+    crate::persist::write_atomic(&path, &text).ok()?;
+    Some(path)
+}
+
+/// 启动时检查恢复文件：对话写回对话目录后询问是否重新打开，同意时改为恢复一个新会话。
+/// 已指定 `--resume` 或问题集时不打扰，恢复文件留到下次。
+pub fn offer_recovery(mut args: Args) -> Result<Args, Box<dyn std::error::Error>> {
+    if args.resume.is_some() || args.question_set.is_some() {
+        return Ok(args);
+    }
+    let Some(mut data) = read_recovery()? else {
+        return Ok(args);
+    };
+    let path = recovery_path()?;
+    data.conversations.retain(|conv| history_len(conv) > 0);
+    if data.conversations.is_empty() {
+        fs::remove_file(&path)?;
+        return Ok(args);
+    }
+    for conv in data
+        .conversations
+        .iter()
+        .filter(|conv| should_write_back(conv))
+    {
+        save_conversation(conv).map_err(|e| format!("写回恢复的对话失败：{e}"))?;
+    }
+    print_recovered(&data);
+    if confirm_restore()? {
+        args.resume = Some(save_recovered_session(&data)?);
+    } else {
+        println!("已保留这些对话，可用 /open <id> 打开。");
+    }
+    fs::remove_file(&path)?;
+    Ok(args)
+}
+
+/// 读取恢复文件；文件损坏时隔离并提示，按没有恢复文件处理。
+fn read_recovery() -> Result<Option<RecoveryData>, Box<dyn std::error::Error>> {
+    let path = recovery_path()?;
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    match serde_json::from_str(&text) {
+        Ok(data) => Ok(Some(data)),
+        Err(err) => {
+            let moved = crate::persist::quarantine(&path)?;
+            eprintln!("恢复文件已损坏（{err}），已移到 {}", moved.display());
+            Ok(None)
+        }
+    }
+}
+
+/// 只在对话文件缺失、损坏或比快照旧时写回，避免覆盖快照之后保存的内容。
+fn should_write_back(conv: &ConversationData) -> bool {
+    match load_conversation(&conv.id) {
+        Ok(saved) => history_len(&saved) < history_len(conv),
+        Err(_) => true,
+    }
+}
+
+/// 不含系统提示词的消息数，压缩归档的消息也计入。
+fn history_len(conv: &ConversationData) -> usize {
+    let active = conv
+        .messages
+        .iter()
+        .filter(|m| m.role != ROLE_SYSTEM)
+        .count();
+    active + conv.archived_messages.len()
+}
+
+fn print_recovered(data: &RecoveryData) {
+    println!(
+        "上次 deepchat 异常退出，已找回 {} 个对话：",
+        data.conversations.len()
+    );
+    for conv in &data.conversations {
+        match conv.title.as_deref() {
+            Some(title) => println!("  {}  {title}", conv.id),
+            None => println!("  {}", conv.id),
+        }
+    }
+}
+
+/// 非交互环境下不询问，只写回对话。
+fn confirm_restore() -> Result<bool, Box<dyn std::error::Error>> {
+    if !io::stdin().is_terminal() {
+        return Ok(false);
+    }
+    print!("是否重新打开这些对话？[Y/n] ");
+    io::stdout().flush()?;
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line)?;
+    let answer = line.trim().to_lowercase();
+    Ok(answer.is_empty() || answer == "y" || answer == "yes")
+}
+
+fn save_recovered_session(data: &RecoveryData) -> Result<String, Box<dyn std::error::Error>> {
+    let open: Vec<String> = data.conversations.iter().map(|c| c.id.clone()).collect();
+    let active_category = data
+        .active_conversation
+        .as_deref()
+        .and_then(|id| data.conversations.iter().find(|c| c.id == id))
+        .map(|c| c.category.as_str());
+    let location = crate::session::save_session(
+        &[],
+        &open,
+        data.active_conversation.as_deref(),
+        active_category,
+        None,
+    )?;
+    Ok(location.id)
+}
@@ -5,7 +5,9 @@ use crate::render::markdown::{
 };
 use crate::render::theme::{RenderTheme, theme_cache_key};
 use crate::render::util::{hash_message, label_for_role, ranges_overlap, suffix_for_index};
-use crate::types::{Message, ROLE_ASSISTANT, ROLE_REASONING, ROLE_SYSTEM, ROLE_TOOL, ROLE_USER};
+use crate::types::{
+    Message, ROLE_ASSISTANT, ROLE_REASONING, ROLE_SUMMARY, ROLE_SYSTEM, ROLE_TOOL, ROLE_USER,
+};
 use ratatui::text::{Line, Text};
 use std::borrow::Cow;
 
@@ -260,7 +262,7 @@ fn render_message_content_lines(
     streaming: bool,
 ) -> Vec<Line<'static>> {
     match msg.role.as_str() {
-        ROLE_USER | ROLE_ASSISTANT | ROLE_REASONING | ROLE_SYSTEM | ROLE_TOOL => {
+        ROLE_USER | ROLE_ASSISTANT | ROLE_REASONING | ROLE_SYSTEM | ROLE_TOOL | ROLE_SUMMARY => {
             let content = message_content(msg, streaming);
             render_markdown_lines(content.as_ref(), width, theme, streaming, false)
         }
@@ -269,7 +271,7 @@ fn render_message_content_lines(
 }
 pub(crate) fn count_message_lines(msg: &Message, width: usize, streaming: bool) -> usize {
     match msg.role.as_str() {
-        ROLE_USER | ROLE_ASSISTANT | ROLE_REASONING | ROLE_SYSTEM | ROLE_TOOL => {
+        ROLE_USER | ROLE_ASSISTANT | ROLE_REASONING | ROLE_SYSTEM | ROLE_TOOL | ROLE_SUMMARY => {
             let content = message_content(msg, streaming);
             count_markdown_lines(content.as_ref(), width)
         }
@@ -277,7 +279,17 @@ pub(crate) fn count_message_lines(msg: &Message, width: usize, streaming: bool)
     }
 }
 
+/// 摘要消息前加一条分隔线，标出压缩发生的位置。
+const COMPACT_DIVIDER: &str = "*──────── 以上较早的对话已压缩，原文保存在会话文件中 ────────*";
+
 fn message_content<'a>(msg: &'a Message, streaming: bool) -> Cow<'a, str> {
+    if msg.role == ROLE_SUMMARY {
+        return Cow::Owned(format!("{COMPACT_DIVIDER}\n\n{}", msg.content));
+    }
+    if !msg.attachments.is_empty() {
+        let chips = crate::attachments::placeholders(&msg.attachments);
+        return Cow::Owned(format!("`{chips}`\n\n{}", msg.content));
+    }
     if streaming {
         Cow::Owned(close_unbalanced_code_fence(&msg.content))
     } else {
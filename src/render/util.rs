@@ -1,4 +1,6 @@
-use crate::types::{ROLE_ASSISTANT, ROLE_REASONING, ROLE_SYSTEM, ROLE_TOOL, ROLE_USER};
+use crate::types::{
+    ROLE_ASSISTANT, ROLE_REASONING, ROLE_SUMMARY, ROLE_SYSTEM, ROLE_TOOL, ROLE_USER,
+};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
@@ -29,6 +31,7 @@ pub(crate) fn label_for_role(role: &str, suffix: Option<&str>) -> Option<String>
         ROLE_REASONING => Some("🧠".to_string()),
         ROLE_SYSTEM => Some("⚙️".to_string()),
         ROLE_TOOL => Some("🔧".to_string()),
+        ROLE_SUMMARY => Some("📌 对话摘要".to_string()),
         _ => None,
     }
 }
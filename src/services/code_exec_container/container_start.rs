@@ -100,7 +100,7 @@ fn add_workspace_mount(cmd: &mut Command, workspace: Option<&WorkspaceConfig>) {
     };
     cmd.arg("-v").arg(format!(
         "{}:{}",
-        workspace.host_path.display(),
+        workspace.mount_source().display(),
         workspace.mount_path
     ));
     cmd.arg("-e")
@@ -130,17 +130,27 @@ fn apply_network_mode(cmd: &mut Command) {
 }
 
 fn run_container_command(mut cmd: Command) -> Result<String, String> {
+    let image = code_exec_image();
     let output = cmd
-        .arg(code_exec_image())
+        .arg(&image)
         .arg("sleep")
         .arg("infinity")
         .output()
-        .map_err(|e| format!("Docker 启动失败：{e}"))?;
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                "Docker 启动失败：未找到 docker 命令，请安装 Docker，或在配置中设置 exec_backend: \"host\"".to_string()
+            } else {
+                format!("Docker 启动失败：{e}")
+            }
+        })?;
     if !output.status.success() {
-        return Err(format!(
-            "Docker 启动失败：{}",
-            String::from_utf8_lossy(&output.stderr)
-        ));
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if is_missing_image_error(&stderr) {
+            return Err(format!(
+                "Docker 启动失败：沙箱镜像 {image} 不存在，请先运行 `deepchat sandbox build` 构建"
+            ));
+        }
+        return Err(format!("Docker 启动失败：{stderr}"));
     }
     let id = String::from_utf8_lossy(&output.stdout).trim().to_string();
     if id.is_empty() {
@@ -149,6 +159,12 @@ fn run_container_command(mut cmd: Command) -> Result<String, String> {
     Ok(id)
 }
 
+fn is_missing_image_error(stderr: &str) -> bool {
+    stderr.contains("Unable to find image")
+        || stderr.contains("pull access denied")
+        || stderr.contains("No such image")
+}
+
 pub(super) fn is_container_running(container_id: &str) -> bool {
     if let Ok(output) = Command::new("docker")
         .arg("inspect")
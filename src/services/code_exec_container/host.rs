@@ -0,0 +1,101 @@
+use crate::services::host_sandbox::{
+    host_command, kill_process_group, prepare_scratch_dirs, run_dir,
+};
+use crate::services::workspace::WorkspaceConfig;
+use crate::ui::state::{CodeExecLive, CodeExecStdin};
+use std::process::Stdio;
+use std::sync::atomic::AtomicBool;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+
+use super::stream::{
+    OutputTarget, finalize_exec, mark_cancelled, spawn_stdin_writer, spawn_stream_reader,
+    take_child_pipes,
+};
+
+pub(crate) fn run_python_on_host_stream(
+    workspace: &WorkspaceConfig,
+    run_id: &str,
+    code: &str,
+    live: Arc<Mutex<CodeExecLive>>,
+    cancel: Arc<AtomicBool>,
+    stdin: mpsc::Receiver<CodeExecStdin>,
+) -> Result<(), String> {
+    run_on_host_stream(workspace, run_id, "py", code, live, cancel, stdin)
+}
+
+pub(crate) fn run_bash_on_host_stream(
+    workspace: &WorkspaceConfig,
+    run_id: &str,
+    code: &str,
+    live: Arc<Mutex<CodeExecLive>>,
+    cancel: Arc<AtomicBool>,
+    stdin: mpsc::Receiver<CodeExecStdin>,
+) -> Result<(), String> {
+    run_on_host_stream(workspace, run_id, "sh", code, live, cancel, stdin)
+}
+
+fn run_on_host_stream(
+    workspace: &WorkspaceConfig,
+    run_id: &str,
+    ext: &str,
+    code: &str,
+    live: Arc<Mutex<CodeExecLive>>,
+    cancel: Arc<AtomicBool>,
+    stdin: mpsc::Receiver<CodeExecStdin>,
+) -> Result<(), String> {
+    let finished = Arc::new(AtomicBool::new(false));
+    let script = write_host_script(run_id, ext, code)?;
+    let script_arg = script.to_string_lossy().to_string();
+    let mut cmd = if ext == "py" {
+        host_command("python3", &["-u", &script_arg], Some(workspace))
+    } else {
+        host_command("bash", &[&script_arg], Some(workspace))
+    };
+    cmd.stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let mut child = cmd.spawn().map_err(|e| format!("本机执行失败：{e}"))?;
+    let (stdout, stderr) = take_child_pipes(&mut child)?;
+    spawn_stdin_writer(child.stdin.take(), stdin, &finished);
+    let t_out = spawn_stream_reader(stdout, Arc::clone(&live), OutputTarget::Stdout);
+    let t_err = spawn_stream_reader(stderr, Arc::clone(&live), OutputTarget::Stderr);
+    let killer = spawn_host_cancel_watcher(child.id(), &live, &cancel, &finished);
+    let status = child.wait().map_err(|e| format!("本机执行失败：{e}"))?;
+    finalize_exec(status.code(), &live, &finished, t_out, t_err, killer);
+    let _ = std::fs::remove_file(&script);
+    Ok(())
+}
+
+fn write_host_script(run_id: &str, ext: &str, code: &str) -> Result<std::path::PathBuf, String> {
+    prepare_scratch_dirs()?;
+    let path = run_dir().join(format!("{run_id}.{ext}"));
+    let mut payload = code.to_string();
+    if !payload.ends_with('\n') {
+        payload.push('\n');
+    }
+    std::fs::write(&path, payload).map_err(|e| format!("写入脚本失败：{e}"))?;
+    Ok(path)
+}
+
+fn spawn_host_cancel_watcher(
+    pid: u32,
+    live: &Arc<Mutex<CodeExecLive>>,
+    cancel: &Arc<AtomicBool>,
+    finished: &Arc<AtomicBool>,
+) -> std::thread::JoinHandle<()> {
+    let live_kill = Arc::clone(live);
+    let cancel_kill = Arc::clone(cancel);
+    let finished_kill = Arc::clone(finished);
+    std::thread::spawn(move || {
+        while !cancel_kill.load(std::sync::atomic::Ordering::Relaxed)
+            && !finished_kill.load(std::sync::atomic::Ordering::Relaxed)
+        {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+        if cancel_kill.load(std::sync::atomic::Ordering::Relaxed) {
+            kill_process_group(pid);
+            mark_cancelled(&live_kill);
+        }
+    })
+}
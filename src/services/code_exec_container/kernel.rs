@@ -0,0 +1,435 @@
+use crate::ui::state::{CodeExecLive, CodeExecStdin};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use super::files::{script_path, write_script_file};
+
+const DONE_MARKER: &str = "\u{1e}DEEPCHAT_KERNEL_DONE:";
+const INTERRUPT_GRACE: Duration = Duration::from_secs(5);
+
+/// 内核的 stdin 只传带长度前缀的帧：`C` 为待执行的代码，`I` 为转发给代码的输入，`E` 关闭输入。
+/// 输入写进每个代码块独立的管道，代码没读完的行随该代码块结束丢弃，不会打乱帧。
+const KERNEL_SCRIPT: &str = r#"import os
+import queue
+import signal
+import sys
+import threading
+import traceback
+
+_MARK = "\x1eDEEPCHAT_KERNEL_DONE:"
+_ns = {"__name__": "__main__"}
+_cells = queue.Queue()
+_input = {"fd": None}
+_lock = threading.Lock()
+signal.signal(signal.SIGINT, signal.default_int_handler)
+
+
+def _read_frames(src):
+    while True:
+        header = src.readline()
+        if not header:
+            break
+        kind, _, size = header.strip().partition(b" ")
+        try:
+            payload = src.read(int(size or b"0"))
+        except ValueError:
+            continue
+        if kind == b"C":
+            _cells.put((payload.decode("utf-8", "replace"), _open_cell_input()))
+        elif kind in (b"I", b"E"):
+            _feed_input(payload, kind == b"E")
+    _cells.put(None)
+
+
+def _open_cell_input():
+    read_fd, write_fd = os.pipe()
+    os.set_blocking(write_fd, False)
+    with _lock:
+        if _input["fd"] is not None:
+            os.close(_input["fd"])
+        _input["fd"] = write_fd
+    return read_fd
+
+
+def _feed_input(data, eof):
+    with _lock:
+        fd = _input["fd"]
+        if fd is None:
+            return
+        try:
+            if data:
+                os.write(fd, data)
+        except OSError:
+            pass
+        if eof:
+            os.close(fd)
+            _input["fd"] = None
+
+
+threading.Thread(target=_read_frames, args=(sys.stdin.buffer,), daemon=True).start()
+
+while True:
+    try:
+        cell = _cells.get()
+    except KeyboardInterrupt:
+        continue
+    if cell is None:
+        break
+    src, read_fd = cell
+    sys.stdin = open(read_fd, "r", encoding="utf-8", errors="replace")
+    status = 0
+    try:
+        exec(compile(src, "<cell>", "exec"), _ns)
+    except KeyboardInterrupt:
+        status = 130
+        sys.stderr.write("KeyboardInterrupt\n")
+    except SystemExit as e:
+        status = e.code if isinstance(e.code, int) else 1
+    except BaseException:
+        status = 1
+        traceback.print_exc()
+    sys.stdin.close()
+    sys.stdout.flush()
+    sys.stderr.flush()
+    sys.stdout.write(_MARK + str(status) + "\n")
+    sys.stdout.flush()
+    sys.stderr.write(_MARK + str(status) + "\n")
+    sys.stderr.flush()
+"#;
+
+struct KernelSession {
+    container_id: String,
+    child: Child,
+    stdin: ChildStdin,
+    rx: mpsc::Receiver<KernelOutput>,
+}
+
+enum KernelOutput {
+    Stdout(String),
+    Stderr(String),
+    StdoutDone(i32),
+    StderrDone,
+    Closed,
+}
+
+enum CellOutcome {
+    Done(i32),
+    Interrupted,
+    Closed,
+}
+
+static KERNELS: OnceLock<Mutex<HashMap<String, KernelSession>>> = OnceLock::new();
+
+fn kernels() -> &'static Mutex<HashMap<String, KernelSession>> {
+    KERNELS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+pub(crate) fn run_python_in_kernel_stream(
+    container_id: &str,
+    kernel_key: &str,
+    code: &str,
+    live: Arc<Mutex<CodeExecLive>>,
+    cancel: Arc<AtomicBool>,
+    stdin: mpsc::Receiver<CodeExecStdin>,
+) -> Result<(), String> {
+    let mut session = take_or_spawn_kernel(container_id, kernel_key)?;
+    if let Err(err) = send_cell(&mut session, code) {
+        shutdown_session(session, kernel_key);
+        return Err(err);
+    }
+    match drain_cell(&mut session, kernel_key, &live, &cancel, &stdin) {
+        CellOutcome::Done(status) => {
+            finish_live(&live, status, None);
+            put_back_kernel(kernel_key, session);
+        }
+        CellOutcome::Interrupted => {
+            finish_live(&live, -1, Some("已停止执行\n"));
+            put_back_kernel(kernel_key, session);
+        }
+        CellOutcome::Closed => {
+            shutdown_session(session, kernel_key);
+            let note = if cancel.load(Ordering::Relaxed) {
+                "已停止执行（Python 内核已重启）\n"
+            } else {
+                "Python 内核已退出，下次执行将自动重启\n"
+            };
+            finish_live(&live, -1, Some(note));
+        }
+    }
+    Ok(())
+}
+
+/// 重启指定对话的 Python 内核；返回是否存在正在运行的内核。
+pub(crate) fn restart_kernel(kernel_key: &str) -> bool {
+    let session = kernels()
+        .lock()
+        .ok()
+        .and_then(|mut map| map.remove(kernel_key));
+    match session {
+        Some(session) => {
+            shutdown_session(session, kernel_key);
+            true
+        }
+        None => {
+            let running = super::cached_container_id();
+            if let Some(id) = running.as_deref() {
+                kill_kernel_process(id, kernel_key);
+            }
+            running.is_some()
+        }
+    }
+}
+
+fn take_or_spawn_kernel(container_id: &str, kernel_key: &str) -> Result<KernelSession, String> {
+    let existing = kernels()
+        .lock()
+        .map_err(|_| "Python 内核启动失败：内核缓存锁异常".to_string())?
+        .remove(kernel_key);
+    if let Some(mut session) = existing {
+        let alive = matches!(session.child.try_wait(), Ok(None));
+        if alive && session.container_id == container_id {
+            return Ok(session);
+        }
+        shutdown_session(session, kernel_key);
+    }
+    spawn_kernel(container_id, kernel_key)
+}
+
+fn put_back_kernel(kernel_key: &str, session: KernelSession) {
+    match kernels().lock() {
+        Ok(mut map) => {
+            map.insert(kernel_key.to_string(), session);
+        }
+        Err(_) => shutdown_session(session, kernel_key),
+    }
+}
+
+fn spawn_kernel(container_id: &str, kernel_key: &str) -> Result<KernelSession, String> {
+    let script_id = kernel_script_id(kernel_key);
+    write_script_file(container_id, &script_id, "py", KERNEL_SCRIPT)?;
+    let mut child = Command::new("docker")
+        .arg("exec")
+        .arg("-i")
+        .arg(container_id)
+        .arg("python")
+        .arg("-u")
+        .arg(script_path(&script_id, "py"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Python 内核启动失败：{e}"))?;
+    let stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| "无法写入 Python 内核 stdin".to_string())?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| "无法读取 stdout".to_string())?;
+    let stderr = child
+        .stderr
+        .take()
+        .ok_or_else(|| "无法读取 stderr".to_string())?;
+    let (tx, rx) = mpsc::channel();
+    spawn_kernel_reader(stdout, tx.clone(), false);
+    spawn_kernel_reader(stderr, tx, true);
+    Ok(KernelSession {
+        container_id: container_id.to_string(),
+        child,
+        stdin,
+        rx,
+    })
+}
+
+fn spawn_kernel_reader(
+    stream: impl Read + Send + 'static,
+    tx: mpsc::Sender<KernelOutput>,
+    is_stderr: bool,
+) {
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(stream);
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            match reader.read_until(b'\n', &mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    let line = String::from_utf8_lossy(&buf).to_string();
+                    if forward_kernel_line(&tx, line, is_stderr).is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+        let _ = tx.send(KernelOutput::Closed);
+    });
+}
+
+fn forward_kernel_line(
+    tx: &mpsc::Sender<KernelOutput>,
+    line: String,
+    is_stderr: bool,
+) -> Result<(), mpsc::SendError<KernelOutput>> {
+    let Some(pos) = line.find(DONE_MARKER) else {
+        return tx.send(output_chunk(line, is_stderr));
+    };
+    let prefix = &line[..pos];
+    if !prefix.is_empty() {
+        tx.send(output_chunk(prefix.to_string(), is_stderr))?;
+    }
+    let status = line[pos + DONE_MARKER.len()..]
+        .trim()
+        .parse::<i32>()
+        .unwrap_or(1);
+    if is_stderr {
+        tx.send(KernelOutput::StderrDone)
+    } else {
+        tx.send(KernelOutput::StdoutDone(status))
+    }
+}
+
+fn output_chunk(text: String, is_stderr: bool) -> KernelOutput {
+    if is_stderr {
+        KernelOutput::Stderr(text)
+    } else {
+        KernelOutput::Stdout(text)
+    }
+}
+
+fn send_cell(session: &mut KernelSession, code: &str) -> Result<(), String> {
+    write_frame(&mut session.stdin, b'C', code.as_bytes())
+        .map_err(|e| format!("写入 Python 内核失败：{e}"))
+}
+
+fn write_frame(stdin: &mut ChildStdin, kind: u8, payload: &[u8]) -> std::io::Result<()> {
+    let header = format!("{} {}\n", kind as char, payload.len());
+    stdin.write_all(header.as_bytes())?;
+    stdin.write_all(payload)?;
+    stdin.flush()
+}
+
+fn drain_cell(
+    session: &mut KernelSession,
+    kernel_key: &str,
+    live: &Arc<Mutex<CodeExecLive>>,
+    cancel: &Arc<AtomicBool>,
+    stdin: &mpsc::Receiver<CodeExecStdin>,
+) -> CellOutcome {
+    let mut stdout_status = None;
+    let mut stderr_done = false;
+    let mut interrupted_at: Option<Instant> = None;
+    loop {
+        if let (Some(status), true) = (stdout_status, stderr_done) {
+            if interrupted_at.is_some() {
+                return CellOutcome::Interrupted;
+            }
+            return CellOutcome::Done(status);
+        }
+        match session.rx.recv_timeout(Duration::from_millis(50)) {
+            Ok(KernelOutput::Stdout(text)) => append_live(live, &text, false),
+            Ok(KernelOutput::Stderr(text)) => append_live(live, &text, true),
+            Ok(KernelOutput::StdoutDone(status)) => stdout_status = Some(status),
+            Ok(KernelOutput::StderrDone) => stderr_done = true,
+            Ok(KernelOutput::Closed) | Err(mpsc::RecvTimeoutError::Disconnected) => {
+                return CellOutcome::Closed;
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+        }
+        forward_cell_stdin(session, stdin);
+        if !cancel.load(Ordering::Relaxed) {
+            continue;
+        }
+        match interrupted_at {
+            None => {
+                interrupt_kernel(&session.container_id, kernel_key);
+                interrupted_at = Some(Instant::now());
+            }
+            Some(at) if at.elapsed() > INTERRUPT_GRACE => return CellOutcome::Closed,
+            Some(_) => {}
+        }
+    }
+}
+
+fn forward_cell_stdin(session: &mut KernelSession, stdin: &mpsc::Receiver<CodeExecStdin>) {
+    while let Ok(msg) = stdin.try_recv() {
+        let _ = match msg {
+            CodeExecStdin::Line(line) => {
+                write_frame(&mut session.stdin, b'I', format!("{line}\n").as_bytes())
+            }
+            CodeExecStdin::Eof => write_frame(&mut session.stdin, b'E', &[]),
+        };
+    }
+}
+
+fn append_live(live: &Arc<Mutex<CodeExecLive>>, text: &str, is_stderr: bool) {
+    if let Ok(mut live) = live.lock() {
+        if is_stderr {
+            live.stderr.push_str(text);
+        } else {
+            live.stdout.push_str(text);
+        }
+    }
+}
+
+fn finish_live(live: &Arc<Mutex<CodeExecLive>>, status: i32, note: Option<&str>) {
+    if let Ok(mut live) = live.lock()
+        && !live.done
+    {
+        if let Some(note) = note {
+            live.stderr.push_str(note);
+        }
+        live.exit_code = Some(status);
+        live.done = true;
+        live.finished_at = Some(Instant::now());
+    }
+}
+
+fn interrupt_kernel(container_id: &str, kernel_key: &str) {
+    let _ = Command::new("docker")
+        .arg("exec")
+        .arg(container_id)
+        .arg("sh")
+        .arg("-lc")
+        .arg(format!(
+            "pkill -INT -f {}",
+            script_path(&kernel_script_id(kernel_key), "py")
+        ))
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+fn kill_kernel_process(container_id: &str, kernel_key: &str) {
+    let _ = Command::new("docker")
+        .arg("exec")
+        .arg(container_id)
+        .arg("sh")
+        .arg("-lc")
+        .arg(format!(
+            "pkill -KILL -f {}",
+            script_path(&kernel_script_id(kernel_key), "py")
+        ))
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+fn shutdown_session(mut session: KernelSession, kernel_key: &str) {
+    kill_kernel_process(&session.container_id, kernel_key);
+    let _ = session.child.kill();
+    let _ = session.child.wait();
+}
+
+fn kernel_script_id(kernel_key: &str) -> String {
+    let key: String = kernel_key
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    format!("kernel-{key}")
+}
@@ -3,10 +3,14 @@ use std::sync::{Mutex, OnceLock};
 
 mod container_start;
 mod files;
+mod host;
+mod kernel;
 mod stream;
 
 use container_start::{is_container_running, start_container};
 
+pub(crate) use host::{run_bash_on_host_stream, run_python_on_host_stream};
+pub(crate) use kernel::{restart_kernel, run_python_in_kernel_stream};
 pub(crate) use stream::{run_bash_in_container_stream, run_python_in_container_stream};
 
 static CONTAINER_CACHE: OnceLock<Mutex<Option<String>>> = OnceLock::new();
@@ -28,3 +32,8 @@ pub(crate) fn ensure_container_cached(workspace: &WorkspaceConfig) -> Result<Str
     *guard = Some(id.clone());
     Ok(id)
 }
+
+fn cached_container_id() -> Option<String> {
+    let guard = cached_container().lock().ok()?;
+    guard.as_ref().filter(|id| is_container_running(id)).cloned()
+}
@@ -1,7 +1,8 @@
-use crate::ui::state::CodeExecLive;
-use std::io::Read;
+use crate::ui::state::{CodeExecLive, CodeExecStdin};
+use std::io::{Read, Write};
 use std::process::{Command, Stdio};
 use std::sync::atomic::AtomicBool;
+use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
 
 use super::files::{
@@ -14,11 +15,13 @@ pub(crate) fn run_python_in_container_stream(
     code: &str,
     live: Arc<Mutex<CodeExecLive>>,
     cancel: Arc<AtomicBool>,
+    stdin: mpsc::Receiver<CodeExecStdin>,
 ) -> Result<(), String> {
     let finished = Arc::new(AtomicBool::new(false));
     write_code_file(container_id, run_id, code)?;
     let mut child = spawn_python_exec(container_id, run_id)?;
     let (stdout, stderr) = take_child_pipes(&mut child)?;
+    spawn_stdin_writer(child.stdin.take(), stdin, &finished);
     let t_out = spawn_stream_reader(stdout, Arc::clone(&live), OutputTarget::Stdout);
     let t_err = spawn_stream_reader(stderr, Arc::clone(&live), OutputTarget::Stderr);
     let killer = spawn_cancel_watcher(container_id, run_id, &live, &cancel, &finished);
@@ -34,11 +37,13 @@ pub(crate) fn run_bash_in_container_stream(
     code: &str,
     live: Arc<Mutex<CodeExecLive>>,
     cancel: Arc<AtomicBool>,
+    stdin: mpsc::Receiver<CodeExecStdin>,
 ) -> Result<(), String> {
     let finished = Arc::new(AtomicBool::new(false));
     write_script_file(container_id, run_id, "sh", code)?;
     let mut child = spawn_bash_exec(container_id, run_id)?;
     let (stdout, stderr) = take_child_pipes(&mut child)?;
+    spawn_stdin_writer(child.stdin.take(), stdin, &finished);
     let t_out = spawn_stream_reader(stdout, Arc::clone(&live), OutputTarget::Stdout);
     let t_err = spawn_stream_reader(stderr, Arc::clone(&live), OutputTarget::Stderr);
     let killer = spawn_cancel_watcher(container_id, run_id, &live, &cancel, &finished);
@@ -65,7 +70,7 @@ pub(crate) fn stop_exec(container_id: &str, run_id: &str) -> bool {
     true
 }
 
-enum OutputTarget {
+pub(super) enum OutputTarget {
     Stdout,
     Stderr,
 }
@@ -78,7 +83,7 @@ fn spawn_python_exec(container_id: &str, run_id: &str) -> Result<std::process::C
         .arg("python")
         .arg("-u")
         .arg(code_path(run_id))
-        .stdin(Stdio::null())
+        .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
     cmd.spawn().map_err(|e| format!("Docker 执行失败：{e}"))
@@ -91,13 +96,13 @@ fn spawn_bash_exec(container_id: &str, run_id: &str) -> Result<std::process::Chi
         .arg(container_id)
         .arg("bash")
         .arg(super::files::script_path(run_id, "sh"))
-        .stdin(Stdio::null())
+        .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
     cmd.spawn().map_err(|e| format!("Docker 执行失败：{e}"))
 }
 
-fn take_child_pipes(
+pub(super) fn take_child_pipes(
     child: &mut std::process::Child,
 ) -> Result<(std::process::ChildStdout, std::process::ChildStderr), String> {
     let stdout = child
@@ -111,7 +116,32 @@ fn take_child_pipes(
     Ok((stdout, stderr))
 }
 
-fn spawn_stream_reader(
+pub(super) fn spawn_stdin_writer(
+    pipe: Option<std::process::ChildStdin>,
+    rx: mpsc::Receiver<CodeExecStdin>,
+    finished: &Arc<AtomicBool>,
+) {
+    let Some(mut pipe) = pipe else {
+        return;
+    };
+    let finished = Arc::clone(finished);
+    std::thread::spawn(move || {
+        while !finished.load(std::sync::atomic::Ordering::Relaxed) {
+            match rx.recv_timeout(std::time::Duration::from_millis(50)) {
+                Ok(CodeExecStdin::Line(line)) => {
+                    let payload = format!("{line}\n");
+                    if pipe.write_all(payload.as_bytes()).is_err() || pipe.flush().is_err() {
+                        return;
+                    }
+                }
+                Ok(CodeExecStdin::Eof) | Err(mpsc::RecvTimeoutError::Disconnected) => return,
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+            }
+        }
+    });
+}
+
+pub(super) fn spawn_stream_reader(
     mut stream: impl Read + Send + 'static,
     live: Arc<Mutex<CodeExecLive>>,
     target: OutputTarget,
@@ -163,7 +193,7 @@ fn spawn_cancel_watcher(
     })
 }
 
-fn mark_cancelled(live: &Arc<Mutex<CodeExecLive>>) {
+pub(super) fn mark_cancelled(live: &Arc<Mutex<CodeExecLive>>) {
     if let Ok(mut live) = live.lock() {
         live.stderr.push_str("已停止执行\n");
         live.exit_code = Some(-1);
@@ -172,7 +202,7 @@ fn mark_cancelled(live: &Arc<Mutex<CodeExecLive>>) {
     }
 }
 
-fn finalize_exec(
+pub(super) fn finalize_exec(
     status_code: Option<i32>,
     live: &Arc<Mutex<CodeExecLive>>,
     finished: &Arc<AtomicBool>,
@@ -32,6 +32,44 @@ pub(crate) fn read_only_enabled() -> bool {
     }
 }
 
+pub(crate) fn python_kernel_enabled() -> bool {
+    match std::env::var("DEEPCHAT_CODE_EXEC_KERNEL") {
+        Ok(value) => {
+            let v = value.trim().to_ascii_lowercase();
+            !(v.is_empty() || v == "0" || v == "false" || v == "off" || v == "no")
+        }
+        Err(_) => false,
+    }
+}
+
+pub(crate) fn host_backend_enabled() -> bool {
+    match std::env::var("DEEPCHAT_EXEC_BACKEND") {
+        Ok(value) => value.trim().eq_ignore_ascii_case("host"),
+        Err(_) => false,
+    }
+}
+
+pub(crate) fn host_cpu_seconds() -> u64 {
+    match std::env::var("DEEPCHAT_HOST_CPU_SECONDS") {
+        Ok(value) => value.trim().parse::<u64>().unwrap_or(300),
+        Err(_) => 300,
+    }
+}
+
+pub(crate) fn host_memory_mb() -> u64 {
+    match std::env::var("DEEPCHAT_HOST_MEMORY_MB") {
+        Ok(value) => value.trim().parse::<u64>().unwrap_or(2048),
+        Err(_) => 2048,
+    }
+}
+
+pub(crate) fn host_file_size_mb() -> u64 {
+    match std::env::var("DEEPCHAT_HOST_FILE_SIZE_MB") {
+        Ok(value) => value.trim().parse::<u64>().unwrap_or(512),
+        Err(_) => 512,
+    }
+}
+
 pub(crate) fn work_dir() -> String {
     if read_only_enabled() {
         "/opt/deepchat/work".to_string()
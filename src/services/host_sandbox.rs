@@ -0,0 +1,94 @@
+use crate::services::code_exec_container_env::{
+    host_cpu_seconds, host_file_size_mb, host_memory_mb, pip_cache_dir,
+};
+use crate::services::workspace::WorkspaceConfig;
+use std::os::unix::process::CommandExt;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// 传递给本机子进程的环境变量白名单，其余变量（含 API key 等）一律丢弃。
+const ENV_ALLOWLIST: &[&str] = &[
+    "PATH", "LANG", "LC_ALL", "LC_CTYPE", "TERM", "TZ", "USER", "LOGNAME",
+];
+
+/// 通过 sh 的 ulimit 设置 CPU 时间 / 虚拟内存 / 单文件大小上限后再 exec 目标程序。
+const RLIMIT_WRAPPER: &str = r#"ulimit -t "$1" || exit 125; ulimit -v "$2" 2>/dev/null; ulimit -f "$3" || exit 125; shift 3; exec "$@""#;
+
+pub(crate) fn scratch_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("deepchat-host-{}", std::process::id()))
+}
+
+pub(crate) fn run_dir() -> PathBuf {
+    scratch_dir().join("run")
+}
+
+pub(crate) fn prepare_scratch_dirs() -> Result<(), String> {
+    let scratch = scratch_dir();
+    for dir in [
+        scratch.join("run"),
+        scratch.join("tmp"),
+        scratch.join("site-packages"),
+    ] {
+        std::fs::create_dir_all(&dir).map_err(|e| format!("本机执行目录创建失败：{e}"))?;
+    }
+    Ok(())
+}
+
+pub(crate) fn host_command(
+    program: &str,
+    args: &[&str],
+    workspace: Option<&WorkspaceConfig>,
+) -> Command {
+    let scratch = scratch_dir();
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c")
+        .arg(RLIMIT_WRAPPER)
+        .arg("deepchat-sandbox")
+        .arg(host_cpu_seconds().to_string())
+        .arg((host_memory_mb() * 1024).to_string())
+        .arg((host_file_size_mb() * 2048).to_string())
+        .arg(program)
+        .args(args)
+        .current_dir(&scratch)
+        .process_group(0);
+    apply_env_allowlist(&mut cmd);
+    apply_sandbox_envs(&mut cmd, workspace);
+    cmd
+}
+
+pub(crate) fn kill_process_group(pid: u32) {
+    let _ = Command::new("kill")
+        .arg("-KILL")
+        .arg("--")
+        .arg(format!("-{pid}"))
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+fn apply_env_allowlist(cmd: &mut Command) {
+    cmd.env_clear();
+    for key in ENV_ALLOWLIST {
+        if let Ok(value) = std::env::var(key) {
+            cmd.env(key, value);
+        }
+    }
+}
+
+fn apply_sandbox_envs(cmd: &mut Command, workspace: Option<&WorkspaceConfig>) {
+    let scratch = scratch_dir();
+    let tmp = scratch.join("tmp");
+    let site = scratch.join("site-packages");
+    cmd.env("HOME", &scratch)
+        .env("TMPDIR", &tmp)
+        .env("TMP", &tmp)
+        .env("TEMP", &tmp)
+        .env("DEEPCHAT_WORKDIR", &scratch)
+        .env("PIP_TARGET", &site)
+        .env("PIP_CACHE_DIR", pip_cache_dir())
+        .env("PYTHONPATH", &site)
+        .env("PIP_DISABLE_PIP_VERSION_CHECK", "1");
+    if let Some(workspace) = workspace {
+        cmd.env("DEEPCHAT_WORKSPACE", workspace.mount_source());
+    }
+}
@@ -1,13 +1,26 @@
 pub(crate) mod code_exec_container;
 pub(crate) mod code_exec_container_env;
+pub(crate) mod host_sandbox;
 pub(crate) mod net;
+pub(crate) mod runtime_agent_guard;
+pub(crate) mod runtime_autosave;
 pub(crate) mod runtime_code_exec;
 pub(crate) mod runtime_code_exec_helpers;
 pub(crate) mod runtime_code_exec_output;
+pub(crate) mod runtime_compact;
+pub(crate) mod runtime_compare;
+pub(crate) mod runtime_delegation;
 pub(crate) mod runtime_file_patch;
+pub(crate) mod runtime_prompts;
 pub(crate) mod runtime_question_review;
 pub(crate) mod runtime_requests;
+pub(crate) mod runtime_titles;
+pub(crate) mod runtime_variants;
+pub(crate) mod runtime_tools;
+pub(crate) mod runtime_workspace_changes;
 pub(crate) mod runtime_yolo;
+pub(crate) mod sandbox_image;
 pub(crate) mod tool_service;
 pub(crate) mod tools;
 pub(crate) mod workspace;
+pub(crate) mod workspace_sync;
@@ -0,0 +1,339 @@
+//! LLM 流量录制与回放（cassette）
+//!
+//! `--record-cassette <file>`：每次请求结束后，把请求上下文与完整事件序列（含相对时间）追加为一行 JSON。
+//! `--replay-cassette <file>`：不访问网络，取出录制的请求并按原时间间隔把事件重新发给运行时，
+//! 后续处理（渲染、工具调用、统计）与真实请求走同一条路径。
+
+use crate::args::Args;
+use crate::types::{Message, ROLE_SYSTEM, ToolCall, Usage};
+use crate::ui::events::{LlmEvent, RuntimeEvent, send_llm};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, OnceLock, atomic::AtomicBool};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::request::RequestInput;
+use super::retry::sleep_unless_cancelled;
+
+pub(super) type RunRequest =
+    fn(RequestInput, Vec<&'static str>, Arc<AtomicBool>, Sender<RuntimeEvent>);
+
+#[derive(Serialize, Deserialize)]
+struct CassetteEntry {
+    recorded_at: String,
+    base_url: String,
+    model: String,
+    message_index: usize,
+    tools: Vec<String>,
+    messages: Vec<Message>,
+    events: Vec<RecordedEvent>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RecordedEvent {
+    /// 距请求开始的毫秒数
+    at_ms: u64,
+    #[serde(flatten)]
+    event: CassetteEvent,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum CassetteEvent {
+    Chunk {
+        text: String,
+    },
+    ReasoningChunk {
+        text: String,
+    },
+    Error {
+        message: String,
+    },
+    Done {
+        usage: Option<Usage>,
+    },
+    ToolCalls {
+        calls: Vec<ToolCall>,
+        usage: Option<Usage>,
+    },
+    Retrying {
+        attempt: u32,
+        max_attempts: u32,
+        delay_ms: u64,
+    },
+    Stalled {
+        reason: String,
+        will_retry: bool,
+    },
+}
+
+impl From<&LlmEvent> for CassetteEvent {
+    fn from(event: &LlmEvent) -> Self {
+        match event {
+            LlmEvent::Chunk(text) => Self::Chunk { text: text.clone() },
+            LlmEvent::ReasoningChunk(text) => Self::ReasoningChunk { text: text.clone() },
+            LlmEvent::Error(message) => Self::Error {
+                message: message.clone(),
+            },
+            LlmEvent::Done { usage } => Self::Done {
+                usage: usage.clone(),
+            },
+            LlmEvent::ToolCalls { calls, usage } => Self::ToolCalls {
+                calls: calls.clone(),
+                usage: usage.clone(),
+            },
+            LlmEvent::Retrying {
+                attempt,
+                max_attempts,
+                delay_ms,
+            } => Self::Retrying {
+                attempt: *attempt,
+                max_attempts: *max_attempts,
+                delay_ms: *delay_ms,
+            },
+            LlmEvent::Stalled { reason, will_retry } => Self::Stalled {
+                reason: reason.clone(),
+                will_retry: *will_retry,
+            },
+        }
+    }
+}
+
+impl From<CassetteEvent> for LlmEvent {
+    fn from(event: CassetteEvent) -> Self {
+        match event {
+            CassetteEvent::Chunk { text } => Self::Chunk(text),
+            CassetteEvent::ReasoningChunk { text } => Self::ReasoningChunk(text),
+            CassetteEvent::Error { message } => Self::Error(message),
+            CassetteEvent::Done { usage } => Self::Done { usage },
+            CassetteEvent::ToolCalls { calls, usage } => Self::ToolCalls { calls, usage },
+            CassetteEvent::Retrying {
+                attempt,
+                max_attempts,
+                delay_ms,
+            } => Self::Retrying {
+                attempt,
+                max_attempts,
+                delay_ms,
+            },
+            CassetteEvent::Stalled { reason, will_retry } => Self::Stalled { reason, will_retry },
+        }
+    }
+}
+
+struct Recorder {
+    path: PathBuf,
+    /// 多个标签页并发请求时串行追加，避免行交错
+    lock: Mutex<()>,
+}
+
+enum CassetteMode {
+    Record(Recorder),
+    Replay(Mutex<Vec<CassetteEntry>>),
+}
+
+static CASSETTE: OnceLock<Option<CassetteMode>> = OnceLock::new();
+
+pub(crate) fn init_cassette(args: &Args) -> Result<(), String> {
+    let mode = if let Some(path) = args.record_cassette.as_deref() {
+        Some(CassetteMode::Record(Recorder {
+            path: PathBuf::from(path),
+            lock: Mutex::new(()),
+        }))
+    } else if let Some(path) = args.replay_cassette.as_deref() {
+        let entries = load_cassette(Path::new(path))?;
+        Some(CassetteMode::Replay(Mutex::new(entries)))
+    } else {
+        None
+    };
+    let _ = CASSETTE.set(mode);
+    Ok(())
+}
+
+fn load_cassette(path: &Path) -> Result<Vec<CassetteEntry>, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("读取 cassette 失败：{}：{e}", path.display()))?;
+    let mut entries = Vec::new();
+    for (idx, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = serde_json::from_str(line)
+            .map_err(|e| format!("cassette 第 {} 行解析失败：{e}", idx + 1))?;
+        entries.push(entry);
+    }
+    if entries.is_empty() {
+        return Err(format!("cassette 中没有录制的请求：{}", path.display()));
+    }
+    Ok(entries)
+}
+
+/// 按 cassette 模式执行一次请求：回放时不访问网络，录制时转发并记录事件，否则直接请求。
+pub(super) fn run_with_cassette(
+    input: RequestInput,
+    enabled: Vec<&'static str>,
+    cancel: Arc<AtomicBool>,
+    tx: Sender<RuntimeEvent>,
+    run: RunRequest,
+) {
+    match CASSETTE.get().and_then(Option::as_ref) {
+        Some(CassetteMode::Replay(entries)) => replay(&input, entries, &cancel, &tx),
+        Some(CassetteMode::Record(recorder)) => recorder.record(input, enabled, cancel, tx, run),
+        None => run(input, enabled, cancel, tx),
+    }
+}
+
+impl Recorder {
+    fn record(
+        &self,
+        input: RequestInput,
+        enabled: Vec<&'static str>,
+        cancel: Arc<AtomicBool>,
+        tx: Sender<RuntimeEvent>,
+        run: RunRequest,
+    ) {
+        let mut entry = CassetteEntry {
+            recorded_at: chrono::Local::now().to_rfc3339(),
+            base_url: input.base_url.clone(),
+            model: input.model.clone(),
+            message_index: input.message_index,
+            tools: enabled.iter().map(|name| name.to_string()).collect(),
+            messages: input.messages.clone(),
+            events: Vec::new(),
+        };
+        let (proxy_tx, proxy_rx) = mpsc::channel();
+        let forward = thread::spawn(move || forward_events(proxy_rx, tx));
+        // 请求结束后 proxy_tx 被丢弃，转发线程随之退出
+        run(input, enabled, cancel, proxy_tx);
+        entry.events = forward.join().unwrap_or_default();
+        let _ = self.append(&entry);
+    }
+
+    fn append(&self, entry: &CassetteEntry) -> Result<(), String> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|_| "cassette 写入锁已损坏".to_string())?;
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).map_err(|e| format!("创建 cassette 目录失败：{e}"))?;
+        }
+        let line =
+            serde_json::to_string(entry).map_err(|e| format!("序列化 cassette 失败：{e}"))?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| format!("打开 cassette 失败：{e}"))?;
+        writeln!(file, "{line}").map_err(|e| format!("写入 cassette 失败：{e}"))
+    }
+}
+
+fn forward_events(rx: Receiver<RuntimeEvent>, tx: Sender<RuntimeEvent>) -> Vec<RecordedEvent> {
+    let started = Instant::now();
+    let mut events = Vec::new();
+    for event in rx {
+        if let RuntimeEvent::Llm(ui) = &event {
+            events.push(RecordedEvent {
+                at_ms: started.elapsed().as_millis() as u64,
+                event: CassetteEvent::from(&ui.event),
+            });
+        }
+        let _ = tx.send(event);
+    }
+    events
+}
+
+fn replay(
+    input: &RequestInput,
+    entries: &Mutex<Vec<CassetteEntry>>,
+    cancel: &Arc<AtomicBool>,
+    tx: &Sender<RuntimeEvent>,
+) {
+    let entry = match entries.lock() {
+        Ok(mut entries) => take_entry(&mut entries, &input.messages),
+        Err(_) => Err("cassette 回放锁已损坏".to_string()),
+    };
+    let entry = match entry {
+        Ok(entry) => entry,
+        Err(err) => {
+            send_llm(
+                tx,
+                input.tab.clone(),
+                input.request_id,
+                LlmEvent::Error(err),
+            );
+            return;
+        }
+    };
+    let started = Instant::now();
+    for recorded in entry.events {
+        let due = Duration::from_millis(recorded.at_ms).saturating_sub(started.elapsed());
+        if !sleep_unless_cancelled(due, cancel) {
+            return;
+        }
+        send_llm(
+            tx,
+            input.tab.clone(),
+            input.request_id,
+            recorded.event.into(),
+        );
+    }
+}
+
+/// 取与本次请求上下文一致的录制（多个标签页并发时到达顺序可能不同）；
+/// 没有匹配时报错，不拿其他请求的录制顶替。
+fn take_entry(
+    entries: &mut Vec<CassetteEntry>,
+    messages: &[Message],
+) -> Result<CassetteEntry, String> {
+    if entries.is_empty() {
+        return Err("cassette 中录制的请求已全部回放完毕".to_string());
+    }
+    match entries
+        .iter()
+        .position(|entry| same_messages(&entry.messages, messages))
+    {
+        Some(idx) => Ok(entries.remove(idx)),
+        None => Err(format!(
+            "cassette 中没有与本次请求匹配的录制（{}，剩余 {} 条录制）",
+            describe_request(messages),
+            entries.len()
+        )),
+    }
+}
+
+/// 用消息数与最后一条消息的开头描述请求，便于对照 cassette 查找差异。
+fn describe_request(messages: &[Message]) -> String {
+    let Some(last) = messages.last() else {
+        return "空请求".to_string();
+    };
+    let mut preview: String = last.content.chars().take(40).collect();
+    if last.content.chars().count() > 40 {
+        preview.push('…');
+    }
+    format!(
+        "共 {} 条消息，最后一条 {}：{}",
+        messages.len(),
+        last.role,
+        preview.replace('\n', " ")
+    )
+}
+
+/// 系统提示词在发送时才渲染，可能带日期、工作区等随环境变化的内容，
+/// 因此只比较对话历史，不比较系统消息。
+fn same_messages(a: &[Message], b: &[Message]) -> bool {
+    let a: Vec<_> = history(a).collect();
+    let b: Vec<_> = history(b).collect();
+    a.len() == b.len()
+        && a.iter()
+            .zip(&b)
+            .all(|(x, y)| x.role == y.role && x.content == y.content)
+}
+
+fn history(messages: &[Message]) -> impl Iterator<Item = &Message> {
+    messages.iter().filter(|m| m.role != ROLE_SYSTEM)
+}
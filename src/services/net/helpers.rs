@@ -92,16 +92,30 @@ pub(super) fn convert_tool_call(
     }
 }
 
-pub(super) fn map_usage(usage: rig::completion::Usage) -> Usage {
+/// rig 的 `Usage` 不含缓存命中数，从服务商的原始响应里读取。
+pub(super) fn map_usage(usage: rig::completion::Usage, raw: &impl serde::Serialize) -> Usage {
     Usage {
         prompt_tokens: Some(usage.input_tokens),
         completion_tokens: Some(usage.output_tokens),
         total_tokens: Some(usage.total_tokens),
+        cached_tokens: cached_tokens(raw),
     }
 }
 
-pub(super) fn usage_from_stream<T: GetTokenUsage>(res: &T) -> Option<Usage> {
-    res.token_usage().map(map_usage)
+pub(super) fn usage_from_stream<T: GetTokenUsage + serde::Serialize>(res: &T) -> Option<Usage> {
+    res.token_usage().map(|usage| map_usage(usage, res))
+}
+
+/// DeepSeek 用 `prompt_cache_hit_tokens`，OpenAI 兼容接口用 `prompt_tokens_details.cached_tokens`，
+/// Anthropic 用 `cache_read_input_tokens`；rig 的响应类型没有保留这些字段时返回 None。
+fn cached_tokens(raw: &impl serde::Serialize) -> Option<u64> {
+    let value = serde_json::to_value(raw).ok()?;
+    let usage = value.get("usage").unwrap_or(&value);
+    usage
+        .get("prompt_cache_hit_tokens")
+        .or_else(|| usage.pointer("/prompt_tokens_details/cached_tokens"))
+        .or_else(|| usage.get("cache_read_input_tokens"))
+        .and_then(serde_json::Value::as_u64)
 }
 
 pub(super) fn log_tool_call(
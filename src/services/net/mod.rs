@@ -1,8 +1,12 @@
+mod cassette;
 mod helpers;
 mod net_logging;
 mod request;
+mod retry;
+mod stall;
 mod stream;
 mod types;
 
+pub(crate) use cassette::init_cassette;
 pub(crate) use request::request_llm_stream;
 pub(crate) use types::LlmStreamRequestParams;
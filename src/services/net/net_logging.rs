@@ -1,3 +1,4 @@
+use crate::llm::context_budget::ContextReport;
 use crate::llm::rig::RigRequestContext;
 use crate::ui::events::{LlmEvent, RuntimeEvent, send_llm};
 use std::fs;
@@ -16,6 +17,7 @@ pub(super) fn build_enabled_tools(
     enable_read_code: bool,
     enable_modify_file: bool,
     enable_ask_questions: bool,
+    enable_delegate_task: bool,
 ) -> Vec<&'static str> {
     let mut out = Vec::new();
     if enable_web_search {
@@ -38,6 +40,9 @@ pub(super) fn build_enabled_tools(
     if enable_ask_questions {
         out.push("ask_questions");
     }
+    if enable_delegate_task {
+        out.push("delegate_task");
+    }
     out
 }
 
@@ -63,17 +68,30 @@ fn format_request_log(base_url: &str, model: &str, ctx: &RigRequestContext) -> S
     out.push_str("model: ");
     out.push_str(model);
     out.push('\n');
+    out.push_str(&format_context_log(&ctx.context));
     out.push_str("--- preamble ---\n");
     out.push_str(&ctx.preamble);
     out.push('\n');
     out.push_str("--- history ---\n");
     append_history_log(&mut out, &ctx.history);
     out.push_str("--- prompt ---\n");
-    out.push_str(&ctx.prompt);
+    out.push_str(&message_log_entry(&ctx.prompt).1);
     out.push('\n');
     out
 }
 
+/// 记录预算估算与裁剪结果，便于核对实际发送的历史为何比对话短。
+fn format_context_log(report: &ContextReport) -> String {
+    let window = report
+        .window
+        .map(|w| w.to_string())
+        .unwrap_or_else(|| "未知".to_string());
+    format!(
+        "context: estimated_tokens={} window={window} dropped_messages={} elided_tool_outputs={}\n",
+        report.estimated_tokens, report.dropped_messages, report.elided_tool_outputs
+    )
+}
+
 fn append_history_log(out: &mut String, history: &[rig::completion::Message]) {
     for msg in history {
         let (role, content) = message_log_entry(msg);
@@ -104,6 +122,7 @@ fn user_content_text(content: &rig::OneOrMany<rig::completion::message::UserCont
             rig::completion::message::UserContent::ToolResult(result) => {
                 parts.push(tool_result_text(result));
             }
+            rig::completion::message::UserContent::Image(_) => parts.push("[图片]".to_string()),
             _ => parts.push("[非文本内容]".to_string()),
         }
     }
@@ -1,11 +1,21 @@
+use crate::llm::reasoning::ReasoningSettings;
+use crate::llm::timeouts::StreamTimeouts;
 use crate::types::Message;
 use crate::ui::events::{LlmEvent, RuntimeEvent, send_llm};
 use std::sync::mpsc::Sender;
-use std::sync::{Arc, atomic::AtomicBool};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
 use tokio::runtime::Runtime;
 
+use super::cassette::run_with_cassette;
 use super::helpers::handle_request_error;
 use super::net_logging::build_enabled_tools;
+use super::retry::{
+    ErrorClass, MAX_ATTEMPTS, backoff_delay, classify_error, notify_retry, sleep_unless_cancelled,
+};
+use super::stall::{is_stall_error, notify_stalled};
 use super::stream::stream_request;
 use super::types::LlmStreamRequestParams;
 
@@ -15,9 +25,12 @@ pub(crate) fn request_llm_stream(params: LlmStreamRequestParams) {
         api_key: params.api_key.clone(),
         model: params.model.clone(),
         max_tokens: params.max_tokens,
+        context_window: params.context_window,
         messages: params.messages.clone(),
         prompts_dir: params.prompts_dir.clone(),
-        show_reasoning: params.show_reasoning,
+        reasoning: params.reasoning,
+        timeouts: params.timeouts,
+        temperature: params.temperature,
         log_dir: params.log_dir.clone(),
         log_session_id: params.log_session_id.clone(),
         message_index: params.message_index,
@@ -31,8 +44,15 @@ pub(crate) fn request_llm_stream(params: LlmStreamRequestParams) {
         params.enable_read_code,
         params.enable_modify_file,
         params.enable_ask_questions,
+        params.enable_delegate_task,
+    );
+    run_with_cassette(
+        input,
+        enabled,
+        params.cancel,
+        params.tx,
+        run_llm_stream_with_input,
     );
-    run_llm_stream_with_input(input, enabled, params.cancel, params.tx);
 }
 
 fn run_llm_stream_with_input(
@@ -44,9 +64,36 @@ fn run_llm_stream_with_input(
     let Some(rt) = init_runtime(&tx, &input.tab, input.request_id) else {
         return;
     };
-    let result = rt.block_on(stream_request(&input, &enabled, &cancel, &tx));
-    if let Err(err) = result {
-        handle_request_error(&err, &input, &tx);
+    let mut attempt = 1;
+    loop {
+        let Err(err) = rt.block_on(stream_request(&input, &enabled, &cancel, &tx)) else {
+            return;
+        };
+        if cancel.load(Ordering::Relaxed) {
+            return;
+        }
+        if is_stall_error(&err) {
+            let will_retry = input.timeouts.retry_on_stall && attempt < MAX_ATTEMPTS;
+            notify_stalled(&input, &tx, &err, will_retry);
+            if !will_retry {
+                return;
+            }
+        }
+        let ErrorClass::Transient { retry_after } = classify_error(&err) else {
+            handle_request_error(&err, &input, &tx);
+            return;
+        };
+        if attempt >= MAX_ATTEMPTS {
+            let err = format!("{err}（已重试 {} 次）", attempt - 1);
+            handle_request_error(&err, &input, &tx);
+            return;
+        }
+        let delay = backoff_delay(attempt, retry_after);
+        attempt += 1;
+        notify_retry(&input, &tx, attempt, delay, &err);
+        if !sleep_unless_cancelled(delay, &cancel) {
+            return;
+        }
     }
 }
 
@@ -55,9 +102,12 @@ pub(super) struct RequestInput {
     pub(super) api_key: String,
     pub(super) model: String,
     pub(super) max_tokens: Option<u64>,
+    pub(super) context_window: Option<u64>,
     pub(super) messages: Vec<Message>,
     pub(super) prompts_dir: String,
-    pub(super) show_reasoning: bool,
+    pub(super) reasoning: ReasoningSettings,
+    pub(super) timeouts: StreamTimeouts,
+    pub(super) temperature: Option<f64>,
     pub(super) log_dir: Option<String>,
     pub(super) log_session_id: String,
     pub(super) message_index: usize,
@@ -70,9 +120,12 @@ struct RequestConfig {
     api_key: String,
     model: String,
     max_tokens: Option<u64>,
+    context_window: Option<u64>,
     messages: Vec<Message>,
     prompts_dir: String,
-    show_reasoning: bool,
+    reasoning: ReasoningSettings,
+    timeouts: StreamTimeouts,
+    temperature: Option<f64>,
     log_dir: Option<String>,
     log_session_id: String,
     message_index: usize,
@@ -87,9 +140,12 @@ impl RequestInput {
             api_key: config.api_key,
             model: config.model,
             max_tokens: config.max_tokens,
+            context_window: config.context_window,
             messages: config.messages,
             prompts_dir: config.prompts_dir,
-            show_reasoning: config.show_reasoning,
+            reasoning: config.reasoning,
+            timeouts: config.timeouts,
+            temperature: config.temperature,
             log_dir: config.log_dir,
             log_session_id: config.log_session_id,
             message_index: config.message_index,
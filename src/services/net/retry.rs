@@ -0,0 +1,177 @@
+use crate::ui::events::{LlmEvent, RuntimeEvent, send_llm};
+use std::sync::mpsc::Sender;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+use std::time::{Duration, Instant};
+
+use super::net_logging::write_response_log;
+use super::request::RequestInput;
+use super::stall::is_stall_error;
+
+/// 单次请求的最大尝试次数（含首次请求）。
+pub(super) const MAX_ATTEMPTS: u32 = 5;
+const BASE_DELAY_MS: u64 = 1_000;
+const MAX_DELAY_MS: u64 = 30_000;
+const MAX_RETRY_AFTER_MS: u64 = 120_000;
+const SLEEP_SLICE: Duration = Duration::from_millis(100);
+
+const TRANSIENT_STATUS: &[u16] = &[
+    408, 409, 425, 429, 500, 502, 503, 504, 520, 521, 522, 523, 524, 529,
+];
+const FATAL_STATUS: &[u16] = &[400, 401, 402, 403, 404, 405, 413, 415, 422];
+
+const TRANSIENT_KEYWORDS: &[&str] = &[
+    "timed out",
+    "timeout",
+    "connection",
+    "connect error",
+    "error sending request",
+    "error decoding response body",
+    "broken pipe",
+    "unexpected eof",
+    "reset by peer",
+    "dns error",
+    "temporarily",
+    "overloaded",
+    "rate limit",
+    "too many requests",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ErrorClass {
+    Transient { retry_after: Option<Duration> },
+    Fatal,
+}
+
+/// 根据错误文本判断是否值得重试：429/5xx/网络/超时/响应停滞可重试，鉴权与参数错误直接失败。
+pub(super) fn classify_error(err: &str) -> ErrorClass {
+    if is_stall_error(err) {
+        return ErrorClass::Transient { retry_after: None };
+    }
+    let lower = err.to_ascii_lowercase();
+    let transient = match status_code(&lower) {
+        Some(code) => TRANSIENT_STATUS.contains(&code),
+        None => TRANSIENT_KEYWORDS.iter().any(|kw| lower.contains(kw)),
+    };
+    if !transient {
+        return ErrorClass::Fatal;
+    }
+    ErrorClass::Transient {
+        retry_after: retry_after_hint(&lower),
+    }
+}
+
+pub(super) fn is_transient(err: &str) -> bool {
+    matches!(classify_error(err), ErrorClass::Transient { .. })
+}
+
+/// 指数退避（1s、2s、4s…，上限 30s）加少量抖动；服务端给出 Retry-After 时以其为准。
+pub(super) fn backoff_delay(failed_attempts: u32, retry_after: Option<Duration>) -> Duration {
+    if let Some(delay) = retry_after {
+        return delay.min(Duration::from_millis(MAX_RETRY_AFTER_MS));
+    }
+    let exp = failed_attempts.saturating_sub(1).min(16);
+    let base = BASE_DELAY_MS.saturating_mul(1u64 << exp).min(MAX_DELAY_MS);
+    let jitter = rand::random::<u64>() % (base / 5 + 1);
+    Duration::from_millis(base + jitter)
+}
+
+pub(super) fn notify_retry(
+    input: &RequestInput,
+    tx: &Sender<RuntimeEvent>,
+    next_attempt: u32,
+    delay: Duration,
+    err: &str,
+) {
+    if let Some(dir) = input.log_dir.as_deref() {
+        let payload = format!(
+            "retry: attempt {next_attempt}/{MAX_ATTEMPTS} in {}ms\nerror: {err}",
+            delay.as_millis()
+        );
+        let _ = write_response_log(
+            dir,
+            &input.log_session_id,
+            &input.tab,
+            input.message_index,
+            &payload,
+        );
+    }
+    send_llm(
+        tx,
+        input.tab.clone(),
+        input.request_id,
+        LlmEvent::Retrying {
+            attempt: next_attempt,
+            max_attempts: MAX_ATTEMPTS,
+            delay_ms: delay.as_millis() as u64,
+        },
+    );
+}
+
+/// 分片休眠以便及时响应取消；返回 false 表示等待期间请求已被取消。
+pub(super) fn sleep_unless_cancelled(delay: Duration, cancel: &Arc<AtomicBool>) -> bool {
+    let deadline = Instant::now() + delay;
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return false;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return true;
+        }
+        std::thread::sleep(SLEEP_SLICE.min(deadline - now));
+    }
+}
+
+fn status_code(text: &str) -> Option<u16> {
+    let bytes = text.as_bytes();
+    for start in 0..bytes.len().saturating_sub(2) {
+        let end = start + 3;
+        let before_ok = start == 0 || !bytes[start - 1].is_ascii_digit();
+        let after_ok = end == bytes.len() || !bytes[end].is_ascii_digit();
+        if !before_ok || !after_ok || !bytes[start..end].iter().all(u8::is_ascii_digit) {
+            continue;
+        }
+        let Ok(code) = text[start..end].parse::<u16>() else {
+            continue;
+        };
+        if TRANSIENT_STATUS.contains(&code) || FATAL_STATUS.contains(&code) {
+            return Some(code);
+        }
+    }
+    None
+}
+
+/// 从错误文本中提取服务端的重试提示（Retry-After 头、retry_after 字段或 "try again in Ns"）。
+fn retry_after_hint(lower: &str) -> Option<Duration> {
+    const MARKERS: &[&str] = &["retry-after", "retry_after", "retry after", "try again in"];
+    for marker in MARKERS {
+        let Some(pos) = lower.find(marker) else {
+            continue;
+        };
+        let rest = &lower[pos + marker.len()..];
+        if let Some(secs) = leading_number(rest) {
+            return Some(Duration::from_millis((secs * 1000.0) as u64));
+        }
+    }
+    None
+}
+
+fn leading_number(text: &str) -> Option<f64> {
+    let trimmed =
+        text.trim_start_matches(|c: char| c == ':' || c == '"' || c == '=' || c.is_whitespace());
+    let end = trimmed
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(trimmed.len());
+    let value = trimmed[..end].parse::<f64>().ok()?;
+    if !value.is_finite() || value < 0.0 {
+        return None;
+    }
+    let unit = trimmed[end..].trim_start();
+    if unit.starts_with("ms") {
+        return Some(value / 1000.0);
+    }
+    Some(value)
+}
@@ -0,0 +1,72 @@
+use crate::ui::events::{LlmEvent, RuntimeEvent, send_llm};
+use std::future::Future;
+use std::sync::mpsc::Sender;
+use std::time::Duration;
+
+use super::net_logging::write_response_log;
+use super::request::RequestInput;
+
+const STALL_PREFIX: &str = "响应停滞：";
+
+#[derive(Clone, Copy)]
+pub(super) enum StallStage {
+    Connect,
+    FirstToken,
+    Idle,
+}
+
+impl StallStage {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Connect => "建立连接",
+            Self::FirstToken => "等待首个 token",
+            Self::Idle => "等待后续内容",
+        }
+    }
+}
+
+/// 在限定时间内等待 `fut`；超时返回停滞错误，未配置超时则一直等待。
+pub(super) async fn within<F: Future>(
+    limit: Option<Duration>,
+    stage: StallStage,
+    fut: F,
+) -> Result<F::Output, String> {
+    let Some(limit) = limit else {
+        return Ok(fut.await);
+    };
+    tokio::time::timeout(limit, fut)
+        .await
+        .map_err(|_| format!("{STALL_PREFIX}{}超过 {} 秒", stage.label(), limit.as_secs()))
+}
+
+pub(super) fn is_stall_error(err: &str) -> bool {
+    err.starts_with(STALL_PREFIX)
+}
+
+/// 通知界面本次请求已停滞；`will_retry` 为 false 时界面保留已输出的部分并结束本次请求。
+pub(super) fn notify_stalled(
+    input: &RequestInput,
+    tx: &Sender<RuntimeEvent>,
+    reason: &str,
+    will_retry: bool,
+) {
+    if let Some(dir) = input.log_dir.as_deref() {
+        let payload = format!("stalled (retry: {will_retry}): {reason}");
+        let _ = write_response_log(
+            dir,
+            &input.log_session_id,
+            &input.tab,
+            input.message_index,
+            &payload,
+        );
+    }
+    send_llm(
+        tx,
+        input.tab.clone(),
+        input.request_id,
+        LlmEvent::Stalled {
+            reason: reason.to_string(),
+            will_retry,
+        },
+    );
+}
@@ -1,4 +1,9 @@
-use crate::llm::rig::{CompletionModelChoice, build_completion_request, completion_model_for, prepare_rig_context};
+use crate::llm::context_budget::ContextBudget;
+use crate::llm::reasoning::MIN_THINKING_BUDGET;
+use crate::llm::rig::{
+    CompletionModelChoice, build_completion_request, completion_model_for, prepare_rig_context,
+    supports_image_input,
+};
 use crate::types::ToolCall;
 use crate::ui::events::RuntimeEvent;
 use futures::StreamExt;
@@ -16,6 +21,8 @@ use super::helpers::{
     send_done, send_reasoning_chunk, send_tool_calls, usage_from_stream,
 };
 use super::request::RequestInput;
+use super::retry::is_transient;
+use super::stall::{StallStage, within};
 
 pub(super) async fn stream_request(
     input: &RequestInput,
@@ -23,7 +30,15 @@ pub(super) async fn stream_request(
     cancel: &Arc<AtomicBool>,
     tx: &Sender<RuntimeEvent>,
 ) -> Result<(), String> {
-    let (ctx, _templates) = prepare_rig_context(&input.messages, &input.prompts_dir, enabled)?;
+    let budget = ContextBudget::new(input.context_window, input.max_tokens);
+    let include_images = supports_image_input(&input.base_url, &input.model);
+    let (ctx, _templates) = prepare_rig_context(
+        &input.messages,
+        &input.prompts_dir,
+        enabled,
+        budget,
+        include_images,
+    )?;
     log_request(input, &ctx);
     let model = completion_model_for(&input.base_url, &input.api_key, &input.model)?;
     stream_with_model(model, &ctx, input, cancel, tx).await
@@ -38,37 +53,92 @@ async fn stream_with_model(
 ) -> Result<(), String> {
     match model {
         CompletionModelChoice::OpenAi(model) => {
-            stream_with_model_impl(model, ctx, input, cancel, tx, input.max_tokens).await
+            let params = openai_params(input);
+            stream_with_model_impl(model, ctx, input, cancel, tx, params).await
         }
         CompletionModelChoice::DeepSeek(model) => {
-            stream_with_model_impl(model, ctx, input, cancel, tx, input.max_tokens).await
+            let params = ProviderParams {
+                max_tokens: input.max_tokens,
+                temperature: input.temperature,
+                additional: None,
+            };
+            stream_with_model_impl(model, ctx, input, cancel, tx, params).await
         }
         CompletionModelChoice::Anthropic(model) => {
-            let tokens = input.max_tokens.or(Some(1024));
-            stream_with_model_impl(model, ctx, input, cancel, tx, tokens).await
+            let params = anthropic_params(input);
+            stream_with_model_impl(model, ctx, input, cancel, tx, params).await
         }
     }
 }
 
+/// 各提供方不同的请求参数：输出上限、采样温度与推理相关的附加字段。
+struct ProviderParams {
+    max_tokens: Option<u64>,
+    temperature: Option<f64>,
+    additional: Option<serde_json::Value>,
+}
+
+fn openai_params(input: &RequestInput) -> ProviderParams {
+    ProviderParams {
+        max_tokens: input.max_tokens,
+        temperature: input.temperature,
+        additional: input
+            .reasoning
+            .effort
+            .map(|effort| serde_json::json!({ "reasoning_effort": effort.as_str() })),
+    }
+}
+
+fn anthropic_params(input: &RequestInput) -> ProviderParams {
+    let max_tokens = input.max_tokens.unwrap_or(1024);
+    // 工具结果回传时历史里没有带签名的 thinking 块，这一轮不开启思考
+    let after_tool = input
+        .messages
+        .last()
+        .is_some_and(|m| m.role == crate::types::ROLE_TOOL);
+    match input.reasoning.anthropic_thinking_budget() {
+        Some(budget) if !after_tool => ProviderParams {
+            // budget_tokens 必须小于 max_tokens，为正文至少留出 1024
+            max_tokens: Some(max_tokens.max(budget + MIN_THINKING_BUDGET)),
+            // 开启思考时接口不允许调整 temperature
+            temperature: None,
+            additional: Some(serde_json::json!({
+                "thinking": { "type": "enabled", "budget_tokens": budget }
+            })),
+        },
+        _ => ProviderParams {
+            max_tokens: Some(max_tokens),
+            temperature: input.temperature,
+            additional: None,
+        },
+    }
+}
+
 async fn stream_with_model_impl<M>(
     model: M,
     ctx: &crate::llm::rig::RigRequestContext,
     input: &RequestInput,
     cancel: &Arc<AtomicBool>,
     tx: &Sender<RuntimeEvent>,
-    max_tokens: Option<u64>,
+    params: ProviderParams,
 ) -> Result<(), String>
 where
     M: CompletionModel,
 {
-    let stream = match build_completion_request(&model, ctx)
-        .max_tokens_opt(max_tokens)
-        .stream()
-        .await
-    {
+    let request = build_completion_request(&model, ctx)
+        .max_tokens_opt(params.max_tokens)
+        .temperature_opt(params.temperature)
+        .additional_params_opt(params.additional.clone())
+        .stream();
+    let stream = match within(input.timeouts.connect, StallStage::Connect, request).await? {
         Ok(stream) => stream,
-        Err(_) => {
-            return run_non_stream_request(&model, ctx, input, cancel, tx, max_tokens).await;
+        Err(err) => {
+            // 限流/5xx/网络错误交给外层重试；其余情况（如不支持流式）退回非流式请求
+            let err = format!("请求失败：{err}");
+            if is_transient(&err) {
+                return Err(err);
+            }
+            return run_non_stream_request(&model, ctx, input, cancel, tx, params).await;
         }
     };
     process_stream(stream, input, cancel, tx).await
@@ -81,10 +151,24 @@ async fn process_stream<R>(
     tx: &Sender<RuntimeEvent>,
 ) -> Result<(), String>
 where
-    R: rig::completion::GetTokenUsage + Clone + Unpin,
+    R: rig::completion::GetTokenUsage + serde::Serialize + Clone + Unpin,
 {
     let mut state = StreamState::new();
-    while let Some(item) = stream.next().await {
+    loop {
+        let (limit, stage) = if state.received_any {
+            (input.timeouts.idle, StallStage::Idle)
+        } else {
+            (input.timeouts.first_token, StallStage::FirstToken)
+        };
+        let item = match within(limit, stage, stream.next()).await {
+            Ok(Some(item)) => item,
+            Ok(None) => break,
+            Err(err) => {
+                stream.cancel();
+                return Err(err);
+            }
+        };
+        state.received_any = true;
         if cancel.load(Ordering::Relaxed) {
             stream.cancel();
             return Ok(());
@@ -108,7 +192,7 @@ fn handle_stream_item<R>(
     tx: &Sender<RuntimeEvent>,
 ) -> Result<StreamStep, String>
 where
-    R: rig::completion::GetTokenUsage,
+    R: rig::completion::GetTokenUsage + serde::Serialize,
 {
     match item {
         Ok(content) => handle_stream_ok(content, state, input, tx),
@@ -123,7 +207,7 @@ fn handle_stream_ok<R>(
     tx: &Sender<RuntimeEvent>,
 ) -> Result<StreamStep, String>
 where
-    R: rig::completion::GetTokenUsage,
+    R: rig::completion::GetTokenUsage + serde::Serialize,
 {
     match content {
         StreamedAssistantContent::Text(text) => {
@@ -133,7 +217,7 @@ where
         }
         StreamedAssistantContent::ReasoningDelta { reasoning, .. } => {
             state.seen_reasoning_delta = true;
-            send_reasoning_if_enabled(input, tx, reasoning);
+            send_reasoning_if_any(input, tx, reasoning);
             Ok(StreamStep::Continue)
         }
         StreamedAssistantContent::Reasoning(reasoning) => {
@@ -161,19 +245,23 @@ async fn run_non_stream_request(
     input: &RequestInput,
     cancel: &Arc<AtomicBool>,
     tx: &Sender<RuntimeEvent>,
-    max_tokens: Option<u64>,
+    params: ProviderParams,
 ) -> Result<(), String> {
-    let response = build_completion_request(model, ctx)
-        .max_tokens_opt(max_tokens)
-        .send()
-        .await
+    let request = build_completion_request(model, ctx)
+        .max_tokens_opt(params.max_tokens)
+        .temperature_opt(params.temperature)
+        .additional_params_opt(params.additional)
+        .send();
+    // 非流式请求要等完整回复，按首个 token 的超时限制
+    let response = within(input.timeouts.first_token, StallStage::FirstToken, request)
+        .await?
         .map_err(|e| format!("请求失败：{e}"))?;
     if cancel.load(Ordering::Relaxed) {
         return Ok(());
     }
     let (text, calls, reasoning) = split_choice(&response.choice);
     let (text, reasoning) = split_think_text(text, reasoning);
-    let usage = Some(map_usage(response.usage));
+    let usage = Some(map_usage(response.usage, &response.raw_response));
     send_reasoning_from_choice(input, tx, &reasoning);
     if calls.is_empty() {
         handle_non_stream_text(input, cancel, tx, &text, usage);
@@ -240,6 +328,8 @@ fn finalize_stream(input: &RequestInput, tx: &Sender<RuntimeEvent>, mut state: S
 struct StreamState {
     text: String,
     usage: Option<crate::types::Usage>,
+    /// 已收到过任意片段；之后改用片段间隔的超时
+    received_any: bool,
     seen_reasoning_delta: bool,
     think: ThinkState,
 }
@@ -249,6 +339,7 @@ impl StreamState {
         Self {
             text: String::new(),
             usage: None,
+            received_any: false,
             seen_reasoning_delta: false,
             think: ThinkState::new(),
         }
@@ -260,8 +351,8 @@ enum StreamStep {
     ToolCalls(Vec<ToolCall>),
 }
 
-fn send_reasoning_if_enabled(input: &RequestInput, tx: &Sender<RuntimeEvent>, text: String) {
-    if input.show_reasoning && !text.is_empty() {
+fn send_reasoning_if_any(input: &RequestInput, tx: &Sender<RuntimeEvent>, text: String) {
+    if !text.is_empty() {
         send_reasoning_chunk(input, tx, text);
     }
 }
@@ -275,7 +366,7 @@ fn send_final_reasoning_if_needed(
     if state.seen_reasoning_delta {
         return;
     }
-    send_reasoning_if_enabled(input, tx, text);
+    send_reasoning_if_any(input, tx, text);
 }
 
 fn send_reasoning_from_choice(
@@ -284,7 +375,7 @@ fn send_reasoning_from_choice(
     reasoning: &Option<String>,
 ) {
     if let Some(text) = reasoning {
-        send_reasoning_if_enabled(input, tx, text.clone());
+        send_reasoning_if_any(input, tx, text.clone());
     }
 }
 
@@ -347,7 +438,7 @@ fn handle_think_result(
         send_chunk(tx, input, parsed.visible);
     }
     if !parsed.reasoning.is_empty() {
-        send_reasoning_if_enabled(input, tx, parsed.reasoning);
+        send_reasoning_if_any(input, tx, parsed.reasoning);
     }
 }
 
@@ -1,3 +1,5 @@
+use crate::llm::reasoning::ReasoningSettings;
+use crate::llm::timeouts::StreamTimeouts;
 use crate::types::Message;
 use std::sync::mpsc::Sender;
 use std::sync::{Arc, atomic::AtomicBool};
@@ -7,15 +9,19 @@ pub struct LlmStreamRequestParams {
     pub api_key: String,
     pub model: String,
     pub max_tokens: Option<u64>,
+    pub context_window: Option<u64>,
     pub messages: Vec<Message>,
     pub prompts_dir: String,
-    pub show_reasoning: bool,
+    pub reasoning: ReasoningSettings,
+    pub timeouts: StreamTimeouts,
+    pub temperature: Option<f64>,
     pub enable_web_search: bool,
     pub enable_code_exec: bool,
     pub enable_read_file: bool,
     pub enable_read_code: bool,
     pub enable_modify_file: bool,
     pub enable_ask_questions: bool,
+    pub enable_delegate_task: bool,
     pub log_dir: Option<String>,
     pub log_session_id: String,
     pub message_index: usize,
@@ -0,0 +1,146 @@
+//! 工具循环护栏
+//!
+//! 工具结果回传给模型之前检查本轮的步数、耗时、tokens 与重复调用；超出上限时暂停，
+//! 由用户用 `/continue [N]` 决定是否再放行 N 步。
+
+use crate::args::Args;
+use crate::llm::agent_limits::AgentLimits;
+use crate::services::runtime_requests::start_followup;
+use crate::types::ROLE_ASSISTANT;
+use crate::ui::events::RuntimeEvent;
+use crate::ui::notice::{push_long_notice, push_notice};
+use crate::ui::runtime_helpers::TabState;
+use crate::ui::state::{AgentTurn, App};
+use std::sync::OnceLock;
+use std::sync::mpsc;
+
+const DEFAULT_CONTINUE_STEPS: u32 = 5;
+
+static DEFAULT_LIMITS: OnceLock<AgentLimits> = OnceLock::new();
+
+/// 记录配置文件中的默认上限；对话内未用 `/limits` 调整时使用。
+pub(crate) fn init_agent_limits(limits: AgentLimits) {
+    let _ = DEFAULT_LIMITS.set(limits);
+}
+
+pub(crate) fn effective_limits(app: &App) -> AgentLimits {
+    app.agent_limits
+        .unwrap_or_else(|| DEFAULT_LIMITS.get().copied().unwrap_or_default())
+}
+
+/// 用户发出新消息，开始新的一轮工具循环。
+pub(crate) fn begin_turn(app: &mut App) {
+    app.agent_turn = AgentTurn::begin();
+}
+
+/// 超出任一上限时暂停本轮并提示用户；返回 true 表示不再自动请求模型。
+pub(crate) fn pause_if_limited(app: &mut App) -> bool {
+    let Some(reason) = limit_reason(&app.agent_turn, &effective_limits(app)) else {
+        return false;
+    };
+    mark_last_step_paused(app);
+    push_long_notice(
+        app,
+        format!(
+            "智能体想继续执行，但{reason}。输入 /continue [N] 再允许执行 N 步（默认 {DEFAULT_CONTINUE_STEPS}）"
+        ),
+    );
+    app.agent_turn.paused = Some(reason);
+    true
+}
+
+fn limit_reason(turn: &AgentTurn, limits: &AgentLimits) -> Option<String> {
+    if limits.max_repeated_calls > 0 && turn.repeated >= limits.max_repeated_calls {
+        return Some(format!("已连续 {} 轮调用相同的工具与参数", turn.repeated));
+    }
+    let step_cap = turn.step_cap.unwrap_or(limits.max_steps);
+    if step_cap > 0 && turn.steps >= step_cap {
+        return Some(format!("本轮工具轮次已达上限（{step_cap} 步）"));
+    }
+    let elapsed = turn.started_at.map_or(0, |t| t.elapsed().as_secs());
+    if limits.max_turn_secs > 0 && elapsed >= limits.max_turn_secs {
+        return Some(format!(
+            "本轮已运行 {elapsed} 秒，超过上限 {} 秒",
+            limits.max_turn_secs
+        ));
+    }
+    if limits.max_turn_tokens > 0 && turn.tokens >= limits.max_turn_tokens {
+        return Some(format!(
+            "本轮已消耗 {} tokens，超过上限 {}",
+            turn.tokens, limits.max_turn_tokens
+        ));
+    }
+    None
+}
+
+fn mark_last_step_paused(app: &mut App) {
+    let Some(idx) = app
+        .messages
+        .iter()
+        .rposition(|m| m.role == ROLE_ASSISTANT && m.tool_calls.is_some())
+    else {
+        return;
+    };
+    app.assistant_stats
+        .entry(idx)
+        .or_default()
+        .push_str(" · 已暂停");
+    app.dirty_indices.push(idx);
+}
+
+/// `/continue [N]`：放行被护栏暂停的工具循环，再允许执行 N 步。
+pub(crate) fn handle_agent_continue(
+    tab_state: &mut TabState,
+    registry: &crate::model_registry::ModelRegistry,
+    args: &Args,
+    tx: &mpsc::Sender<RuntimeEvent>,
+) {
+    let app = &mut tab_state.app;
+    let arg = app.pending_agent_steps.take().unwrap_or_default();
+    if app.agent_turn.paused.is_none() {
+        push_notice(app, "当前没有因上限暂停的工具循环");
+        return;
+    }
+    let steps = match parse_steps(&arg) {
+        Ok(steps) => steps,
+        Err(err) => {
+            push_notice(app, err);
+            return;
+        }
+    };
+    app.agent_turn.resume(steps);
+    push_notice(app, format!("已再允许执行 {steps} 步"));
+    start_followup(tab_state, registry, args, tx);
+}
+
+fn parse_steps(arg: &str) -> Result<u32, String> {
+    if arg.is_empty() {
+        return Ok(DEFAULT_CONTINUE_STEPS);
+    }
+    match arg.parse::<u32>() {
+        Ok(steps) if steps > 0 => Ok(steps),
+        _ => Err("用法：/continue [N]（N 为正整数）".to_string()),
+    }
+}
+
+/// `/limits [steps=N] [time=秒] [tokens=N] [repeat=N] [depth=N]`：查看或调整本对话的工具循环上限，0 表示不限制。
+pub(crate) fn handle_limits_command(app: &mut App, arg: &str) {
+    if arg.is_empty() {
+        let text = format!("工具循环上限：{}", effective_limits(app).describe());
+        push_notice(app, text);
+        return;
+    }
+    if arg == "reset" {
+        app.agent_limits = None;
+        let text = format!("已恢复默认上限：{}", effective_limits(app).describe());
+        push_notice(app, text);
+        return;
+    }
+    match effective_limits(app).with_overrides(arg) {
+        Ok(limits) => {
+            app.agent_limits = Some(limits);
+            push_notice(app, format!("本对话工具循环上限：{}", limits.describe()));
+        }
+        Err(err) => push_notice(app, err),
+    }
+}
@@ -0,0 +1,107 @@
+//! 自动保存
+//!
+//! 每条回复完成后立即把所在对话写盘（临时文件加改名，不会写出半截 JSON），
+//! 同时刷新崩溃恢复用的快照，不再依赖 `/save` 或正常退出。
+
+use crate::conversation::{ConversationData, save_conversation};
+use crate::recovery::{snapshot_lengths, update_snapshot};
+use crate::ui::notice::push_notice;
+use crate::ui::runtime_helpers::{TabState, tab_to_conversation};
+
+/// 对比标签页是临时的，保留或放弃后即关闭，不写盘也不进快照。
+pub(crate) fn autosave_done_tabs(tabs: &mut [TabState], done_tabs: &[usize], active_tab: usize) {
+    if done_tabs.is_empty() {
+        return;
+    }
+    let ephemeral = compare_tab_ids(tabs);
+    let mut changed = Vec::new();
+    for &idx in done_tabs {
+        let Some(tab) = tabs.get_mut(idx) else {
+            continue;
+        };
+        if ephemeral.contains(&tab.conversation_id) {
+            continue;
+        }
+        let conv = tab_to_conversation(tab);
+        if let Err(err) = save_conversation(&conv) {
+            push_notice(&mut tab.app, format!("自动保存失败：{err}"));
+        }
+        changed.push(conv);
+    }
+    snapshot_tabs(tabs, active_tab, &ephemeral, changed);
+}
+
+/// 每帧检查一次：发送问题、追加工具结果或开关标签页后就刷新快照，不等回复完成。
+/// 只比较消息数，没有变化时不复制对话。
+pub(crate) fn refresh_snapshot(tabs: &[TabState], active_tab: usize) {
+    let ephemeral = compare_tab_ids(tabs);
+    let saved = snapshot_lengths();
+    let open = tabs
+        .iter()
+        .filter(|t| !ephemeral.contains(&t.conversation_id));
+    let changed: Vec<ConversationData> = open
+        .clone()
+        .filter(|t| saved.get(&t.conversation_id) != Some(&snapshot_len(t)))
+        .map(snapshot_conversation)
+        .collect();
+    if changed.is_empty() && saved.len() == open.count() {
+        return;
+    }
+    snapshot_tabs(tabs, active_tab, &ephemeral, changed);
+}
+
+fn snapshot_tabs(
+    tabs: &[TabState],
+    active_tab: usize,
+    ephemeral: &[String],
+    changed: Vec<ConversationData>,
+) {
+    let open: Vec<String> = tabs
+        .iter()
+        .map(|t| t.conversation_id.clone())
+        .filter(|id| !ephemeral.contains(id))
+        .collect();
+    update_snapshot(
+        tabs.get(active_tab).map(|t| t.conversation_id.clone()),
+        &open,
+        changed,
+        |id| {
+            tabs.iter()
+                .find(|t| t.conversation_id == id)
+                .map(snapshot_conversation)
+        },
+    );
+}
+
+/// 还在流式输出的回复与思考不进快照，恢复后停在刚发出的问题上。
+fn snapshot_conversation(tab: &TabState) -> ConversationData {
+    let mut conv = tab_to_conversation(tab);
+    conv.messages.truncate(settled_len(tab));
+    conv
+}
+
+fn snapshot_len(tab: &TabState) -> usize {
+    settled_len(tab) + tab.app.archived_messages.len()
+}
+
+fn settled_len(tab: &TabState) -> usize {
+    let app = &tab.app;
+    [app.pending_assistant, app.pending_reasoning]
+        .into_iter()
+        .flatten()
+        .fold(app.messages.len(), usize::min)
+}
+
+/// 对话在回复之外发生变化时（如保留对比结果）立即写盘。
+pub(crate) fn autosave_tab(tab: &mut TabState) {
+    if let Err(err) = save_conversation(&tab_to_conversation(tab)) {
+        push_notice(&mut tab.app, format!("自动保存失败：{err}"));
+    }
+}
+
+fn compare_tab_ids(tabs: &[TabState]) -> Vec<String> {
+    tabs.iter()
+        .filter_map(|t| t.app.compare.as_ref())
+        .flat_map(|compare| compare.entries.iter().map(|e| e.conversation_id.clone()))
+        .collect()
+}
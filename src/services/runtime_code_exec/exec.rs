@@ -1,37 +1,95 @@
 use crate::services::code_exec_container::{
-    run_bash_in_container_stream, run_python_in_container_stream,
+    run_bash_in_container_stream, run_bash_on_host_stream, run_python_in_container_stream,
+    run_python_in_kernel_stream, run_python_on_host_stream,
 };
 use crate::services::runtime_code_exec::helpers::{mark_exec_error, mark_unsupported_language};
-use crate::ui::state::{CodeExecLive, PendingCodeExec};
+use crate::services::workspace::WorkspaceConfig;
+use crate::ui::state::{CodeExecLive, CodeExecStdin, PendingCodeExec};
 
 pub(super) fn spawn_exec(
     container_id: String,
     run_id: String,
+    kernel_key: Option<String>,
     pending: PendingCodeExec,
     live: std::sync::Arc<std::sync::Mutex<CodeExecLive>>,
     cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    stdin: std::sync::mpsc::Receiver<CodeExecStdin>,
 ) {
     if pending.language == "python" {
-        spawn_python_exec(container_id, run_id, pending, live, cancel);
+        match kernel_key {
+            Some(key) => spawn_kernel_exec(container_id, key, pending, live, cancel, stdin),
+            None => spawn_python_exec(container_id, run_id, pending, live, cancel, stdin),
+        }
     } else if pending.language == "bash" || pending.language == "sh" {
-        spawn_bash_exec(container_id, run_id, pending, live, cancel);
+        spawn_bash_exec(container_id, run_id, pending, live, cancel, stdin);
     } else {
         mark_unsupported_language(&live, &pending.language);
     }
 }
 
+pub(super) fn spawn_host_exec(
+    workspace: WorkspaceConfig,
+    run_id: String,
+    pending: PendingCodeExec,
+    live: std::sync::Arc<std::sync::Mutex<CodeExecLive>>,
+    cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    stdin: std::sync::mpsc::Receiver<CodeExecStdin>,
+) {
+    let run = match pending.language.as_str() {
+        "python" => run_python_on_host_stream,
+        "bash" | "sh" => run_bash_on_host_stream,
+        _ => {
+            mark_unsupported_language(&live, &pending.language);
+            return;
+        }
+    };
+    let code = pending.exec_code.as_deref().unwrap_or(&pending.code);
+    if let Err(err) = run(&workspace, &run_id, code, live.clone(), cancel, stdin) {
+        mark_exec_error(&live, err);
+    }
+}
+
 fn spawn_python_exec(
     container_id: String,
     run_id: String,
     pending: PendingCodeExec,
     live: std::sync::Arc<std::sync::Mutex<CodeExecLive>>,
     cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    stdin: std::sync::mpsc::Receiver<CodeExecStdin>,
 ) {
     std::thread::spawn(move || {
         let code = pending.exec_code.as_deref().unwrap_or(&pending.code);
-        if let Err(err) =
-            run_python_in_container_stream(&container_id, &run_id, code, live.clone(), cancel)
-        {
+        if let Err(err) = run_python_in_container_stream(
+            &container_id,
+            &run_id,
+            code,
+            live.clone(),
+            cancel,
+            stdin,
+        ) {
+            mark_exec_error(&live, err);
+        }
+    });
+}
+
+fn spawn_kernel_exec(
+    container_id: String,
+    kernel_key: String,
+    pending: PendingCodeExec,
+    live: std::sync::Arc<std::sync::Mutex<CodeExecLive>>,
+    cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    stdin: std::sync::mpsc::Receiver<CodeExecStdin>,
+) {
+    std::thread::spawn(move || {
+        let code = pending.exec_code.as_deref().unwrap_or(&pending.code);
+        if let Err(err) = run_python_in_kernel_stream(
+            &container_id,
+            &kernel_key,
+            code,
+            live.clone(),
+            cancel,
+            stdin,
+        ) {
             mark_exec_error(&live, err);
         }
     });
@@ -43,11 +101,12 @@ fn spawn_bash_exec(
     pending: PendingCodeExec,
     live: std::sync::Arc<std::sync::Mutex<CodeExecLive>>,
     cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    stdin: std::sync::mpsc::Receiver<CodeExecStdin>,
 ) {
     std::thread::spawn(move || {
         let code = pending.exec_code.as_deref().unwrap_or(&pending.code);
         if let Err(err) =
-            run_bash_in_container_stream(&container_id, &run_id, code, live.clone(), cancel)
+            run_bash_in_container_stream(&container_id, &run_id, code, live.clone(), cancel, stdin)
         {
             mark_exec_error(&live, err);
         }
@@ -1,8 +1,10 @@
 use crate::types::Message;
-use crate::ui::state::{App, CodeExecLive, PendingCodeExec};
+use crate::ui::state::{App, CodeExecLive, CodeExecStdin, PendingCodeExec};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 pub(super) fn reset_code_exec_ui(app: &mut App) {
+    app.code_exec_stdin = None;
+    app.code_exec_stdin_input = tui_textarea::TextArea::default();
     app.code_exec_scroll = 0;
     app.code_exec_stdout_scroll = 0;
     app.code_exec_stderr_scroll = 0;
@@ -23,6 +25,8 @@ pub(super) fn init_code_exec_live(app: &mut App) -> std::sync::Arc<std::sync::Mu
         stderr: String::new(),
         exit_code: None,
         done: false,
+        stdin_log: String::new(),
+        stdin_closed: false,
     }));
     app.code_exec_live = Some(live.clone());
     app.code_exec_result_ready = false;
@@ -37,6 +41,13 @@ pub(super) fn init_code_exec_live(app: &mut App) -> std::sync::Arc<std::sync::Mu
     live
 }
 
+pub(super) fn init_stdin_channel(app: &mut App) -> std::sync::mpsc::Receiver<CodeExecStdin> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    app.code_exec_stdin = Some(tx);
+    app.code_exec_stdin_input = tui_textarea::TextArea::default();
+    rx
+}
+
 pub(super) fn init_cancel_flag(app: &mut App) -> std::sync::Arc<std::sync::atomic::AtomicBool> {
     let cancel = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
     app.code_exec_cancel = Some(cancel.clone());
@@ -89,11 +100,14 @@ pub(super) fn push_tool_message(app: &mut App, content: String, call_id: String)
         content,
         tool_call_id: Some(call_id),
         tool_calls: None,
+        attachments: Vec::new(),
     });
     app.dirty_indices.push(idx);
 }
 
 pub(super) fn reset_code_exec_after_exit(app: &mut App) {
+    app.code_exec_stdin = None;
+    app.code_exec_stdin_input = tui_textarea::TextArea::default();
     app.code_exec_live = None;
     app.code_exec_result_ready = false;
     app.code_exec_cancel = None;
@@ -107,6 +121,8 @@ pub(super) fn reset_code_exec_after_exit(app: &mut App) {
 }
 
 pub(super) fn reset_code_exec_after_deny(app: &mut App) {
+    app.code_exec_stdin = None;
+    app.code_exec_stdin_input = tui_textarea::TextArea::default();
     app.code_exec_live = None;
     app.code_exec_result_ready = false;
     app.code_exec_finished_output = None;
@@ -3,13 +3,14 @@ mod helpers;
 mod pending;
 
 use crate::args::Args;
-use crate::services::code_exec_container::ensure_container_cached;
+use crate::services::code_exec_container::{ensure_container_cached, restart_kernel};
+use crate::services::code_exec_container_env::{host_backend_enabled, python_kernel_enabled};
 use crate::ui::events::RuntimeEvent;
 use crate::services::runtime_code_exec_helpers::inject_requirements;
 use crate::services::runtime_code_exec_output::{escape_json_string, take_code_exec_reason};
 use crate::ui::runtime_helpers::TabState;
-use crate::services::runtime_requests::start_followup_request;
-use crate::ui::state::{CodeExecReasonTarget, PendingCodeExec};
+use crate::services::runtime_requests::start_followup;
+use crate::ui::state::{CodeExecReasonTarget, CodeExecStdin, PendingCodeExec};
 use crate::services::tools::{parse_bash_exec_args, parse_code_exec_args};
 use std::sync::mpsc;
 use std::time::Instant;
@@ -66,6 +67,30 @@ pub(crate) fn handle_code_exec_stop(tab_state: &mut TabState) {
     tab_state.app.code_exec_hover = None;
 }
 
+pub(crate) fn submit_code_exec_stdin(app: &mut crate::ui::state::App, eof: bool) {
+    let (Some(tx), Some(live)) = (app.code_exec_stdin.clone(), app.code_exec_live.clone()) else {
+        return;
+    };
+    let Ok(mut live) = live.lock() else {
+        return;
+    };
+    if live.done || live.stdin_closed {
+        return;
+    }
+    if eof {
+        if tx.send(CodeExecStdin::Eof).is_ok() {
+            live.stdin_closed = true;
+        }
+        return;
+    }
+    let line = app.code_exec_stdin_input.lines().join("\n");
+    app.code_exec_stdin_input = tui_textarea::TextArea::default();
+    if tx.send(CodeExecStdin::Line(line.clone())).is_ok() {
+        live.stdin_log.push_str(&line);
+        live.stdin_log.push('\n');
+    }
+}
+
 pub(crate) fn handle_code_exec_exit(
     tab_state: &mut TabState,
     registry: &crate::model_registry::ModelRegistry,
@@ -93,7 +118,7 @@ pub(crate) fn handle_code_exec_approve(
     if tab_state.app.code_exec_live.is_some() {
         return;
     }
-    let (live, cancel, run_id) = init_exec_state(tab_state);
+    let (live, cancel, run_id, stdin) = init_exec_state(tab_state);
     let exec_code = build_exec_code(&pending);
     helpers::store_exec_code(&mut tab_state.app, &mut pending, exec_code);
     let workspace = match crate::services::workspace::resolve_workspace(args) {
@@ -103,7 +128,24 @@ pub(crate) fn handle_code_exec_approve(
             return;
         }
     };
-    spawn_exec_thread(workspace, pending, live, cancel, run_id);
+    let kernel_key = (python_kernel_enabled() && !host_backend_enabled())
+        .then(|| tab_state.conversation_id.clone());
+    spawn_exec_thread(workspace, pending, live, cancel, stdin, run_id, kernel_key);
+}
+
+pub(crate) fn handle_kernel_restart(tab_state: &mut TabState) {
+    let content = if !python_kernel_enabled() {
+        "未启用 Python 常驻内核（使用 --python-kernel 或 DEEPCHAT_CODE_EXEC_KERNEL=1 开启）。"
+    } else if host_backend_enabled() {
+        "本机执行后端不支持 Python 常驻内核。"
+    } else if tab_state.app.code_exec_live.is_some() {
+        "代码正在执行中，请先停止后再重启内核。"
+    } else if restart_kernel(&tab_state.conversation_id) {
+        "Python 内核已重启，变量与导入已清空。"
+    } else {
+        "当前对话尚无运行中的 Python 内核，下次执行时将自动启动。"
+    };
+    crate::ui::notice::push_notice(&mut tab_state.app, content);
 }
 
 fn init_exec_state(
@@ -112,11 +154,13 @@ fn init_exec_state(
     std::sync::Arc<std::sync::Mutex<crate::ui::state::CodeExecLive>>,
     std::sync::Arc<std::sync::atomic::AtomicBool>,
     String,
+    std::sync::mpsc::Receiver<crate::ui::state::CodeExecStdin>,
 ) {
     let live = helpers::init_code_exec_live(&mut tab_state.app);
     let cancel = helpers::init_cancel_flag(&mut tab_state.app);
     let run_id = helpers::init_run_id(&mut tab_state.app);
-    (live, cancel, run_id)
+    let stdin = helpers::init_stdin_channel(&mut tab_state.app);
+    (live, cancel, run_id, stdin)
 }
 
 fn build_exec_code(pending: &PendingCodeExec) -> String {
@@ -132,12 +176,18 @@ fn spawn_exec_thread(
     pending: PendingCodeExec,
     live: std::sync::Arc<std::sync::Mutex<crate::ui::state::CodeExecLive>>,
     cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    stdin: std::sync::mpsc::Receiver<crate::ui::state::CodeExecStdin>,
     run_id: String,
+    kernel_key: Option<String>,
 ) {
     std::thread::spawn(move || {
         if cancel.load(std::sync::atomic::Ordering::Relaxed) {
             return;
         }
+        if host_backend_enabled() {
+            exec::spawn_host_exec(workspace, run_id, pending, live, cancel, stdin);
+            return;
+        }
         let container_id = match ensure_container_cached(&workspace) {
             Ok(id) => id,
             Err(err) => {
@@ -148,7 +198,15 @@ fn spawn_exec_thread(
         if cancel.load(std::sync::atomic::Ordering::Relaxed) {
             return;
         }
-        exec::spawn_exec(container_id, run_id, pending, live, cancel);
+        exec::spawn_exec(
+            container_id,
+            run_id,
+            kernel_key,
+            pending,
+            live,
+            cancel,
+            stdin,
+        );
     });
 }
 
@@ -171,32 +229,3 @@ pub(crate) fn handle_code_exec_deny(
     helpers::push_tool_message(&mut tab_state.app, content, pending.call_id);
     start_followup(tab_state, registry, args, tx);
 }
-
-fn start_followup(
-    tab_state: &mut TabState,
-    registry: &crate::model_registry::ModelRegistry,
-    args: &Args,
-    tx: &mpsc::Sender<RuntimeEvent>,
-) {
-    let model = registry
-        .get(&tab_state.app.model_key)
-        .unwrap_or_else(|| registry.get(&registry.default_key).expect("model"));
-    let log_session_id = tab_state.app.log_session_id.clone();
-    start_followup_request(crate::services::runtime_requests::StartFollowupRequestParams {
-        tab_state,
-        base_url: &model.base_url,
-        api_key: &model.api_key,
-        model: &model.model,
-        max_tokens: model.max_tokens,
-        show_reasoning: args.show_reasoning,
-        tx,
-        enable_web_search: args.web_search_enabled(),
-        enable_code_exec: args.code_exec_enabled(),
-        enable_read_file: args.read_file_enabled(),
-        enable_read_code: args.read_code_enabled(),
-        enable_modify_file: args.modify_file_enabled(),
-        enable_ask_questions: args.ask_questions_enabled(),
-        log_requests: args.log_requests.clone(),
-        log_session_id,
-    });
-}
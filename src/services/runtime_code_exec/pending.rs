@@ -24,6 +24,7 @@ fn push_no_pending(app: &mut App) {
         content: "没有待审批的代码执行请求。".to_string(),
         tool_call_id: None,
         tool_calls: None,
+        attachments: Vec::new(),
     });
     app.dirty_indices.push(idx);
 }
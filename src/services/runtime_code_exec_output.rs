@@ -16,6 +16,7 @@ pub(crate) fn build_code_exec_tool_output(
     append_exit_code(&mut text, live.exit_code);
     append_output_block(&mut text, "stdout", &stdout_filtered, stdout_empty);
     append_output_block(&mut text, "stderr", &live.stderr, stderr_empty);
+    append_stdin_block(&mut text, live);
     append_empty_note(&mut text, live, stdout_empty, stderr_empty);
     append_stop_reason(&mut text, pending);
     text
@@ -92,6 +93,20 @@ fn append_output_block(out: &mut String, label: &str, content: &str, empty: bool
     out.push_str("```\n");
 }
 
+fn append_stdin_block(out: &mut String, live: &CodeExecLive) {
+    if !live.stdin_log.is_empty() {
+        out.push_str("stdin(用户输入):\n```text\n");
+        out.push_str(&live.stdin_log);
+        if !live.stdin_log.ends_with('\n') {
+            out.push('\n');
+        }
+        out.push_str("```\n");
+    }
+    if live.stdin_closed {
+        out.push_str("stdin_eof: 用户已关闭 stdin\n");
+    }
+}
+
 fn append_empty_note(
     out: &mut String,
     live: &CodeExecLive,
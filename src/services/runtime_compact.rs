@@ -0,0 +1,192 @@
+use crate::args::Args;
+use crate::llm::context_budget::{ContextBudget, context_report, system_prompt_tokens};
+use crate::services::runtime_requests::{
+    CompactRequest, StartFollowupRequestParams, start_compact_request,
+};
+use crate::types::{Message, ROLE_ASSISTANT, ROLE_SUMMARY, ROLE_SYSTEM, ROLE_USER};
+use crate::ui::events::RuntimeEvent;
+use crate::ui::notice::push_notice;
+use crate::ui::runtime_helpers::TabState;
+use crate::ui::state::CompactionState;
+use std::collections::BTreeMap;
+use std::sync::mpsc;
+
+/// 自动压缩阈值：预计占用达到上下文窗口的该比例时触发。
+const AUTO_COMPACT_RATIO: f64 = 0.85;
+/// 至少有这么多条可压缩的消息才值得发起压缩。
+const MIN_COMPACT_MESSAGES: usize = 2;
+
+const COMPACT_INSTRUCTION: &str = "请将以上对话压缩为一份摘要，供后续对话继续使用。\
+要求：保留用户的目标与约束、已确认的结论与决定、关键的代码/文件路径/命令/数据、\
+尚未解决的问题与下一步计划；省略寒暄与重复内容；使用与对话相同的语言，只输出摘要正文。";
+
+pub(crate) fn handle_compact_command(
+    tab_state: &mut TabState,
+    registry: &crate::model_registry::ModelRegistry,
+    args: &Args,
+    tx: &mpsc::Sender<RuntimeEvent>,
+) {
+    if tab_state.app.busy {
+        push_notice(&mut tab_state.app, "请等待当前回复结束后再压缩对话");
+        return;
+    }
+    let Some(keep_from) = compact_split(&tab_state.app.messages) else {
+        push_notice(&mut tab_state.app, "对话较短，无需压缩");
+        return;
+    };
+    start_compaction(tab_state, keep_from, registry, args, tx);
+}
+
+/// 处理本轮结束的 tab：完成进行中的压缩；开启 --auto-compact 时在接近窗口上限后自动压缩。
+pub(crate) fn apply_compactions(
+    tabs: &mut [TabState],
+    done_tabs: &[usize],
+    registry: &crate::model_registry::ModelRegistry,
+    args: &Args,
+    tx: &mpsc::Sender<RuntimeEvent>,
+) {
+    for &tab in done_tabs {
+        let Some(tab_state) = tabs.get_mut(tab) else {
+            continue;
+        };
+        if let Some(state) = tab_state.app.compaction.take() {
+            finish_compaction(tab_state, state);
+            continue;
+        }
+        if args.auto_compact
+            && !tab_state.app.busy
+            && near_context_limit(tab_state, registry)
+            && let Some(keep_from) = compact_split(&tab_state.app.messages)
+        {
+            push_notice(
+                &mut tab_state.app,
+                "上下文接近窗口上限，正在自动压缩较早的对话",
+            );
+            start_compaction(tab_state, keep_from, registry, args, tx);
+        }
+    }
+}
+
+fn start_compaction(
+    tab_state: &mut TabState,
+    keep_from: usize,
+    registry: &crate::model_registry::ModelRegistry,
+    args: &Args,
+    tx: &mpsc::Sender<RuntimeEvent>,
+) {
+    let model = registry
+        .get(&tab_state.app.model_key)
+        .unwrap_or_else(|| registry.get(&registry.default_key).expect("model"));
+    let log_session_id = tab_state.app.log_session_id.clone();
+    start_compact_request(
+        StartFollowupRequestParams {
+            tab_state,
+            base_url: &model.base_url,
+            api_key: &model.api_key,
+            model: &model.model,
+            max_tokens: model.max_tokens,
+            context_window: model.context_window,
+            reasoning: model.reasoning,
+            timeouts: model.timeouts,
+            tx,
+            enable_web_search: false,
+            enable_code_exec: false,
+            enable_read_file: false,
+            enable_read_code: false,
+            enable_modify_file: false,
+            enable_ask_questions: false,
+            enable_delegate_task: false,
+            log_requests: args.log_requests.clone(),
+            log_session_id,
+        },
+        CompactRequest {
+            keep_from,
+            instruction: COMPACT_INSTRUCTION.to_string(),
+        },
+    );
+}
+
+/// 最后一轮（从最后一条用户消息开始）原样保留，之前的内容参与压缩。
+fn compact_split(messages: &[Message]) -> Option<usize> {
+    let keep_from = messages.iter().rposition(|m| m.role == ROLE_USER)?;
+    let compactable = messages[..keep_from]
+        .iter()
+        .filter(|m| m.role != ROLE_SYSTEM)
+        .count();
+    (compactable >= MIN_COMPACT_MESSAGES).then_some(keep_from)
+}
+
+fn near_context_limit(
+    tab_state: &TabState,
+    registry: &crate::model_registry::ModelRegistry,
+) -> bool {
+    let Some(model) = registry.get(&tab_state.app.model_key) else {
+        return false;
+    };
+    let Some(budget) = ContextBudget::new(model.context_window, model.max_tokens) else {
+        return false;
+    };
+    let messages = &tab_state.app.messages;
+    let report = context_report(messages, system_prompt_tokens(messages), Some(budget));
+    report.estimated_tokens as f64 >= budget.window as f64 * AUTO_COMPACT_RATIO
+}
+
+fn finish_compaction(tab_state: &mut TabState, state: CompactionState) {
+    let app = &mut tab_state.app;
+    let summary_from = state.summary_from.min(app.messages.len());
+    let keep_from = state.keep_from.min(summary_from);
+    let summary = collect_summary(&app.messages[summary_from..]);
+    app.messages.truncate(summary_from);
+    if summary.is_empty() {
+        app.assistant_stats.retain(|idx, _| *idx < summary_from);
+        app.answer_variants.retain(|idx, _| *idx < summary_from);
+        push_notice(app, "压缩失败：模型未返回摘要");
+        tab_state.reset_render_state(tab_state.app.messages.len().saturating_sub(1));
+        return;
+    }
+    let tail = app.messages.split_off(keep_from);
+    let older = std::mem::take(&mut app.messages);
+    let (system, archived): (Vec<Message>, Vec<Message>) =
+        older.into_iter().partition(|m| m.role == ROLE_SYSTEM);
+    let archived_count = archived.len();
+    app.archived_messages.extend(archived);
+    app.messages = system;
+    app.messages.push(Message {
+        role: ROLE_SUMMARY.to_string(),
+        content: summary,
+        tool_call_id: None,
+        tool_calls: None,
+        attachments: Vec::new(),
+    });
+    let tail_start = app.messages.len();
+    app.messages.extend(tail);
+    app.assistant_stats = shift_stats(&app.assistant_stats, keep_from, summary_from, tail_start);
+    app.answer_variants = shift_stats(&app.answer_variants, keep_from, summary_from, tail_start);
+    push_notice(app, format!("已将较早的 {archived_count} 条消息压缩为摘要"));
+    tab_state.reset_render_state(tab_state.app.messages.len().saturating_sub(1));
+}
+
+/// 摘要可能被拆成思考过程与正文两条消息，只取正文。
+fn collect_summary(messages: &[Message]) -> String {
+    messages
+        .iter()
+        .filter(|m| m.role == ROLE_ASSISTANT)
+        .map(|m| m.content.trim())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// 保留区间内按消息下标记录的信息（统计、回答候选），平移到压缩后的位置。
+fn shift_stats<T: Clone>(
+    stats: &BTreeMap<usize, T>,
+    keep_from: usize,
+    keep_end: usize,
+    new_start: usize,
+) -> BTreeMap<usize, T> {
+    stats
+        .iter()
+        .filter(|(idx, _)| (keep_from..keep_end).contains(*idx))
+        .map(|(idx, val)| (idx - keep_from + new_start, val.clone()))
+        .collect()
+}
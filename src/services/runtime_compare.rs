@@ -0,0 +1,311 @@
+use crate::args::Args;
+use crate::framework::widget_system::runtime::logic::{add_usage_totals, stop_stream};
+use crate::services::runtime_autosave::autosave_tab;
+use crate::services::runtime_requests::{StartTabRequestParams, start_tab_request};
+use crate::ui::events::RuntimeEvent;
+use crate::ui::notice::push_notice;
+use crate::ui::runtime_helpers::TabState;
+use crate::ui::state::{CompareEntry, CompareState};
+use std::sync::mpsc;
+use std::time::Instant;
+
+/// 对比列由 Flex3 排布，最多同时对比三个模型。
+pub(crate) const MAX_COMPARE_MODELS: usize = 3;
+const MIN_COMPARE_MODELS: usize = 2;
+
+/// `/compare` 的参数：模型名列表开启对比模式，`off` 关闭。
+pub(crate) fn handle_compare_models_command(
+    tab_state: &mut TabState,
+    registry: &crate::model_registry::ModelRegistry,
+) {
+    let app = &mut tab_state.app;
+    let arg = app.pending_compare_models.take().unwrap_or_default();
+    if arg.is_empty() {
+        let text = if app.compare_models.is_empty() {
+            format!(
+                "用法：/compare <模型1> <模型2> [模型3]（/compare off 关闭）；可用模型：{}",
+                model_keys(registry)
+            )
+        } else {
+            format!("对比模式已开启：{}", app.compare_models.join(" · "))
+        };
+        push_notice(app, text);
+        return;
+    }
+    if arg == "off" {
+        app.compare_models.clear();
+        push_notice(app, "已关闭对比模式");
+        return;
+    }
+    match parse_compare_models(&arg, registry) {
+        Ok(models) => {
+            push_notice(
+                app,
+                format!(
+                    "已开启对比模式：{}，下一条消息将同时发送给这些模型（不启用工具）",
+                    models.join(" · ")
+                ),
+            );
+            app.compare_models = models;
+        }
+        Err(err) => push_notice(app, err),
+    }
+}
+
+fn parse_compare_models(
+    arg: &str,
+    registry: &crate::model_registry::ModelRegistry,
+) -> Result<Vec<String>, String> {
+    let mut models: Vec<String> = Vec::new();
+    for key in arg.split([' ', ',', '，']).filter(|k| !k.is_empty()) {
+        if registry.get(key).is_none() {
+            return Err(format!("未知模型：{key}（可用：{}）", model_keys(registry)));
+        }
+        if !models.iter().any(|m| m == key) {
+            models.push(key.to_string());
+        }
+    }
+    if !(MIN_COMPARE_MODELS..=MAX_COMPARE_MODELS).contains(&models.len()) {
+        return Err(format!(
+            "对比模式需要 {MIN_COMPARE_MODELS}～{MAX_COMPARE_MODELS} 个不同的模型"
+        ));
+    }
+    Ok(models)
+}
+
+fn model_keys(registry: &crate::model_registry::ModelRegistry) -> String {
+    registry
+        .models
+        .iter()
+        .map(|m| m.key.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// 对比模式下发送消息：为每个模型复制一份当前对话到新标签页并同时发起请求。
+pub(crate) fn start_compare(
+    tabs: &mut Vec<TabState>,
+    active_tab: usize,
+    line: String,
+    registry: &crate::model_registry::ModelRegistry,
+    args: &Args,
+    tx: &mpsc::Sender<RuntimeEvent>,
+) {
+    let Some(source) = tabs.get_mut(active_tab) else {
+        return;
+    };
+    if source.app.compare.is_some() {
+        source.app.input = tui_textarea::TextArea::from(line.lines().map(str::to_string));
+        push_notice(
+            &mut source.app,
+            "请先保留或放弃当前的对比结果（Enter 保留 · Esc 放弃）",
+        );
+        return;
+    }
+    let attachments = std::mem::take(&mut source.app.pending_attachments);
+    let models = source.app.compare_models.clone();
+    let seeds: Vec<TabState> = models
+        .iter()
+        .map(|key| compare_tab(source, key, &attachments))
+        .collect();
+    let history_len = source.app.messages.len();
+    let mut entries = Vec::new();
+    for tab in seeds {
+        entries.push(CompareEntry {
+            model_key: tab.app.model_key.clone(),
+            conversation_id: tab.conversation_id.clone(),
+            history_len,
+            started_at: Instant::now(),
+            elapsed_ms: None,
+        });
+        tabs.push(tab);
+        let idx = tabs.len() - 1;
+        start_compare_request(&mut tabs[idx], &line, registry, args, tx);
+    }
+    if let Some(source) = tabs.get_mut(active_tab) {
+        source.app.compare = Some(CompareState {
+            question: line,
+            entries,
+        });
+    }
+}
+
+fn compare_tab(
+    source: &TabState,
+    model_key: &str,
+    attachments: &[crate::types::Attachment],
+) -> TabState {
+    let conv_id = crate::conversation::new_conversation_id()
+        .unwrap_or_else(|_| format!("{}-{model_key}", source.conversation_id));
+    let mut tab = TabState::new(
+        conv_id,
+        source.category.clone(),
+        "",
+        false,
+        model_key,
+        &source.app.prompt_key,
+    );
+    tab.app.messages = source.app.messages.clone();
+    tab.app.dirty_indices = (0..tab.app.messages.len()).collect();
+    tab.app.pending_attachments = attachments.to_vec();
+    tab.app.set_log_session_id(&source.app.log_session_id);
+    tab.app.prompts_dir = source.app.prompts_dir.clone();
+    tab.app.tavily_api_key = source.app.tavily_api_key.clone();
+    tab.app.hooks = source.app.hooks.clone();
+    tab
+}
+
+/// 对比标签页在后台运行，工具审批无法呈现给用户，因此只比较纯文本回答。
+fn start_compare_request(
+    tab_state: &mut TabState,
+    question: &str,
+    registry: &crate::model_registry::ModelRegistry,
+    args: &Args,
+    tx: &mpsc::Sender<RuntimeEvent>,
+) {
+    let model = registry
+        .get(&tab_state.app.model_key)
+        .unwrap_or_else(|| registry.get(&registry.default_key).expect("model"));
+    let log_session_id = tab_state.app.log_session_id.clone();
+    start_tab_request(StartTabRequestParams {
+        tab_state,
+        question,
+        base_url: &model.base_url,
+        api_key: &model.api_key,
+        model: &model.model,
+        max_tokens: model.max_tokens,
+        context_window: model.context_window,
+        reasoning: model.reasoning,
+        timeouts: model.timeouts,
+        tx,
+        enable_web_search: false,
+        enable_code_exec: false,
+        enable_read_file: false,
+        enable_read_code: false,
+        enable_modify_file: false,
+        enable_ask_questions: false,
+        enable_delegate_task: false,
+        log_requests: args.log_requests.clone(),
+        log_session_id,
+    });
+}
+
+/// 记录本轮结束的对比标签页的总用时（含工具调用的多轮请求）。
+pub(crate) fn record_compare_finish(tabs: &mut [TabState], done_tabs: &[usize]) {
+    let finished: Vec<String> = done_tabs
+        .iter()
+        .filter_map(|&idx| tabs.get(idx))
+        .filter(|tab| !tab.app.busy)
+        .map(|tab| tab.conversation_id.clone())
+        .collect();
+    if finished.is_empty() {
+        return;
+    }
+    for tab in tabs.iter_mut() {
+        let Some(compare) = tab.app.compare.as_mut() else {
+            continue;
+        };
+        for entry in &mut compare.entries {
+            if entry.elapsed_ms.is_none() && finished.contains(&entry.conversation_id) {
+                entry.elapsed_ms = Some(entry.started_at.elapsed().as_millis() as u64);
+            }
+        }
+    }
+}
+
+/// 保留第 `column` 列的回答：当前标签页换成该模型的对话并继续使用该模型，其余对比标签页关闭。
+pub(crate) fn keep_compare_answer(tabs: &mut Vec<TabState>, active_tab: &mut usize, column: usize) {
+    let Some(source) = tabs.get_mut(*active_tab) else {
+        return;
+    };
+    let Some(compare) = source.app.compare.take() else {
+        return;
+    };
+    let Some(entry) = compare.entries.get(column).cloned() else {
+        source.app.compare = Some(compare);
+        return;
+    };
+    let Some(chosen_idx) = tabs
+        .iter()
+        .position(|t| t.conversation_id == entry.conversation_id)
+    else {
+        discard_compare_tabs(tabs, active_tab, &compare);
+        if let Some(source) = tabs.get_mut(*active_tab) {
+            push_notice(&mut source.app, "对比标签页已关闭，无法保留该回答");
+        }
+        return;
+    };
+    if tabs[chosen_idx].app.busy {
+        let source = &mut tabs[*active_tab];
+        source.app.compare = Some(compare);
+        push_notice(
+            &mut source.app,
+            format!("{} 仍在生成，请稍候", entry.model_key),
+        );
+        return;
+    }
+    let chosen = &mut tabs[chosen_idx].app;
+    let messages = std::mem::take(&mut chosen.messages);
+    let stats = std::mem::take(&mut chosen.assistant_stats);
+    let usage_log = std::mem::take(&mut chosen.usage_log);
+    discard_compare_tabs(tabs, active_tab, &compare);
+    let Some(source) = tabs.get_mut(*active_tab) else {
+        return;
+    };
+    let app = &mut source.app;
+    app.messages = messages;
+    app.assistant_stats = stats;
+    for record in usage_log {
+        add_usage_totals(app, &record);
+        app.usage_log.push(record);
+    }
+    app.model_key = entry.model_key.clone();
+    app.compare_models.clear();
+    app.follow = true;
+    push_notice(
+        app,
+        format!(
+            "已保留 {} 的回答，后续对话使用该模型（/compare 可再次对比）",
+            entry.model_key
+        ),
+    );
+    source.reset_render_state(source.app.messages.len().saturating_sub(1));
+    autosave_tab(source);
+}
+
+/// 放弃本轮对比：关闭对比标签页，问题放回输入框以便修改后重发。
+pub(crate) fn discard_compare(tabs: &mut Vec<TabState>, active_tab: &mut usize) {
+    let Some(compare) = tabs
+        .get_mut(*active_tab)
+        .and_then(|source| source.app.compare.take())
+    else {
+        return;
+    };
+    discard_compare_tabs(tabs, active_tab, &compare);
+    if let Some(source) = tabs.get_mut(*active_tab) {
+        source.app.input =
+            tui_textarea::TextArea::from(compare.question.lines().map(str::to_string));
+        push_notice(&mut source.app, "已放弃本轮对比");
+    }
+}
+
+fn discard_compare_tabs(tabs: &mut Vec<TabState>, active_tab: &mut usize, compare: &CompareState) {
+    let source_id = tabs
+        .get(*active_tab)
+        .map(|t| t.conversation_id.clone())
+        .unwrap_or_default();
+    let is_compare_tab = |tab: &TabState| {
+        compare
+            .entries
+            .iter()
+            .any(|e| e.conversation_id == tab.conversation_id)
+    };
+    for tab in tabs.iter_mut().filter(|t| is_compare_tab(t)) {
+        stop_stream(&mut tab.app);
+    }
+    tabs.retain(|tab| !is_compare_tab(tab));
+    *active_tab = tabs
+        .iter()
+        .position(|t| t.conversation_id == source_id)
+        .unwrap_or(0);
+}
@@ -0,0 +1,445 @@
+//! `delegate_task`：把子任务交给子对话
+//!
+//! 工具调用只记录 [`DelegateRequest`]，由运行循环新建子对话标签页并发起请求。子对话给出最终回复后，
+//! 等待模式把回复作为工具结果交还父对话并继续本轮工具循环；不等待模式在父对话空闲时把结果作为新消息发回。
+
+use crate::args::{Args, ToolFlags};
+use crate::framework::widget_system::interaction::text_utils::truncate_to_width;
+use crate::llm::prompts::PromptRegistry;
+use crate::model_registry::{ModelProfile, ModelRegistry};
+use crate::services::runtime_agent_guard::effective_limits;
+use crate::services::runtime_requests::{
+    StartTabRequestParams, start_followup, start_tab_request, turn_in_progress,
+};
+use crate::services::runtime_tools::effective_tools;
+use crate::types::{Message, ROLE_ASSISTANT, ROLE_SYSTEM, ROLE_TOOL, ToolCall};
+use crate::ui::events::RuntimeEvent;
+use crate::ui::notice::{push_long_notice, push_notice};
+use crate::ui::runtime_helpers::TabState;
+use crate::ui::state::{App, DelegateRequest, Delegation, DelegationLink};
+use serde::Deserialize;
+use std::sync::mpsc;
+use std::time::Instant;
+
+const DELEGATE_SYSTEM_SUFFIX: &str = "你正在独立完成另一个对话交给你的子任务，用户不会参与。请直接完成任务，不要向用户提问；最后一条回复会原样作为结果交回，请在其中给出完整的结论。";
+
+#[derive(Deserialize)]
+struct DelegateArgs {
+    task: String,
+    model: Option<String>,
+    prompt: Option<String>,
+    tools: Option<Vec<String>>,
+    wait: Option<bool>,
+}
+
+pub(crate) struct DelegationParams<'a> {
+    pub tabs: &'a mut Vec<TabState>,
+    pub registry: &'a ModelRegistry,
+    pub prompt_registry: &'a PromptRegistry,
+    pub args: &'a Args,
+    pub tx: &'a mpsc::Sender<RuntimeEvent>,
+}
+
+/// 解析 `delegate_task` 的参数并记录待派出的子任务；返回是否需要等待结果。
+pub(crate) fn handle_delegate_request(app: &mut App, call: &ToolCall) -> Result<bool, String> {
+    let args: DelegateArgs = serde_json::from_str(&call.function.arguments)
+        .map_err(|e| format!("delegate_task 参数解析失败：{e}"))?;
+    let task = args.task.trim().to_string();
+    if task.is_empty() {
+        return Err("delegate_task 的 task 不能为空".to_string());
+    }
+    let max_depth = effective_limits(app).max_delegation_depth;
+    if delegation_depth(app) >= max_depth {
+        return Err(format!("子任务嵌套已达上限（{max_depth} 层）"));
+    }
+    let wait = args.wait.unwrap_or(true);
+    app.pending_delegations.push(DelegateRequest {
+        call_id: call.id.clone(),
+        task,
+        model: args.model.filter(|m| !m.trim().is_empty()),
+        prompt: args.prompt.filter(|p| !p.trim().is_empty()),
+        tools: args.tools,
+        wait,
+    });
+    Ok(wait)
+}
+
+fn delegation_depth(app: &App) -> u32 {
+    app.delegated_by.as_ref().map_or(0, |link| link.depth)
+}
+
+/// 父对话正在等待子任务的结果；本轮刚记录、尚未派出的等待子任务也算在内。
+pub(crate) fn waiting_for_delegations(app: &App) -> bool {
+    app.delegations.iter().any(|d| d.wait) || app.pending_delegations.iter().any(|d| d.wait)
+}
+
+/// 为本轮记录的子任务新建子对话并发起请求；有需要等待的子任务时父对话保持忙碌。
+pub(crate) fn spawn_pending_delegations(mut params: DelegationParams<'_>) {
+    for idx in 0..params.tabs.len() {
+        let requests = std::mem::take(&mut params.tabs[idx].app.pending_delegations);
+        if requests.is_empty() {
+            continue;
+        }
+        for request in requests {
+            let result = spawn_child(&mut params, idx, &request);
+            record_delegation(&mut params.tabs[idx].app, request, result);
+        }
+        let app = &mut params.tabs[idx].app;
+        if waiting_for_delegations(app) {
+            app.busy = true;
+            app.busy_since = Some(Instant::now());
+        }
+    }
+}
+
+fn record_delegation(app: &mut App, request: DelegateRequest, spawned: Result<String, String>) {
+    match spawned {
+        Ok(conversation_id) => app.delegations.push(Delegation {
+            call_id: request.call_id,
+            task: request.task,
+            conversation_id,
+            wait: request.wait,
+            result: None,
+        }),
+        Err(err) if request.wait => app.delegations.push(Delegation {
+            call_id: request.call_id,
+            task: request.task,
+            conversation_id: String::new(),
+            wait: true,
+            result: Some(Err(err)),
+        }),
+        Err(err) => push_notice(app, format!("子任务未能开始：{err}")),
+    }
+}
+
+fn spawn_child(
+    params: &mut DelegationParams<'_>,
+    parent_idx: usize,
+    request: &DelegateRequest,
+) -> Result<String, String> {
+    let child = child_tab(
+        &params.tabs[parent_idx],
+        request,
+        params.registry,
+        params.prompt_registry,
+    )?;
+    let conversation_id = child.conversation_id.clone();
+    params.tabs.push(child);
+    let tab_state = params.tabs.last_mut().expect("child tab");
+    let model = model_for(&tab_state.app, params.registry);
+    start_tab_request(tab_request_params(
+        tab_state,
+        &request.task,
+        model,
+        params.args,
+        params.tx,
+    ));
+    Ok(conversation_id)
+}
+
+fn child_tab(
+    parent: &TabState,
+    request: &DelegateRequest,
+    registry: &ModelRegistry,
+    prompt_registry: &PromptRegistry,
+) -> Result<TabState, String> {
+    let model_key = match &request.model {
+        Some(key) if registry.get(key).is_some() => key.clone(),
+        Some(key) => return Err(format!("未知模型：{key}")),
+        None => parent.app.model_key.clone(),
+    };
+    let (prompt_key, system) = child_prompt(parent, request, prompt_registry)?;
+    let tools = child_tools(parent, request)?;
+    let conv_id =
+        crate::conversation::new_conversation_id().map_err(|e| format!("新建子对话失败：{e}"))?;
+    let system = format!("{system}\n\n{DELEGATE_SYSTEM_SUFFIX}");
+    let mut tab = TabState::new(
+        conv_id,
+        parent.category.clone(),
+        system.trim_start(),
+        false,
+        &model_key,
+        &prompt_key,
+    );
+    tab.app.set_log_session_id(&parent.app.log_session_id);
+    tab.app.prompts_dir = parent.app.prompts_dir.clone();
+    tab.app.tavily_api_key = parent.app.tavily_api_key.clone();
+    tab.app.hooks = parent.app.hooks.clone();
+    tab.app.agent_limits = parent.app.agent_limits;
+    tab.app.tool_flags = Some(tools);
+    tab.app.delegated_by = Some(DelegationLink {
+        parent_id: parent.conversation_id.clone(),
+        depth: delegation_depth(&parent.app) + 1,
+    });
+    Ok(tab)
+}
+
+/// 指定了提示词时使用它，否则沿用父对话的系统提示词。
+fn child_prompt(
+    parent: &TabState,
+    request: &DelegateRequest,
+    prompt_registry: &PromptRegistry,
+) -> Result<(String, String), String> {
+    if let Some(key) = &request.prompt {
+        let prompt = prompt_registry
+            .get(key)
+            .ok_or_else(|| format!("提示词不存在：{key}"))?;
+        return Ok((prompt.key.clone(), prompt.content.clone()));
+    }
+    let system = parent
+        .app
+        .messages
+        .iter()
+        .find(|m| m.role == ROLE_SYSTEM)
+        .map(|m| m.content.clone())
+        .unwrap_or_default();
+    Ok((parent.app.prompt_key.clone(), system))
+}
+
+/// 子对话的工具只能是父对话已开启工具的子集；嵌套到上限后不再开放 delegate_task。
+fn child_tools(parent: &TabState, request: &DelegateRequest) -> Result<ToolFlags, String> {
+    let allowed = effective_tools(&parent.app);
+    let mut tools = match &request.tools {
+        None => allowed,
+        Some(names) => {
+            let mut tools = ToolFlags::default();
+            for name in names {
+                if !ToolFlags::NAMES.contains(&name.as_str()) {
+                    return Err(format!("未知的工具：{name}"));
+                }
+                tools.set(name, allowed.get(name));
+            }
+            tools
+        }
+    };
+    let depth = delegation_depth(&parent.app) + 1;
+    tools.delegate_task &= depth < effective_limits(&parent.app).max_delegation_depth;
+    Ok(tools)
+}
+
+/// 收集结束的子对话的回复；等待中的子任务全部返回后继续父对话，不等待的结果在父对话空闲时发回。
+pub(crate) fn settle_delegations(params: DelegationParams<'_>, done_tabs: &[usize]) {
+    collect_child_results(params.tabs, done_tabs);
+    mark_closed_children(params.tabs);
+    for idx in 0..params.tabs.len() {
+        if params.tabs[idx].app.delegations.is_empty() {
+            continue;
+        }
+        if !resume_waiting_parent(
+            &mut params.tabs[idx],
+            params.registry,
+            params.args,
+            params.tx,
+        ) {
+            deliver_background_results(
+                &mut params.tabs[idx],
+                params.registry,
+                params.args,
+                params.tx,
+            );
+        }
+    }
+}
+
+/// 子对话还有工具等待确认、在等自己的子任务或被护栏暂停时，本轮尚未结束，先不取回复。
+fn collect_child_results(tabs: &mut [TabState], done_tabs: &[usize]) {
+    for &idx in done_tabs {
+        let Some(child) = tabs.get(idx) else {
+            continue;
+        };
+        let Some(link) = child.app.delegated_by.as_ref() else {
+            continue;
+        };
+        if turn_in_progress(&child.app) || child.app.agent_turn.paused.is_some() {
+            continue;
+        }
+        let (child_id, parent_id) = (child.conversation_id.clone(), link.parent_id.clone());
+        let answer = final_answer(&child.app);
+        let Some(parent) = tabs.iter_mut().find(|t| t.conversation_id == parent_id) else {
+            continue;
+        };
+        if let Some(entry) = parent
+            .app
+            .delegations
+            .iter_mut()
+            .find(|d| d.conversation_id == child_id && d.result.is_none())
+        {
+            entry.result = Some(answer);
+        }
+    }
+}
+
+fn final_answer(app: &App) -> Result<String, String> {
+    app.messages
+        .iter()
+        .rev()
+        .find(|m| m.role == ROLE_ASSISTANT && m.tool_calls.is_none())
+        .map(|m| m.content.trim().to_string())
+        .filter(|content| !content.is_empty())
+        .ok_or_else(|| "子对话没有给出回复".to_string())
+}
+
+fn mark_closed_children(tabs: &mut [TabState]) {
+    let open: Vec<String> = tabs.iter().map(|t| t.conversation_id.clone()).collect();
+    for tab in tabs.iter_mut() {
+        for entry in &mut tab.app.delegations {
+            if entry.result.is_none() && !open.contains(&entry.conversation_id) {
+                entry.result = Some(Err("子对话已关闭".to_string()));
+            }
+        }
+    }
+}
+
+/// 等待的子任务全部有结果时写入工具结果并继续；返回 false 表示没有需要等待的子任务。
+fn resume_waiting_parent(
+    tab_state: &mut TabState,
+    registry: &ModelRegistry,
+    args: &Args,
+    tx: &mpsc::Sender<RuntimeEvent>,
+) -> bool {
+    let app = &mut tab_state.app;
+    if !waiting_for_delegations(app) {
+        return false;
+    }
+    if app.delegations.iter().any(|d| d.wait && d.result.is_none()) {
+        return true;
+    }
+    let (finished, running): (Vec<_>, Vec<_>) = std::mem::take(&mut app.delegations)
+        .into_iter()
+        .partition(|d| d.wait);
+    app.delegations = running;
+    for entry in finished {
+        let result = entry
+            .result
+            .unwrap_or_else(|| Err("子任务没有结果".to_string()));
+        let content = tool_result_json(result);
+        push_tool_result(app, &entry.call_id, content);
+    }
+    app.busy = false;
+    app.busy_since = None;
+    start_followup(tab_state, registry, args, tx);
+    true
+}
+
+fn tool_result_json(result: Result<String, String>) -> String {
+    let value = match result {
+        Ok(answer) => serde_json::json!({ "ok": true, "answer": answer }),
+        Err(err) => serde_json::json!({ "error": err }),
+    };
+    value.to_string()
+}
+
+fn push_tool_result(app: &mut App, call_id: &str, content: String) {
+    let idx = app.messages.len();
+    app.messages.push(Message {
+        role: ROLE_TOOL.to_string(),
+        content,
+        tool_call_id: Some(call_id.to_string()),
+        tool_calls: None,
+        attachments: Vec::new(),
+    });
+    app.dirty_indices.push(idx);
+}
+
+/// 同一轮中还有代码执行、文件修改或问题审核等待确认时，由那边确认后继续。
+pub(crate) fn has_pending_approval(app: &App) -> bool {
+    app.pending_code_exec.is_some()
+        || app.pending_file_patch.is_some()
+        || app.pending_question_review.is_some()
+}
+
+/// 父对话空闲时，把已完成的后台子任务结果作为一条新消息发回。
+fn deliver_background_results(
+    tab_state: &mut TabState,
+    registry: &ModelRegistry,
+    args: &Args,
+    tx: &mpsc::Sender<RuntimeEvent>,
+) {
+    let app = &mut tab_state.app;
+    if turn_in_progress(app) || app.agent_turn.paused.is_some() {
+        return;
+    }
+    let (finished, running): (Vec<_>, Vec<_>) = std::mem::take(&mut app.delegations)
+        .into_iter()
+        .partition(|d| d.result.is_some());
+    app.delegations = running;
+    if finished.is_empty() {
+        return;
+    }
+    let text = background_results_message(finished);
+    push_long_notice(app, "后台子任务已完成，结果已发回本对话");
+    let model = model_for(&tab_state.app, registry);
+    start_tab_request(tab_request_params(tab_state, &text, model, args, tx));
+}
+
+fn background_results_message(finished: Vec<Delegation>) -> String {
+    finished
+        .into_iter()
+        .filter_map(|entry| {
+            let title = truncate_to_width(entry.task.lines().next().unwrap_or_default(), 40);
+            match entry.result? {
+                Ok(answer) => Some(format!("[后台子任务已完成：{title}]\n{answer}")),
+                Err(err) => Some(format!("[后台子任务失败：{title}] {err}")),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// F6 在等待子任务时放弃等待：子对话继续运行，父对话收到失败结果后可继续输入。
+pub(crate) fn stop_waiting_delegations(app: &mut App) -> bool {
+    if !waiting_for_delegations(app) {
+        return false;
+    }
+    let (waiting, running): (Vec<_>, Vec<_>) = std::mem::take(&mut app.delegations)
+        .into_iter()
+        .partition(|d| d.wait);
+    app.delegations = running;
+    for entry in waiting {
+        let result = entry
+            .result
+            .unwrap_or_else(|| Err("用户停止了等待，子任务仍在子对话中运行".to_string()));
+        push_tool_result(app, &entry.call_id, tool_result_json(result));
+    }
+    app.busy = false;
+    app.busy_since = None;
+    push_notice(app, "已停止等待子任务");
+    true
+}
+
+fn model_for<'a>(app: &App, registry: &'a ModelRegistry) -> &'a ModelProfile {
+    registry
+        .get(&app.model_key)
+        .unwrap_or_else(|| registry.get(&registry.default_key).expect("model"))
+}
+
+fn tab_request_params<'a>(
+    tab_state: &'a mut TabState,
+    question: &'a str,
+    model: &'a ModelProfile,
+    args: &'a Args,
+    tx: &'a mpsc::Sender<RuntimeEvent>,
+) -> StartTabRequestParams<'a> {
+    let log_session_id = tab_state.app.log_session_id.clone();
+    StartTabRequestParams {
+        tab_state,
+        question,
+        base_url: &model.base_url,
+        api_key: &model.api_key,
+        model: &model.model,
+        max_tokens: model.max_tokens,
+        context_window: model.context_window,
+        reasoning: model.reasoning,
+        timeouts: model.timeouts,
+        tx,
+        enable_web_search: args.web_search_enabled(),
+        enable_code_exec: args.code_exec_enabled(),
+        enable_read_file: args.read_file_enabled(),
+        enable_read_code: args.read_code_enabled(),
+        enable_modify_file: args.modify_file_enabled(),
+        enable_ask_questions: args.ask_questions_enabled(),
+        enable_delegate_task: args.delegate_task_enabled(),
+        log_requests: args.log_requests.clone(),
+        log_session_id,
+    }
+}
@@ -1,11 +1,13 @@
 use crate::args::Args;
 use crate::types::Message;
 use crate::services::code_exec_container::ensure_container_cached;
+use crate::services::code_exec_container_env::host_backend_enabled;
+use crate::services::host_sandbox::host_command;
 use crate::ui::events::RuntimeEvent;
 use crate::ui::runtime_helpers::TabState;
-use crate::services::runtime_requests::start_followup_request;
+use crate::services::runtime_requests::start_followup;
 use crate::ui::state::PendingFilePatch;
-use crate::services::workspace::resolve_workspace;
+use crate::services::workspace::{WorkspaceConfig, resolve_workspace};
 use std::io::Write;
 use std::process::{Command, Stdio};
 use std::sync::mpsc;
@@ -100,6 +102,7 @@ fn push_tool_message(app: &mut crate::ui::state::App, content: String, call_id:
         content,
         tool_call_id: Some(call_id),
         tool_calls: None,
+        attachments: Vec::new(),
     });
     app.dirty_indices.push(idx);
 }
@@ -111,35 +114,6 @@ fn reset_patch_ui(app: &mut crate::ui::state::App) {
     app.file_patch_selection = None;
 }
 
-fn start_followup(
-    tab_state: &mut TabState,
-    registry: &crate::model_registry::ModelRegistry,
-    args: &Args,
-    tx: &mpsc::Sender<RuntimeEvent>,
-) {
-    let model = registry
-        .get(&tab_state.app.model_key)
-        .unwrap_or_else(|| registry.get(&registry.default_key).expect("model"));
-    let log_session_id = tab_state.app.log_session_id.clone();
-    start_followup_request(crate::services::runtime_requests::StartFollowupRequestParams {
-        tab_state,
-        base_url: &model.base_url,
-        api_key: &model.api_key,
-        model: &model.model,
-        max_tokens: model.max_tokens,
-        show_reasoning: args.show_reasoning,
-        tx,
-        enable_web_search: args.web_search_enabled(),
-        enable_code_exec: args.code_exec_enabled(),
-        enable_read_file: args.read_file_enabled(),
-        enable_read_code: args.read_code_enabled(),
-        enable_modify_file: args.modify_file_enabled(),
-        enable_ask_questions: args.ask_questions_enabled(),
-        log_requests: args.log_requests.clone(),
-        log_session_id,
-    });
-}
-
 fn render_diff_preview(diff: &str) -> String {
     let delta = Command::new("delta")
         .arg("--no-color")
@@ -166,10 +140,27 @@ fn render_diff_preview(diff: &str) -> String {
 
 fn apply_patch(diff: &str, args: &Args) -> Result<(), String> {
     let workspace = resolve_workspace(args)?;
+    if host_backend_enabled() {
+        return run_host_patch(&workspace, diff);
+    }
     let container_id = ensure_container_cached(&workspace)?;
     run_container_patch(&container_id, diff)
 }
 
+fn run_host_patch(workspace: &WorkspaceConfig, diff: &str) -> Result<(), String> {
+    let mut cmd = host_command("git", &["apply", "--whitespace=nowarn", "-p1"], Some(workspace));
+    cmd.current_dir(workspace.mount_source())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let mut child = cmd.spawn().map_err(|e| format!("应用补丁失败：{e}"))?;
+    write_patch_input(&mut child, diff)?;
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("应用补丁失败：{e}"))?;
+    handle_patch_output(output)
+}
+
 fn run_container_patch(container_id: &str, diff: &str) -> Result<(), String> {
     let mut child = spawn_patch_process(container_id)?;
     write_patch_input(&mut child, diff)?;
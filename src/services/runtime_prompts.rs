@@ -0,0 +1,174 @@
+//! 在界面中管理系统提示词
+//!
+//! F5 提示词列表与 `/prompt` 命令只记录 [`PromptOp`]，由运行循环调用这里执行文件操作、重新加载提示词列表，
+//! 并同步到所有打开的标签页：尚未开始对话的标签页换上新内容，重命名后沿用新名称。
+
+use crate::llm::prompts::PromptRegistry;
+use crate::types::ROLE_SYSTEM;
+use crate::ui::notice::{push_long_notice, push_notice};
+use crate::ui::runtime_helpers::TabState;
+use crate::ui::state::{App, Focus, PromptOp};
+
+const PROMPT_USAGE: &str = "用法：/prompt new <名称> | edit <名称> | dup <名称> [新名称] | rename <名称> <新名称> | delete <名称> | reload";
+
+/// `/prompt <子命令> ...`
+pub(crate) fn handle_prompt_command(app: &mut App, arg: &str) {
+    match parse_prompt_command(arg) {
+        Some(op) => app.pending_prompt_op = Some(op),
+        None => push_notice(app, PROMPT_USAGE),
+    }
+}
+
+fn parse_prompt_command(arg: &str) -> Option<PromptOp> {
+    let parts: Vec<&str> = arg.split_whitespace().collect();
+    let key = |idx: usize| parts.get(idx).map(|s| s.to_string());
+    let op = match (parts.first().copied()?, parts.len()) {
+        ("new", 2) => PromptOp::New { key: key(1)? },
+        ("edit", 2) => PromptOp::Edit { key: key(1)? },
+        ("dup", 2 | 3) => PromptOp::Duplicate {
+            key: key(1)?,
+            new_key: key(2),
+        },
+        ("rename", 3) => PromptOp::Rename {
+            key: key(1)?,
+            new_key: key(2)?,
+        },
+        ("delete", 2) => PromptOp::Delete { key: key(1)? },
+        ("reload", 1) => PromptOp::Reload,
+        _ => return None,
+    };
+    Some(op)
+}
+
+/// 执行文件操作并重新加载；`EditExternal` 需要让出终端，由运行循环自行处理。
+pub(crate) fn apply_prompt_op(
+    op: PromptOp,
+    registry: &mut PromptRegistry,
+    tabs: &mut [TabState],
+    active_tab: usize,
+) {
+    let Some(tab_state) = tabs.get_mut(active_tab) else {
+        return;
+    };
+    if let PromptOp::Edit { key } = &op {
+        start_inline_edit(&mut tab_state.app, registry, key);
+        return;
+    }
+    let result = run_file_op(&op, registry, &tab_state.app);
+    finish_prompt_op(result, &op, registry, tabs, active_tab);
+}
+
+fn run_file_op(op: &PromptOp, registry: &PromptRegistry, app: &App) -> Result<String, String> {
+    match op {
+        PromptOp::New { key } => {
+            registry.create(key, &current_system_prompt(app))?;
+            Ok(format!("已用当前系统提示词新建：{key}"))
+        }
+        PromptOp::Save { key, content } => {
+            let path = registry.save(key, content)?;
+            Ok(format!("已保存提示词：{}", path.display()))
+        }
+        PromptOp::Duplicate { key, new_key } => {
+            let new_key = registry.duplicate(key, new_key.as_deref())?;
+            Ok(format!("已复制为：{new_key}"))
+        }
+        PromptOp::Rename { key, new_key } => {
+            registry.rename(key, new_key)?;
+            Ok(format!("已重命名：{key} → {new_key}"))
+        }
+        PromptOp::Delete { key } => {
+            registry.delete(key)?;
+            Ok(format!("已删除提示词：{key}"))
+        }
+        PromptOp::Reload | PromptOp::Edit { .. } | PromptOp::EditExternal { .. } => {
+            Ok("已重新加载提示词".to_string())
+        }
+    }
+}
+
+/// 文件操作成功后重新加载提示词并同步各标签页，结果提示显示在当前标签页。
+pub(crate) fn finish_prompt_op(
+    result: Result<String, String>,
+    op: &PromptOp,
+    registry: &mut PromptRegistry,
+    tabs: &mut [TabState],
+    active_tab: usize,
+) {
+    let text = match result.and_then(|text| registry.reload().map(|_| text)) {
+        Ok(text) => {
+            sync_open_tabs(op, registry, tabs, active_tab);
+            match registry.skipped_notice() {
+                Some(skipped) => format!("{text}；{skipped}"),
+                None => text,
+            }
+        }
+        Err(err) => err,
+    };
+    if let Some(tab_state) = tabs.get_mut(active_tab) {
+        push_notice(&mut tab_state.app, text);
+    }
+}
+
+fn sync_open_tabs(
+    op: &PromptOp,
+    registry: &PromptRegistry,
+    tabs: &mut [TabState],
+    active_tab: usize,
+) {
+    for (idx, tab_state) in tabs.iter_mut().enumerate() {
+        let app = &mut tab_state.app;
+        match op {
+            PromptOp::Rename { key, new_key } if &app.prompt_key == key => {
+                app.prompt_key = new_key.clone();
+            }
+            PromptOp::New { key } if idx == active_tab => app.prompt_key = key.clone(),
+            _ => {}
+        }
+        if crate::framework::widget_system::runtime_dispatch::can_change_prompt(app)
+            && let Some(prompt) = registry.get(&app.prompt_key)
+        {
+            let (key, content) = (prompt.key.clone(), prompt.content.clone());
+            app.set_system_prompt(&key, &content);
+        }
+    }
+}
+
+fn current_system_prompt(app: &App) -> String {
+    app.messages
+        .iter()
+        .find(|m| m.role == ROLE_SYSTEM)
+        .map(|m| m.content.clone())
+        .unwrap_or_default()
+}
+
+/// 把提示词原文（含 front-matter）载入输入框；Enter 保存，清空后 Enter 放弃。
+fn start_inline_edit(app: &mut App, registry: &PromptRegistry, key: &str) {
+    let Some(prompt) = registry.get(key) else {
+        push_notice(app, format!("提示词不存在：{key}"));
+        return;
+    };
+    if app.busy {
+        push_notice(app, "正在生成回复，稍后再编辑提示词");
+        return;
+    }
+    if app.input.lines().iter().any(|line| !line.is_empty()) {
+        push_notice(app, "输入框中还有内容，请先发送或清空（Ctrl+U）");
+        return;
+    }
+    app.input = tui_textarea::TextArea::from(prompt.content.lines().map(str::to_string));
+    app.editing_prompt = Some(key.to_string());
+    app.focus = Focus::Input;
+    push_long_notice(
+        app,
+        format!("正在编辑提示词 {key}：Enter 保存，Ctrl+J 换行，清空后 Enter 放弃"),
+    );
+}
+
+/// 内置编辑器提交：保存内容或放弃编辑。
+pub(crate) fn submit_inline_edit(app: &mut App, key: String, content: String) {
+    if content.trim().is_empty() {
+        push_notice(app, format!("已放弃编辑提示词：{key}"));
+        return;
+    }
+    app.pending_prompt_op = Some(PromptOp::Save { key, content });
+}
@@ -7,7 +7,7 @@ use crate::types::Message;
 use crate::ui::events::RuntimeEvent;
 use crate::ui::notice::push_notice;
 use crate::ui::runtime_helpers::TabState;
-use crate::services::runtime_requests::start_followup_request;
+use crate::services::runtime_requests::start_followup;
 use crate::ui::state::{PendingQuestionItem, PendingQuestionReview, QuestionDecision};
 use std::sync::mpsc;
 
@@ -152,47 +152,7 @@ fn push_tool_message(app: &mut crate::ui::state::App, content: String, call_id:
         content,
         tool_call_id: Some(call_id),
         tool_calls: None,
+        attachments: Vec::new(),
     });
     app.dirty_indices.push(idx);
 }
-
-fn start_followup(
-    tab_state: &mut TabState,
-    registry: &crate::model_registry::ModelRegistry,
-    args: &Args,
-    tx: &mpsc::Sender<RuntimeEvent>,
-) {
-    let Some(params) = build_followup_params(tab_state, registry, args, tx) else {
-        return;
-    };
-    start_followup_request(params);
-}
-
-fn build_followup_params<'a>(
-    tab_state: &'a mut TabState,
-    registry: &'a crate::model_registry::ModelRegistry,
-    args: &'a Args,
-    tx: &'a mpsc::Sender<RuntimeEvent>,
-) -> Option<crate::services::runtime_requests::StartFollowupRequestParams<'a>> {
-    let model = registry.get(&tab_state.app.model_key).unwrap_or_else(|| {
-        registry.get(&registry.default_key).expect("model")
-    });
-    let log_session_id = tab_state.app.log_session_id.clone();
-    Some(crate::services::runtime_requests::StartFollowupRequestParams {
-        tab_state,
-        base_url: &model.base_url,
-        api_key: &model.api_key,
-        model: &model.model,
-        max_tokens: model.max_tokens,
-        show_reasoning: args.show_reasoning,
-        tx,
-        enable_web_search: args.web_search_enabled(),
-        enable_code_exec: args.code_exec_enabled(),
-        enable_read_file: args.read_file_enabled(),
-        enable_read_code: args.read_code_enabled(),
-        enable_modify_file: args.modify_file_enabled(),
-        enable_ask_questions: args.ask_questions_enabled(),
-        log_requests: args.log_requests.clone(),
-        log_session_id,
-    })
-}
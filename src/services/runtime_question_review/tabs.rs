@@ -225,7 +225,9 @@ fn tab_request_params<'a>(
         api_key: &model.api_key,
         model: &model.model,
         max_tokens: model.max_tokens,
-        show_reasoning: args.show_reasoning,
+        context_window: model.context_window,
+        reasoning: model.reasoning,
+        timeouts: model.timeouts,
         tx,
         enable_web_search: args.web_search_enabled(),
         enable_code_exec: args.code_exec_enabled(),
@@ -233,6 +235,7 @@ fn tab_request_params<'a>(
         enable_read_code: args.read_code_enabled(),
         enable_modify_file: args.modify_file_enabled(),
         enable_ask_questions: args.ask_questions_enabled(),
+        enable_delegate_task: args.delegate_task_enabled(),
         log_requests: args.log_requests.clone(),
         log_session_id,
     }
@@ -1,7 +1,18 @@
-use crate::types::{Message, ROLE_ASSISTANT, ROLE_USER};
+use crate::args::Args;
+use crate::llm::context_budget::{ContextBudget, context_report, system_prompt_tokens};
+use crate::llm::prompts::{PromptMeta, parse_front_matter};
+use crate::llm::reasoning::ReasoningSettings;
+use crate::llm::templates::{PromptVars, RigTemplates};
+use crate::llm::timeouts::StreamTimeouts;
+use crate::model_registry::ModelRegistry;
+use crate::services::runtime_agent_guard::{begin_turn, pause_if_limited};
+use crate::services::runtime_delegation::{has_pending_approval, waiting_for_delegations};
+use crate::services::runtime_tools::effective_tools;
+use crate::services::workspace::cached_workspace_root;
+use crate::types::{Message, ROLE_ASSISTANT, ROLE_SYSTEM, ROLE_USER};
 use crate::ui::events::RuntimeEvent;
 use crate::ui::runtime_helpers::TabState;
-use crate::ui::state::{App, Focus, RequestHandle};
+use crate::ui::state::{App, CompactionState, Focus, RequestHandle};
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::mpsc;
@@ -15,7 +26,9 @@ pub(crate) struct StartTabRequestParams<'a> {
     pub api_key: &'a str,
     pub model: &'a str,
     pub max_tokens: Option<u64>,
-    pub show_reasoning: bool,
+    pub context_window: Option<u64>,
+    pub reasoning: ReasoningSettings,
+    pub timeouts: StreamTimeouts,
     pub tx: &'a mpsc::Sender<RuntimeEvent>,
     pub enable_web_search: bool,
     pub enable_code_exec: bool,
@@ -23,6 +36,7 @@ pub(crate) struct StartTabRequestParams<'a> {
     pub enable_read_code: bool,
     pub enable_modify_file: bool,
     pub enable_ask_questions: bool,
+    pub enable_delegate_task: bool,
     pub log_requests: Option<String>,
     pub log_session_id: String,
 }
@@ -34,23 +48,29 @@ pub(crate) fn start_tab_request(params: StartTabRequestParams<'_>) {
     if !push_user_message(app, params.question) {
         return;
     }
+    begin_turn(app);
     start_request_common(StartRequestCommonParams {
         app,
         base_url: params.base_url,
         api_key: params.api_key,
         model: params.model,
         max_tokens: params.max_tokens,
-        show_reasoning: params.show_reasoning,
+        context_window: params.context_window,
+        reasoning: params.reasoning,
+        timeouts: params.timeouts,
         tx: params.tx,
         tab_id,
+        category: params.tab_state.category.clone(),
         enable_web_search: params.enable_web_search,
         enable_code_exec: params.enable_code_exec,
         enable_read_file: params.enable_read_file,
         enable_read_code: params.enable_read_code,
         enable_modify_file: params.enable_modify_file,
         enable_ask_questions: params.enable_ask_questions,
+        enable_delegate_task: params.enable_delegate_task,
         log_requests: params.log_requests,
         log_session_id: params.log_session_id,
+        compact: None,
     });
 }
 
@@ -60,7 +80,9 @@ pub(crate) struct StartFollowupRequestParams<'a> {
     pub api_key: &'a str,
     pub model: &'a str,
     pub max_tokens: Option<u64>,
-    pub show_reasoning: bool,
+    pub context_window: Option<u64>,
+    pub reasoning: ReasoningSettings,
+    pub timeouts: StreamTimeouts,
     pub tx: &'a mpsc::Sender<RuntimeEvent>,
     pub enable_web_search: bool,
     pub enable_code_exec: bool,
@@ -68,12 +90,16 @@ pub(crate) struct StartFollowupRequestParams<'a> {
     pub enable_read_code: bool,
     pub enable_modify_file: bool,
     pub enable_ask_questions: bool,
+    pub enable_delegate_task: bool,
     pub log_requests: Option<String>,
     pub log_session_id: String,
 }
 
-pub(crate) fn start_followup_request(params: StartFollowupRequestParams<'_>) {
+fn start_followup_request(params: StartFollowupRequestParams<'_>) {
     let app = &mut params.tab_state.app;
+    if pause_if_limited(app) {
+        return;
+    }
     let tab_id = params.tab_state.conversation_id.clone();
     cancel_active_request(app);
     start_request_common(StartRequestCommonParams {
@@ -82,17 +108,102 @@ pub(crate) fn start_followup_request(params: StartFollowupRequestParams<'_>) {
         api_key: params.api_key,
         model: params.model,
         max_tokens: params.max_tokens,
-        show_reasoning: params.show_reasoning,
+        context_window: params.context_window,
+        reasoning: params.reasoning,
+        timeouts: params.timeouts,
         tx: params.tx,
         tab_id,
+        category: params.tab_state.category.clone(),
         enable_web_search: params.enable_web_search,
         enable_code_exec: params.enable_code_exec,
         enable_read_file: params.enable_read_file,
         enable_read_code: params.enable_read_code,
         enable_modify_file: params.enable_modify_file,
         enable_ask_questions: params.enable_ask_questions,
+        enable_delegate_task: params.enable_delegate_task,
         log_requests: params.log_requests,
         log_session_id: params.log_session_id,
+        compact: None,
+    });
+}
+
+/// 工具结果写回后继续本轮工具循环。同一轮还有工具等待确认或子任务未返回时先不请求，
+/// 由最后完成的一方继续，避免把缺少工具结果的历史发给模型。
+pub(crate) fn start_followup(
+    tab_state: &mut TabState,
+    registry: &ModelRegistry,
+    args: &Args,
+    tx: &mpsc::Sender<RuntimeEvent>,
+) {
+    if has_pending_approval(&tab_state.app) || waiting_for_delegations(&tab_state.app) {
+        return;
+    }
+    let model = registry
+        .get(&tab_state.app.model_key)
+        .unwrap_or_else(|| registry.get(&registry.default_key).expect("model"));
+    let log_session_id = tab_state.app.log_session_id.clone();
+    start_followup_request(StartFollowupRequestParams {
+        tab_state,
+        base_url: &model.base_url,
+        api_key: &model.api_key,
+        model: &model.model,
+        max_tokens: model.max_tokens,
+        context_window: model.context_window,
+        reasoning: model.reasoning,
+        timeouts: model.timeouts,
+        tx,
+        enable_web_search: args.web_search_enabled(),
+        enable_code_exec: args.code_exec_enabled(),
+        enable_read_file: args.read_file_enabled(),
+        enable_read_code: args.read_code_enabled(),
+        enable_modify_file: args.modify_file_enabled(),
+        enable_ask_questions: args.ask_questions_enabled(),
+        enable_delegate_task: args.delegate_task_enabled(),
+        log_requests: args.log_requests.clone(),
+        log_session_id,
+    });
+}
+
+/// 本轮尚未结束：请求进行中、有工具等待确认或在等待子任务的结果。
+pub(crate) fn turn_in_progress(app: &App) -> bool {
+    app.busy || has_pending_approval(app) || waiting_for_delegations(app)
+}
+
+/// 压缩请求：只把 `keep_from` 之前的历史连同摘要指令发给模型，不暴露任何工具。
+pub(crate) struct CompactRequest {
+    pub keep_from: usize,
+    pub instruction: String,
+}
+
+pub(crate) fn start_compact_request(
+    params: StartFollowupRequestParams<'_>,
+    compact: CompactRequest,
+) {
+    let app = &mut params.tab_state.app;
+    let tab_id = params.tab_state.conversation_id.clone();
+    cancel_active_request(app);
+    start_request_common(StartRequestCommonParams {
+        app,
+        base_url: params.base_url,
+        api_key: params.api_key,
+        model: params.model,
+        max_tokens: params.max_tokens,
+        context_window: params.context_window,
+        reasoning: params.reasoning,
+        timeouts: params.timeouts,
+        tx: params.tx,
+        tab_id,
+        category: params.tab_state.category.clone(),
+        enable_web_search: false,
+        enable_code_exec: false,
+        enable_read_file: false,
+        enable_read_code: false,
+        enable_modify_file: false,
+        enable_ask_questions: false,
+        enable_delegate_task: false,
+        log_requests: params.log_requests,
+        log_session_id: params.log_session_id,
+        compact: Some(compact),
     });
 }
 
@@ -112,7 +223,9 @@ fn push_user_message(app: &mut App, question: &str) -> bool {
     let Some(content) = content else {
         return false;
     };
-    app.messages.push(user_message(content));
+    let mut message = user_message(content);
+    message.attachments = std::mem::take(&mut app.pending_attachments);
+    app.messages.push(message);
     select_latest_message(app);
     true
 }
@@ -123,6 +236,7 @@ fn user_message(content: String) -> Message {
         content,
         tool_call_id: None,
         tool_calls: None,
+        attachments: Vec::new(),
     }
 }
 
@@ -148,33 +262,140 @@ struct StartRequestCommonParams<'a> {
     api_key: &'a str,
     model: &'a str,
     max_tokens: Option<u64>,
-    show_reasoning: bool,
+    context_window: Option<u64>,
+    reasoning: ReasoningSettings,
+    timeouts: StreamTimeouts,
     tx: &'a mpsc::Sender<RuntimeEvent>,
     tab_id: String,
+    category: String,
     enable_web_search: bool,
     enable_code_exec: bool,
     enable_read_file: bool,
     enable_read_code: bool,
     enable_modify_file: bool,
     enable_ask_questions: bool,
+    enable_delegate_task: bool,
     log_requests: Option<String>,
     log_session_id: String,
+    compact: Option<CompactRequest>,
 }
 
-fn start_request_common(params: StartRequestCommonParams<'_>) {
+fn start_request_common(mut params: StartRequestCommonParams<'_>) {
     if !ensure_api_key(params.app, params.api_key) {
         return;
     }
-    let state = build_request_state(params.app);
+    let mut state = build_request_state(params.app);
+    let meta = prepare_system_prompt(params.app, &params.category, &mut state);
+    apply_tab_tools(&mut params);
+    apply_prompt_meta(&mut params, &meta);
+    state.temperature = meta.temperature;
+    match params.compact.take() {
+        Some(compact) => {
+            params.app.compaction = Some(CompactionState {
+                keep_from: compact.keep_from,
+                summary_from: state.idx,
+            });
+            state.messages.truncate(compact.keep_from);
+            state.messages.push(user_message(compact.instruction));
+        }
+        None => {
+            params.app.compaction = None;
+            let budget = ContextBudget::new(params.context_window, params.max_tokens);
+            notify_context_truncation(params.app, &state.messages, budget);
+        }
+    }
     spawn_llm_request(build_spawn_params(params, state));
 }
 
+/// 解析系统提示词的 front-matter，并按本次请求渲染模板变量；出错时提示并原样发送。
+fn prepare_system_prompt(app: &mut App, category: &str, state: &mut RequestState) -> PromptMeta {
+    let Some(system) = state.messages.iter_mut().find(|m| m.role == ROLE_SYSTEM) else {
+        return PromptMeta::default();
+    };
+    let vars = PromptVars {
+        workspace: cached_workspace_root(),
+        category: category.to_string(),
+        model: app.model_key.clone(),
+    };
+    match render_system_prompt(&system.content, &state.prompts_dir, &vars) {
+        Ok((meta, content)) => {
+            system.content = content;
+            meta
+        }
+        Err(err) => {
+            crate::ui::notice::push_notice(app, err);
+            PromptMeta::default()
+        }
+    }
+}
+
+/// 只有带 front-matter 的提示词才作为模板渲染，普通提示词中的 `{{` 保持原样。
+fn render_system_prompt(
+    source: &str,
+    prompts_dir: &str,
+    vars: &PromptVars,
+) -> Result<(PromptMeta, String), String> {
+    let (meta, body) =
+        parse_front_matter(source).map_err(|e| format!("系统提示词头部解析失败：{e}"))?;
+    let Some(meta) = meta else {
+        return Ok((PromptMeta::default(), source.to_string()));
+    };
+    let content = RigTemplates::load(prompts_dir)?.render_system(body, vars)?;
+    Ok((meta, content))
+}
+
+/// 本对话调整过工具开关时以它为准，否则沿用启动参数；压缩请求不带工具，保持原样。
+fn apply_tab_tools(params: &mut StartRequestCommonParams<'_>) {
+    if params.compact.is_some() || params.app.tool_flags.is_none() {
+        return;
+    }
+    let tools = effective_tools(params.app);
+    params.enable_web_search = tools.web_search;
+    params.enable_code_exec = tools.code_exec;
+    params.enable_read_file = tools.read_file;
+    params.enable_read_code = tools.read_code;
+    params.enable_modify_file = tools.modify_file;
+    params.enable_ask_questions = tools.ask_questions;
+    params.enable_delegate_task = tools.delegate_task;
+}
+
+/// 提示词声明了 `tools` 时只保留其中列出且已启用的工具。
+fn apply_prompt_meta(params: &mut StartRequestCommonParams<'_>, meta: &PromptMeta) {
+    params.enable_web_search &= meta.allows_tool("web_search");
+    params.enable_code_exec &= meta.allows_tool("code_exec");
+    params.enable_read_file &= meta.allows_tool("read_file");
+    params.enable_read_code &= meta.allows_tool("read_code");
+    params.enable_modify_file &= meta.allows_tool("modify_file");
+    params.enable_ask_questions &= meta.allows_tool("ask_questions");
+    params.enable_delegate_task &= meta.allows_tool("delegate_task");
+}
+
+/// 与请求线程使用相同的裁剪规则预估一次，让用户知道较早的历史不会发送给模型。
+fn notify_context_truncation(app: &mut App, messages: &[Message], budget: Option<ContextBudget>) {
+    let report = context_report(messages, system_prompt_tokens(messages), budget);
+    if report.dropped_messages > 0 {
+        crate::ui::notice::push_notice(
+            app,
+            format!("历史过长，已省略较早的 {} 条消息", report.dropped_messages),
+        );
+    } else if report.elided_tool_outputs > 0 {
+        crate::ui::notice::push_notice(
+            app,
+            format!(
+                "历史过长，已压缩 {} 条较早的工具输出",
+                report.elided_tool_outputs
+            ),
+        );
+    }
+}
+
 struct RequestState {
     messages: Vec<Message>,
     idx: usize,
     cancel: Arc<AtomicBool>,
     request_id: u64,
     prompts_dir: String,
+    temperature: Option<f64>,
 }
 
 fn build_request_state(app: &mut App) -> RequestState {
@@ -185,6 +406,7 @@ fn build_request_state(app: &mut App) -> RequestState {
         cancel,
         request_id,
         prompts_dir: app.prompts_dir.clone(),
+        temperature: None,
     }
 }
 
@@ -197,15 +419,19 @@ fn build_spawn_params(
         api_key: params.api_key.to_string(),
         model: params.model.to_string(),
         max_tokens: params.max_tokens,
+        context_window: params.context_window,
         messages: state.messages,
         prompts_dir: state.prompts_dir,
-        show_reasoning: params.show_reasoning,
+        reasoning: params.reasoning,
+        timeouts: params.timeouts,
+        temperature: state.temperature,
         enable_web_search: params.enable_web_search,
         enable_code_exec: params.enable_code_exec,
         enable_read_file: params.enable_read_file,
         enable_read_code: params.enable_read_code,
         enable_modify_file: params.enable_modify_file,
         enable_ask_questions: params.enable_ask_questions,
+        enable_delegate_task: params.enable_delegate_task,
         log_requests: params.log_requests,
         log_session_id: params.log_session_id,
         idx: state.idx,
@@ -221,15 +447,19 @@ struct SpawnLlmRequestParams {
     api_key: String,
     model: String,
     max_tokens: Option<u64>,
+    context_window: Option<u64>,
     messages: Vec<Message>,
     prompts_dir: String,
-    show_reasoning: bool,
+    reasoning: ReasoningSettings,
+    timeouts: StreamTimeouts,
+    temperature: Option<f64>,
     enable_web_search: bool,
     enable_code_exec: bool,
     enable_read_file: bool,
     enable_read_code: bool,
     enable_modify_file: bool,
     enable_ask_questions: bool,
+    enable_delegate_task: bool,
     log_requests: Option<String>,
     log_session_id: String,
     idx: usize,
@@ -246,15 +476,19 @@ fn spawn_llm_request(params: SpawnLlmRequestParams) {
             api_key: params.api_key,
             model: params.model,
             max_tokens: params.max_tokens,
+            context_window: params.context_window,
             messages: params.messages,
             prompts_dir: params.prompts_dir,
-            show_reasoning: params.show_reasoning,
+            reasoning: params.reasoning,
+            timeouts: params.timeouts,
+            temperature: params.temperature,
             enable_web_search: params.enable_web_search,
             enable_code_exec: params.enable_code_exec,
             enable_read_file: params.enable_read_file,
             enable_read_code: params.enable_read_code,
             enable_modify_file: params.enable_modify_file,
             enable_ask_questions: params.enable_ask_questions,
+            enable_delegate_task: params.enable_delegate_task,
             log_dir: params.log_requests,
             log_session_id: params.log_session_id,
             message_index: params.idx,
@@ -275,6 +509,7 @@ fn ensure_api_key(app: &mut App, api_key: &str) -> bool {
         content: "缺少 API Key，无法请求模型。".to_string(),
         tool_call_id: None,
         tool_calls: None,
+        attachments: Vec::new(),
     });
     false
 }
@@ -287,6 +522,7 @@ fn setup_request_state(app: &mut App) -> (Vec<Message>, usize, u64, Arc<AtomicBo
         content: String::new(),
         tool_call_id: None,
         tool_calls: None,
+        attachments: Vec::new(),
     });
     let request_id = app.next_request_id;
     app.next_request_id = app.next_request_id.saturating_add(1);
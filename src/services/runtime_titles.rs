@@ -0,0 +1,225 @@
+//! 对话标题
+//!
+//! 第一轮问答结束后为对话起一个简短标题：配置了 `title_model` 时在后台请求该模型，
+//! 未配置或请求失败时截取首条提问。标题随对话保存，可用 `/title <标题>` 手动修改。
+
+use crate::framework::widget_system::interaction::text_utils::{
+    collapse_text, truncate_to_width_ellipsis_char,
+};
+use crate::llm::reasoning::ReasoningSettings;
+use crate::model_registry::{ModelProfile, ModelRegistry};
+use crate::services::net::{LlmStreamRequestParams, request_llm_stream};
+use crate::types::{Message, ROLE_ASSISTANT, ROLE_SYSTEM, ROLE_USER};
+use crate::ui::events::{LlmEvent, RuntimeEvent};
+use crate::ui::notice::push_notice;
+use crate::ui::runtime_helpers::TabState;
+use crate::ui::state::App;
+use std::sync::mpsc;
+use std::sync::{Arc, OnceLock, atomic::AtomicBool};
+use std::thread;
+
+/// 标题的最大显示宽度（中文约 12 个字）
+const MAX_TITLE_WIDTH: usize = 24;
+/// 发给标题模型的首轮回答最多截取这么多字符
+const MAX_ANSWER_CHARS: usize = 1000;
+const TITLE_MAX_TOKENS: u64 = 64;
+
+const TITLE_INSTRUCTION: &str = "根据下面的一轮对话，为它起一个简短的标题（不超过 12 个字），概括用户想做的事。只输出标题本身，不要引号、标点或解释。";
+
+static TITLE_MODEL: OnceLock<Option<String>> = OnceLock::new();
+
+/// 后台生成的标题；`title` 为 None 表示请求失败，改用首条提问。
+pub(crate) struct TitleEvent {
+    pub(crate) conversation_id: String,
+    pub(crate) title: Option<String>,
+}
+
+/// 记录配置中用于生成标题的模型；未配置时只截取首条提问。
+pub(crate) fn init_title_model(model: Option<String>) {
+    let _ = TITLE_MODEL.set(model);
+}
+
+/// 为本轮结束、还没有标题的对话生成标题。
+pub(crate) fn request_titles(
+    tabs: &mut [TabState],
+    done_tabs: &[usize],
+    registry: &ModelRegistry,
+    tx: &mpsc::Sender<RuntimeEvent>,
+) {
+    let model = TITLE_MODEL
+        .get()
+        .and_then(|key| key.as_deref())
+        .and_then(|key| registry.get(key));
+    for &idx in done_tabs {
+        let Some(tab_state) = tabs.get_mut(idx) else {
+            continue;
+        };
+        let Some((question, answer)) = first_exchange(&tab_state.app) else {
+            continue;
+        };
+        match model {
+            Some(model) => {
+                tab_state.app.title_pending = true;
+                let exchange = title_messages(&question, &answer);
+                spawn_title_request(tab_state, model, exchange, tx);
+            }
+            None => tab_state.app.title = Some(fallback_title(&tab_state.app)),
+        }
+    }
+}
+
+/// 需要起标题时返回首条提问与首个回答。
+fn first_exchange(app: &App) -> Option<(String, String)> {
+    if app.title.is_some() || app.title_pending || app.busy {
+        return None;
+    }
+    let question = app.messages.iter().find(|m| m.role == ROLE_USER)?;
+    let answer = app
+        .messages
+        .iter()
+        .find(|m| m.role == ROLE_ASSISTANT && m.tool_calls.is_none() && !m.content.is_empty())?;
+    let answer: String = answer.content.chars().take(MAX_ANSWER_CHARS).collect();
+    Some((question.content.clone(), answer))
+}
+
+fn spawn_title_request(
+    tab_state: &TabState,
+    model: &ModelProfile,
+    messages: Vec<Message>,
+    tx: &mpsc::Sender<RuntimeEvent>,
+) {
+    let (inner_tx, inner_rx) = mpsc::channel();
+    let params = LlmStreamRequestParams {
+        base_url: model.base_url.trim_end_matches('/').to_string(),
+        api_key: model.api_key.clone(),
+        model: model.model.clone(),
+        max_tokens: Some(TITLE_MAX_TOKENS),
+        context_window: model.context_window,
+        messages,
+        prompts_dir: tab_state.app.prompts_dir.clone(),
+        reasoning: ReasoningSettings::default(),
+        timeouts: model.timeouts,
+        temperature: None,
+        enable_web_search: false,
+        enable_code_exec: false,
+        enable_read_file: false,
+        enable_read_code: false,
+        enable_modify_file: false,
+        enable_ask_questions: false,
+        enable_delegate_task: false,
+        log_dir: None,
+        log_session_id: String::new(),
+        message_index: 0,
+        cancel: Arc::new(AtomicBool::new(false)),
+        tx: inner_tx,
+        tab: tab_state.conversation_id.clone(),
+        request_id: 0,
+    };
+    let conversation_id = tab_state.conversation_id.clone();
+    let tx = tx.clone();
+    thread::spawn(move || {
+        request_llm_stream(params);
+        let title = collect_title(inner_rx);
+        let _ = tx.send(RuntimeEvent::Title(TitleEvent {
+            conversation_id,
+            title,
+        }));
+    });
+}
+
+fn title_messages(question: &str, answer: &str) -> Vec<Message> {
+    let text = format!("用户：{question}\n\n助手：{answer}");
+    vec![
+        plain_message(ROLE_SYSTEM, TITLE_INSTRUCTION),
+        plain_message(ROLE_USER, &text),
+    ]
+}
+
+fn plain_message(role: &str, content: &str) -> Message {
+    Message {
+        role: role.to_string(),
+        content: content.to_string(),
+        tool_call_id: None,
+        tool_calls: None,
+        attachments: Vec::new(),
+    }
+}
+
+/// 读完标题请求的全部事件；出错或停滞时返回 None。
+fn collect_title(rx: mpsc::Receiver<RuntimeEvent>) -> Option<String> {
+    let mut text = String::new();
+    for event in rx {
+        let RuntimeEvent::Llm(ui) = event else {
+            continue;
+        };
+        match ui.event {
+            LlmEvent::Chunk(chunk) => text.push_str(&chunk),
+            LlmEvent::Retrying { .. } => text.clear(),
+            LlmEvent::Error(_)
+            | LlmEvent::Stalled {
+                will_retry: false, ..
+            } => return None,
+            _ => {}
+        }
+    }
+    clean_title(&text)
+}
+
+fn clean_title(text: &str) -> Option<String> {
+    let line = text.lines().map(str::trim).find(|line| !line.is_empty())?;
+    let trimmed = line
+        .trim_start_matches(['#', '*'])
+        .trim_start_matches("标题：")
+        .trim_matches(|c: char| "\"'“”‘’「」《》*。.".contains(c) || c.is_whitespace());
+    if trimmed.is_empty() {
+        return None;
+    }
+    Some(truncate_to_width_ellipsis_char(trimmed, MAX_TITLE_WIDTH))
+}
+
+/// 截取首条提问的第一行作为标题。
+fn fallback_title(app: &App) -> String {
+    let question = app
+        .messages
+        .iter()
+        .find(|m| m.role == ROLE_USER)
+        .map(|m| collapse_text(m.content.lines().next().unwrap_or_default()))
+        .unwrap_or_default();
+    if question.is_empty() {
+        return "未命名对话".to_string();
+    }
+    truncate_to_width_ellipsis_char(&question, MAX_TITLE_WIDTH)
+}
+
+/// 应用后台生成的标题；期间已用 `/title` 手动命名的对话保持不变。
+pub(crate) fn apply_title_events(events: &mut Vec<TitleEvent>, tabs: &mut [TabState]) {
+    for event in events.drain(..) {
+        let Some(tab) = tabs
+            .iter_mut()
+            .find(|t| t.conversation_id == event.conversation_id)
+        else {
+            continue;
+        };
+        let app = &mut tab.app;
+        app.title_pending = false;
+        if app.title.is_none() {
+            let title = event.title.unwrap_or_else(|| fallback_title(app));
+            app.title = Some(title);
+        }
+    }
+}
+
+/// `/title [标题]`：不带参数时显示当前标题。
+pub(crate) fn handle_title_command(app: &mut App, arg: &str) {
+    let title = collapse_text(arg);
+    if title.is_empty() {
+        let text = match &app.title {
+            Some(title) => format!("当前标题：{title}（/title <标题> 修改）"),
+            None => "本对话还没有标题，用法：/title <标题>".to_string(),
+        };
+        push_notice(app, text);
+        return;
+    }
+    push_notice(app, format!("已将标题改为：{title}"));
+    app.title = Some(title);
+}
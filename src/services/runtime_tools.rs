@@ -0,0 +1,119 @@
+//! 按对话开关工具
+//!
+//! `--enable` 决定默认开放的工具；各对话可在工具面板（F11）或用 `/tools` 单独开关，
+//! 调整结果随对话保存。发起请求与执行模型的工具调用时都以本对话的开关为准。
+
+use crate::args::{ToolFlags, parse_enable_item};
+use crate::services::code_exec_container_env::read_only_enabled;
+use crate::ui::notice::push_notice;
+use crate::ui::state::App;
+use std::sync::OnceLock;
+
+const TOOLS_USAGE: &str = "用法：/tools [工具名 | -工具名 ...] | reset（工具名：web_search code_exec read_file read_code modify_file ask_questions delegate_task）";
+
+/// 会执行代码或改动文件的工具，开启时在 Tab 栏标出。
+const DANGEROUS_TOOLS: [(&str, &str); 2] = [("code_exec", "⚡"), ("modify_file", "✎")];
+
+static DEFAULT_TOOLS: OnceLock<ToolFlags> = OnceLock::new();
+
+/// 记录 `--enable` 解析出的默认开关；对话内未调整时使用。
+pub(crate) fn init_default_tools(tools: ToolFlags) {
+    let _ = DEFAULT_TOOLS.set(tools);
+}
+
+/// 本对话实际开放的工具；只读模式下 modify_file 始终关闭。
+pub(crate) fn effective_tools(app: &App) -> ToolFlags {
+    let mut tools = app
+        .tool_flags
+        .unwrap_or_else(|| DEFAULT_TOOLS.get().copied().unwrap_or_default());
+    tools.modify_file &= !read_only_enabled();
+    tools
+}
+
+pub(crate) fn toggle_tool(app: &mut App, idx: usize) {
+    let Some(name) = ToolFlags::NAMES.get(idx).copied() else {
+        return;
+    };
+    let enable = !effective_tools(app).get(name);
+    if let Err(err) = set_tool(app, name, enable) {
+        push_notice(app, err);
+    }
+}
+
+pub(crate) fn reset_tools(app: &mut App) {
+    app.tool_flags = None;
+    let text = format!("已恢复默认工具：{}", describe_tools(effective_tools(app)));
+    push_notice(app, text);
+}
+
+/// `/tools`：不带参数时打开工具面板，否则按 `code_exec -web_search` 的写法调整本对话的工具。
+pub(crate) fn handle_tools_command(app: &mut App, arg: &str) {
+    if arg.is_empty() {
+        app.pending_tools_overlay = true;
+        return;
+    }
+    if arg == "reset" {
+        reset_tools(app);
+        return;
+    }
+    match apply_tools_expr(app, arg) {
+        Ok(()) => {
+            let text = format!("本对话工具：{}", describe_tools(effective_tools(app)));
+            push_notice(app, text);
+        }
+        Err(err) => push_notice(app, err),
+    }
+}
+
+fn apply_tools_expr(app: &mut App, arg: &str) -> Result<(), String> {
+    let items: Vec<(&str, bool)> = arg
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter_map(parse_enable_item)
+        .collect();
+    if let Some((name, _)) = items
+        .iter()
+        .find(|(name, _)| !ToolFlags::NAMES.contains(name))
+    {
+        return Err(format!("未知的工具：{name}\n{TOOLS_USAGE}"));
+    }
+    for (name, enable) in items {
+        set_tool(app, name, enable)?;
+    }
+    Ok(())
+}
+
+fn set_tool(app: &mut App, name: &str, enable: bool) -> Result<(), String> {
+    if enable && name == "modify_file" && read_only_enabled() {
+        return Err("只读模式下不能开启 modify_file".to_string());
+    }
+    let mut tools = effective_tools(app);
+    tools.set(name, enable);
+    app.tool_flags = Some(tools);
+    Ok(())
+}
+
+fn describe_tools(tools: ToolFlags) -> String {
+    let enabled: Vec<&str> = ToolFlags::NAMES
+        .into_iter()
+        .filter(|name| tools.get(name))
+        .collect();
+    if enabled.is_empty() {
+        "无".to_string()
+    } else {
+        enabled.join(", ")
+    }
+}
+
+/// Tab 标题上的危险工具标记，如 `⚡✎`；都未开启时为空。
+pub(crate) fn dangerous_tools_marker(app: &App) -> String {
+    let tools = effective_tools(app);
+    DANGEROUS_TOOLS
+        .iter()
+        .filter(|(name, _)| tools.get(name))
+        .map(|(_, mark)| *mark)
+        .collect()
+}
+
+pub(crate) fn is_dangerous_tool(name: &str) -> bool {
+    DANGEROUS_TOOLS.iter().any(|(tool, _)| *tool == name)
+}
@@ -0,0 +1,165 @@
+//! 回答候选
+//!
+//! 重新生成最后一轮回答时不再分叉新标签页，而是把原回答留作候选：同一轮的各个回答在消息标签上
+//! 显示为「‹2/3›」，导航模式下用 `[` / `]` 切换。`messages` 中只保留选中的回答，后续请求据此发送；
+//! 全部候选随对话保存。之后的对话是基于选中的回答生成的，因此继续提问后就不能再切换更早轮次的回答。
+
+use crate::args::Args;
+use crate::conversation::AnswerVariants;
+use crate::model_registry::ModelRegistry;
+use crate::services::runtime_agent_guard::begin_turn;
+use crate::services::runtime_requests::{start_followup, turn_in_progress};
+use crate::types::{Message, ROLE_ASSISTANT, ROLE_USER};
+use crate::ui::events::RuntimeEvent;
+use crate::ui::notice::push_notice;
+use crate::ui::runtime_helpers::TabState;
+use crate::ui::state::{App, PendingCommand};
+use std::collections::BTreeMap;
+use std::sync::mpsc;
+
+/// `/regen` 与导航模式的 `r`：只能重新生成最后一轮，选中更早的消息时给出提示。
+pub(crate) fn request_regenerate(app: &mut App, selected: Option<usize>) {
+    let last = last_user_idx(&app.messages);
+    let turn = selected.and_then(|idx| turn_start(&app.messages, idx));
+    if turn.is_some() && turn != last {
+        push_notice(app, "只能重新生成最后一轮回答");
+        return;
+    }
+    app.pending_command = Some(PendingCommand::RegenerateAnswer);
+}
+
+/// 保留当前回答作为候选，清掉它后重新请求模型。
+pub(crate) fn handle_regenerate(
+    tab_state: &mut TabState,
+    registry: &ModelRegistry,
+    args: &Args,
+    tx: &mpsc::Sender<RuntimeEvent>,
+) {
+    let app = &mut tab_state.app;
+    if turn_in_progress(app) {
+        push_notice(app, "本轮尚未结束，无法重新生成");
+        return;
+    }
+    let Some(user_idx) = last_user_idx(&app.messages) else {
+        push_notice(app, "还没有可以重新生成的回答");
+        return;
+    };
+    let segment = app.messages.split_off(user_idx + 1);
+    if !segment.is_empty() || app.answer_variants.contains_key(&user_idx) {
+        let entry = app.answer_variants.entry(user_idx).or_default();
+        store_selected(entry, segment);
+        entry.variants.push(Vec::new());
+        entry.selected = entry.variants.len() - 1;
+    }
+    app.assistant_stats.retain(|idx, _| *idx <= user_idx);
+    reset_turn_view(tab_state, user_idx);
+    begin_turn(&mut tab_state.app);
+    start_followup(tab_state, registry, args, tx);
+}
+
+/// 切换最后一轮的回答；`forward` 为 false 时切到上一个。
+pub(crate) fn cycle_variant(tab_state: &mut TabState, forward: bool) {
+    let app = &mut tab_state.app;
+    if turn_in_progress(app) {
+        push_notice(app, "本轮尚未结束，稍后再切换回答");
+        return;
+    }
+    let selected = app.message_history.selected;
+    let Some(user_idx) = turn_start(&app.messages, selected) else {
+        return;
+    };
+    if Some(user_idx) != last_user_idx(&app.messages) {
+        push_notice(app, "后续对话基于这一轮当前的回答，只能切换最后一轮的回答");
+        return;
+    }
+    let Some(entry) = app
+        .answer_variants
+        .get_mut(&user_idx)
+        .filter(|entry| entry.variants.len() > 1)
+    else {
+        push_notice(app, "这一轮只有一个回答，可按 r 重新生成最后一轮");
+        return;
+    };
+    let count = entry.variants.len();
+    entry.selected = entry.selected.min(count - 1);
+    let next = if forward {
+        (entry.selected + 1) % count
+    } else {
+        (entry.selected + count - 1) % count
+    };
+    let outgoing = app.messages.split_off(user_idx + 1);
+    app.messages.extend(entry.variants[next].iter().cloned());
+    store_selected(entry, outgoing);
+    entry.selected = next;
+    let text = format!("已切换到第 {}/{count} 个回答", next + 1);
+    app.assistant_stats.retain(|idx, _| *idx <= user_idx);
+    push_notice(app, text);
+    reset_turn_view(tab_state, user_idx);
+}
+
+/// 有多个回答的轮次在最后一条助手消息的标签上显示「‹2/3›」。
+pub(crate) fn variant_labels(app: &App) -> Vec<(usize, String)> {
+    app.answer_variants
+        .iter()
+        .filter(|(user_idx, entry)| {
+            entry.variants.len() > 1 && is_user_at(&app.messages, **user_idx)
+        })
+        .filter_map(|(&user_idx, entry)| {
+            let end = turn_end(&app.messages, user_idx);
+            let idx = (user_idx + 1..end)
+                .rev()
+                .find(|&idx| app.messages[idx].role == ROLE_ASSISTANT)?;
+            let label = format!("‹{}/{}›", entry.selected + 1, entry.variants.len());
+            Some((idx, label))
+        })
+        .collect()
+}
+
+/// 保存前用 `messages` 中的当前回答刷新选中的候选。
+pub(crate) fn synced_variants(app: &App) -> BTreeMap<usize, AnswerVariants> {
+    let mut variants = app.answer_variants.clone();
+    for (&user_idx, entry) in variants.iter_mut() {
+        if !is_user_at(&app.messages, user_idx) {
+            continue;
+        }
+        let end = turn_end(&app.messages, user_idx);
+        if let Some(slot) = entry.variants.get_mut(entry.selected) {
+            *slot = app.messages[user_idx + 1..end].to_vec();
+        }
+    }
+    variants
+}
+
+/// 记下 `messages` 中原先选中的回答。
+fn store_selected(entry: &mut AnswerVariants, segment: Vec<Message>) {
+    match entry.variants.get_mut(entry.selected) {
+        Some(slot) => *slot = segment,
+        None => entry.variants.push(segment),
+    }
+}
+
+fn reset_turn_view(tab_state: &mut TabState, user_idx: usize) {
+    let end = turn_end(&tab_state.app.messages, user_idx);
+    tab_state.reset_render_state(end.saturating_sub(1));
+}
+
+fn last_user_idx(messages: &[Message]) -> Option<usize> {
+    messages.iter().rposition(|m| m.role == ROLE_USER)
+}
+
+fn turn_start(messages: &[Message], idx: usize) -> Option<usize> {
+    let end = (idx + 1).min(messages.len());
+    messages[..end].iter().rposition(|m| m.role == ROLE_USER)
+}
+
+fn turn_end(messages: &[Message], user_idx: usize) -> usize {
+    messages
+        .iter()
+        .skip(user_idx + 1)
+        .position(|m| m.role == ROLE_USER)
+        .map_or(messages.len(), |offset| user_idx + 1 + offset)
+}
+
+fn is_user_at(messages: &[Message], idx: usize) -> bool {
+    messages.get(idx).is_some_and(|m| m.role == ROLE_USER)
+}
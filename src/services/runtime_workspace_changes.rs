@@ -0,0 +1,74 @@
+use crate::args::Args;
+use crate::services::workspace::resolve_workspace;
+use crate::services::workspace_sync::{apply_workspace_changes, collect_workspace_changes};
+use crate::ui::notice::push_notice;
+use crate::ui::runtime_helpers::TabState;
+use crate::ui::state::PendingWorkspaceChanges;
+
+pub(crate) fn handle_workspace_changes_show(tab_state: &mut TabState, args: &Args) {
+    let workspace = match resolve_workspace(args) {
+        Ok(workspace) => workspace,
+        Err(err) => {
+            push_notice(&mut tab_state.app, format!("workspace 不可用：{err}"));
+            return;
+        }
+    };
+    if workspace.copy_path.is_none() {
+        push_notice(
+            &mut tab_state.app,
+            "未启用隔离工作区（启动时加 --isolated-workspace）",
+        );
+        return;
+    }
+    match collect_workspace_changes(&workspace) {
+        Ok(items) if items.is_empty() => {
+            push_notice(&mut tab_state.app, "隔离工作区没有待同步的改动");
+        }
+        Ok(items) => {
+            tab_state.app.pending_workspace_changes = Some(PendingWorkspaceChanges { items });
+        }
+        Err(err) => push_notice(&mut tab_state.app, format!("读取改动失败：{err}")),
+    }
+}
+
+pub(crate) fn handle_workspace_changes_apply(tab_state: &mut TabState, args: &Args) {
+    let Some(pending) = tab_state.app.pending_workspace_changes.take() else {
+        return;
+    };
+    let result = resolve_workspace(args)
+        .and_then(|workspace| apply_workspace_changes(&workspace, &pending.items));
+    match result {
+        Ok(count) => push_notice(&mut tab_state.app, format!("已同步 {count} 个文件到宿主机")),
+        Err(err) => push_notice(&mut tab_state.app, err),
+    }
+}
+
+pub(crate) fn toggle_workspace_change(tab_state: &mut TabState, idx: usize) -> bool {
+    let Some(pending) = tab_state.app.pending_workspace_changes.as_mut() else {
+        return false;
+    };
+    let Some(item) = pending.items.get_mut(idx) else {
+        return false;
+    };
+    item.selected = !item.selected;
+    true
+}
+
+pub(crate) fn toggle_all_workspace_changes(tab_state: &mut TabState) -> bool {
+    let Some(pending) = tab_state.app.pending_workspace_changes.as_mut() else {
+        return false;
+    };
+    let select = !pending.items.iter().all(|item| item.selected);
+    for item in &mut pending.items {
+        item.selected = select;
+    }
+    true
+}
+
+pub(crate) fn any_workspace_change_selected(tab_state: &TabState) -> bool {
+    tab_state
+        .app
+        .pending_workspace_changes
+        .as_ref()
+        .is_some_and(|pending| pending.items.iter().any(|item| item.selected))
+}
@@ -0,0 +1,303 @@
+use crate::config::{SandboxConfig, default_dockerfile_path};
+use crate::persist::content_hash;
+use crate::services::code_exec_container_env::code_exec_image;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// 镜像上记录构建输入摘要的 label，用于判断镜像是否过期。
+const HASH_LABEL: &str = "deepchat.sandbox.hash";
+const CONTAINER_LABEL: &str = "deepchat-container";
+
+pub(crate) struct SandboxBuildSpec {
+    pub(crate) dockerfile: PathBuf,
+    pub(crate) context: PathBuf,
+    pub(crate) contents: String,
+    pub(crate) hash: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SandboxImageStatus {
+    Ready,
+    Outdated,
+    Unmanaged,
+    Missing,
+    DockerUnavailable(String),
+}
+
+pub(crate) struct SandboxPruneReport {
+    pub(crate) containers: usize,
+    pub(crate) images: String,
+}
+
+pub(crate) fn build_spec(
+    sandbox: &SandboxConfig,
+    dockerfile_override: Option<&str>,
+) -> Result<SandboxBuildSpec, String> {
+    let dockerfile = resolve_dockerfile(sandbox, dockerfile_override)?;
+    let base = std::fs::read_to_string(&dockerfile)
+        .map_err(|e| format!("读取 Dockerfile 失败：{} ({e})", dockerfile.display()))?;
+    let contents = append_extra_packages(base, sandbox)?;
+    let context = dockerfile
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let hash = content_hash(contents.as_bytes());
+    Ok(SandboxBuildSpec {
+        dockerfile,
+        context,
+        contents,
+        hash,
+    })
+}
+
+pub(crate) fn inspect_image(image: &str, expected_hash: Option<&str>) -> SandboxImageStatus {
+    let template = format!("{{{{ index .Config.Labels \"{HASH_LABEL}\" }}}}");
+    let output = Command::new("docker")
+        .args(["image", "inspect", "--format", &template, image])
+        .stdin(Stdio::null())
+        .output();
+    let output = match output {
+        Ok(output) => output,
+        Err(e) => return SandboxImageStatus::DockerUnavailable(e.to_string()),
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        if stderr.contains("No such image") || stderr.contains("No such object") {
+            return SandboxImageStatus::Missing;
+        }
+        return SandboxImageStatus::DockerUnavailable(stderr);
+    }
+    let label = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if label.is_empty() || label == "<no value>" {
+        return SandboxImageStatus::Unmanaged;
+    }
+    match expected_hash {
+        Some(expected) if expected != label => SandboxImageStatus::Outdated,
+        _ => SandboxImageStatus::Ready,
+    }
+}
+
+pub(crate) fn build_image(
+    image: &str,
+    spec: &SandboxBuildSpec,
+    no_cache: bool,
+) -> Result<(), String> {
+    let mut cmd = Command::new("docker");
+    cmd.arg("build")
+        .arg("-t")
+        .arg(image)
+        .arg("--label")
+        .arg(format!("{HASH_LABEL}={}", spec.hash));
+    if no_cache {
+        cmd.arg("--no-cache");
+    }
+    cmd.arg("-f")
+        .arg("-")
+        .arg(&spec.context)
+        .stdin(Stdio::piped());
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("docker build 启动失败：{e}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(spec.contents.as_bytes())
+            .map_err(|e| format!("docker build 写入 Dockerfile 失败：{e}"))?;
+    }
+    let status = child
+        .wait()
+        .map_err(|e| format!("docker build 执行失败：{e}"))?;
+    if !status.success() {
+        return Err(format!("docker build 失败（退出码 {:?}）", status.code()));
+    }
+    Ok(())
+}
+
+pub(crate) fn prune_sandbox() -> Result<SandboxPruneReport, String> {
+    let containers = remove_orphan_containers()?;
+    let output = Command::new("docker")
+        .args(["image", "prune", "-f", "--filter"])
+        .arg(format!("label={HASH_LABEL}"))
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("docker image prune 失败：{e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "docker image prune 失败：{}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let images = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok(SandboxPruneReport { containers, images })
+}
+
+/// 启动时检查沙箱镜像；返回需要提示给用户的指引文本。
+pub(crate) fn startup_image_hint(sandbox: &SandboxConfig) -> Option<String> {
+    let image = code_exec_image();
+    let expected = build_spec(sandbox, None).ok().map(|spec| spec.hash);
+    match inspect_image(&image, expected.as_deref()) {
+        SandboxImageStatus::Ready | SandboxImageStatus::Unmanaged => None,
+        SandboxImageStatus::Outdated => Some(format!(
+            "沙箱镜像 {image} 已过期（Dockerfile 或额外依赖有变更），请运行 `deepchat sandbox build` 重新构建"
+        )),
+        SandboxImageStatus::Missing => Some(format!(
+            "沙箱镜像 {image} 不存在，请先运行 `deepchat sandbox build`，或在配置中设置 exec_backend: \"host\""
+        )),
+        SandboxImageStatus::DockerUnavailable(_) => Some(
+            "Docker 不可用，代码执行/文件工具将无法使用；请启动 Docker，或在配置中设置 exec_backend: \"host\""
+                .to_string(),
+        ),
+    }
+}
+
+pub(crate) fn status_label(status: &SandboxImageStatus) -> String {
+    match status {
+        SandboxImageStatus::Ready => "已就绪".to_string(),
+        SandboxImageStatus::Outdated => {
+            "已过期（运行 deepchat sandbox build 重新构建）".to_string()
+        }
+        SandboxImageStatus::Unmanaged => {
+            "已存在（非 deepchat sandbox build 构建，无法判断是否过期）".to_string()
+        }
+        SandboxImageStatus::Missing => "不存在（运行 deepchat sandbox build 构建）".to_string(),
+        SandboxImageStatus::DockerUnavailable(err) => format!("Docker 不可用：{err}"),
+    }
+}
+
+pub(crate) fn count_sandbox_containers() -> Option<usize> {
+    list_sandbox_containers().ok().map(|items| items.len())
+}
+
+fn resolve_dockerfile(
+    sandbox: &SandboxConfig,
+    dockerfile_override: Option<&str>,
+) -> Result<PathBuf, String> {
+    let configured = dockerfile_override
+        .or(sandbox.dockerfile.as_deref())
+        .map(str::trim)
+        .filter(|path| !path.is_empty());
+    if let Some(path) = configured {
+        let path = PathBuf::from(path);
+        if !path.is_file() {
+            return Err(format!("Dockerfile 不存在：{}", path.display()));
+        }
+        return Ok(path);
+    }
+    default_dockerfile_path().ok_or_else(|| {
+        "未找到 Dockerfile：请在项目根目录运行，或使用 --dockerfile 指定".to_string()
+    })
+}
+
+fn append_extra_packages(mut contents: String, sandbox: &SandboxConfig) -> Result<String, String> {
+    validate_packages(&sandbox.apt_packages)?;
+    validate_packages(&sandbox.pip_packages)?;
+    if !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+    if !sandbox.apt_packages.is_empty() {
+        contents.push_str(&format!(
+            "RUN apt-get update && apt-get install -y --no-install-recommends {} && rm -rf /var/lib/apt/lists/*\n",
+            quote_packages(&sandbox.apt_packages)
+        ));
+    }
+    if !sandbox.pip_packages.is_empty() {
+        contents.push_str(&format!(
+            "RUN pip install --no-cache-dir {}\n",
+            quote_packages(&sandbox.pip_packages)
+        ));
+    }
+    Ok(contents)
+}
+
+/// 逐个加单引号，避免 `numpy>=1.0` 之类的版本约束被 shell 当成重定向；
+/// validate_packages 已排除引号，单引号内无需转义。
+fn quote_packages(packages: &[String]) -> String {
+    packages
+        .iter()
+        .map(|name| format!("'{name}'"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn validate_packages(packages: &[String]) -> Result<(), String> {
+    for name in packages {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || "._-+=<>~!:,[]".contains(ch));
+        if !valid {
+            return Err(format!("沙箱依赖包名无效：{name}"));
+        }
+    }
+    Ok(())
+}
+
+fn list_sandbox_containers() -> Result<Vec<(String, String)>, String> {
+    let output = Command::new("docker")
+        .args(["ps", "-a", "--filter"])
+        .arg(format!("label={CONTAINER_LABEL}"))
+        .arg("--format")
+        .arg(format!("{{{{.ID}}}} {{{{.Label \"{CONTAINER_LABEL}\"}}}}"))
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("docker ps 失败：{e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "docker ps 失败：{}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (id, label) = line.trim().split_once(' ')?;
+            Some((id.to_string(), label.to_string()))
+        })
+        .collect())
+}
+
+/// 删除所属 deepchat 进程已退出的容器；仍在运行的会话容器保持不动。
+fn remove_orphan_containers() -> Result<usize, String> {
+    let orphans: Vec<String> = list_sandbox_containers()?
+        .into_iter()
+        .filter(|(_, label)| !owner_alive(label))
+        .map(|(id, _)| id)
+        .collect();
+    if orphans.is_empty() {
+        return Ok(0);
+    }
+    let output = Command::new("docker")
+        .args(["rm", "-f"])
+        .args(&orphans)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("docker rm 失败：{e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "docker rm 失败：{}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(orphans.len())
+}
+
+/// 容器 label 形如 deepchat-<pid>-<nanos>。
+fn owner_alive(label: &str) -> bool {
+    let Some(pid) = label
+        .strip_prefix("deepchat-")
+        .and_then(|rest| rest.split('-').next())
+        .and_then(|pid| pid.parse::<u32>().ok())
+    else {
+        return false;
+    };
+    if pid == std::process::id() {
+        return true;
+    }
+    Command::new("kill")
+        .arg("-0")
+        .arg(pid.to_string())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
@@ -51,6 +51,7 @@ pub(super) fn push_tool_message(tab_state: &mut TabState, call: &ToolCall, conte
         content,
         tool_call_id: Some(call.id.clone()),
         tool_calls: None,
+        attachments: Vec::new(),
     });
     tab_state.app.dirty_indices.push(idx);
 }
@@ -76,6 +77,7 @@ pub(super) fn push_assistant_message(tab_state: &mut TabState, content: String)
         content,
         tool_call_id: None,
         tool_calls: None,
+        attachments: Vec::new(),
     });
     tab_state.app.dirty_indices.push(idx);
 }
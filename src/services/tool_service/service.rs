@@ -1,10 +1,12 @@
-use crate::args::Args;
+use crate::args::{Args, ToolFlags};
 use crate::hooks::{EVENT_TOOL_AFTER, EVENT_TOOL_BEFORE, run_hooks};
-use crate::model_registry::{ModelProfile, ModelRegistry};
+use crate::model_registry::ModelRegistry;
 use crate::types::ToolCall;
 use crate::ui::events::RuntimeEvent;
 use crate::services::runtime_code_exec::{handle_bash_exec_request, handle_code_exec_request};
-use crate::services::runtime_requests::start_followup_request;
+use crate::services::runtime_delegation::handle_delegate_request;
+use crate::services::runtime_requests::start_followup;
+use crate::services::runtime_tools::effective_tools;
 use crate::ui::runtime_helpers::TabState;
 use crate::services::tools::run_tool;
 use crate::services::workspace::resolve_workspace;
@@ -93,6 +95,7 @@ impl<'a> ToolService<'a> {
             "code_exec" => self.handle_code_exec(call, tab_state, tab_id, state),
             "bash_exec" => self.handle_bash_exec(call, tab_state, tab_id, state),
             "ask_questions" => self.handle_question_review(call, tab_state, state),
+            "delegate_task" => self.handle_delegate_task(call, tab_state, state),
             _ => ToolHookStatus::Error,
         }
     }
@@ -104,7 +107,7 @@ impl<'a> ToolService<'a> {
         state: &mut ToolApplyState,
         kind: ToolKind,
     ) -> ToolHookStatus {
-        if !self.tool_enabled(kind) {
+        if !tool_enabled(effective_tools(&tab_state.app), kind) {
             push_tool_disabled(tab_state, call, state);
             return ToolHookStatus::Disabled;
         }
@@ -153,7 +156,7 @@ impl<'a> ToolService<'a> {
         tab_id: usize,
         state: &mut ToolApplyState,
     ) -> ToolHookStatus {
-        if !self.args.code_exec_enabled() {
+        if !effective_tools(&tab_state.app).code_exec {
             push_tool_error(tab_state, call, state, "code_exec 未启用");
             return ToolHookStatus::Disabled;
         }
@@ -176,7 +179,7 @@ impl<'a> ToolService<'a> {
         tab_id: usize,
         state: &mut ToolApplyState,
     ) -> ToolHookStatus {
-        if !self.args.code_exec_enabled() {
+        if !effective_tools(&tab_state.app).code_exec {
             push_tool_error(tab_state, call, state, "bash_exec 未启用");
             return ToolHookStatus::Disabled;
         }
@@ -198,7 +201,7 @@ impl<'a> ToolService<'a> {
         tab_state: &mut TabState,
         state: &mut ToolApplyState,
     ) -> ToolHookStatus {
-        if !self.args.ask_questions_enabled() {
+        if !effective_tools(&tab_state.app).ask_questions {
             push_tool_error(tab_state, call, state, "ask_questions 未启用");
             return ToolHookStatus::Disabled;
         }
@@ -219,12 +222,32 @@ impl<'a> ToolService<'a> {
         }
     }
 
-    fn tool_enabled(&self, kind: ToolKind) -> bool {
-        match kind {
-            ToolKind::WebSearch => self.args.web_search_enabled(),
-            ToolKind::ReadFile => self.args.read_file_enabled(),
-            ToolKind::ReadCode => self.args.read_code_enabled(),
-            ToolKind::ListDir => self.args.read_file_enabled(),
+    fn handle_delegate_task(
+        &self,
+        call: &ToolCall,
+        tab_state: &mut TabState,
+        state: &mut ToolApplyState,
+    ) -> ToolHookStatus {
+        if !effective_tools(&tab_state.app).delegate_task {
+            push_tool_error(tab_state, call, state, "delegate_task 未启用");
+            return ToolHookStatus::Disabled;
+        }
+        match handle_delegate_request(&mut tab_state.app, call) {
+            Ok(true) => {
+                state.needs_approval = true;
+                state.any_results = true;
+                ToolHookStatus::Ok
+            }
+            Ok(false) => {
+                let content = r#"{"ok":true,"status":"running","note":"子任务已在新对话中开始，完成后结果会作为新消息发回"}"#;
+                push_tool_message(tab_state, call, content.to_string());
+                state.any_results = true;
+                ToolHookStatus::Ok
+            }
+            Err(err) => {
+                push_tool_error(tab_state, call, state, err);
+                ToolHookStatus::Error
+            }
         }
     }
 
@@ -236,8 +259,7 @@ impl<'a> ToolService<'a> {
             push_assistant_message(tab_state, "未找到可靠结果，无法确认。".to_string());
             return;
         }
-        let model = self.resolve_model(tab_state);
-        self.start_followup(tab_state, model);
+        start_followup(tab_state, self.registry, self.args, self.tx);
     }
 
     fn reject_modify_file(
@@ -250,7 +272,7 @@ impl<'a> ToolService<'a> {
             push_tool_error(tab_state, call, state, "read_only 模式禁止 modify_file");
             return true;
         }
-        if !self.args.modify_file_enabled() {
+        if !effective_tools(&tab_state.app).modify_file {
             push_tool_error(tab_state, call, state, "modify_file 未启用");
             return true;
         }
@@ -285,37 +307,6 @@ impl<'a> ToolService<'a> {
         }
         state.any_results = true;
     }
-
-    fn resolve_model(&self, tab_state: &TabState) -> &ModelProfile {
-        self.registry
-            .get(&tab_state.app.model_key)
-            .unwrap_or_else(|| {
-                self.registry
-                    .get(&self.registry.default_key)
-                    .expect("model")
-            })
-    }
-
-    fn start_followup(&self, tab_state: &mut TabState, model: &ModelProfile) {
-        let log_session_id = tab_state.app.log_session_id.clone();
-        start_followup_request(crate::services::runtime_requests::StartFollowupRequestParams {
-            tab_state,
-            base_url: &model.base_url,
-            api_key: &model.api_key,
-            model: &model.model,
-            max_tokens: model.max_tokens,
-            show_reasoning: self.args.show_reasoning,
-            tx: self.tx,
-            enable_web_search: self.args.web_search_enabled(),
-            enable_code_exec: self.args.code_exec_enabled(),
-            enable_read_file: self.args.read_file_enabled(),
-            enable_read_code: self.args.read_code_enabled(),
-            enable_modify_file: self.args.modify_file_enabled(),
-            enable_ask_questions: self.args.ask_questions_enabled(),
-            log_requests: self.args.log_requests.clone(),
-            log_session_id,
-        });
-    }
 }
 
 fn tool_hook_vars(
@@ -333,3 +324,12 @@ fn tool_hook_vars(
         ("HOOK_TOOL_CALL_ID".to_string(), call.id.clone()),
     ]
 }
+
+fn tool_enabled(tools: ToolFlags, kind: ToolKind) -> bool {
+    match kind {
+        ToolKind::WebSearch => tools.web_search,
+        ToolKind::ReadFile => tools.read_file,
+        ToolKind::ReadCode => tools.read_code,
+        ToolKind::ListDir => tools.read_file,
+    }
+}
@@ -1,5 +1,7 @@
 use crate::services::code_exec_container::ensure_container_cached;
-use crate::services::workspace::{WorkspaceConfig, resolve_container_path};
+use crate::services::code_exec_container_env::host_backend_enabled;
+use crate::services::workspace::{WorkspaceConfig, resolve_container_path, resolve_host_path};
+use std::path::Path;
 
 use super::container::run_container_python;
 use super::{ToolResult, tool_err};
@@ -9,11 +11,7 @@ pub(super) fn run_list_dir(args_json: &str, workspace: &WorkspaceConfig) -> Tool
         Ok(val) => val,
         Err(err) => return err,
     };
-    let path = match resolve_container_path(&args.path, workspace) {
-        Ok(val) => val,
-        Err(err) => return tool_err(format!("list_dir 读取失败：{err}")),
-    };
-    let (entries, truncated) = match list_dir_container(&path, &args, workspace) {
+    let (entries, truncated) = match list_dir_entries(&args, workspace) {
         Ok(val) => val,
         Err(err) => return err,
     };
@@ -81,6 +79,61 @@ fn format_list_dir_output(args: &ListDirArgs, entries: Vec<String>, truncated: b
     out
 }
 
+fn list_dir_entries(
+    args: &ListDirArgs,
+    workspace: &WorkspaceConfig,
+) -> Result<(Vec<String>, bool), ToolResult> {
+    if host_backend_enabled() {
+        let path = resolve_host_path(&args.path, workspace)
+            .map_err(|err| tool_err(format!("list_dir 读取失败：{err}")))?;
+        return list_dir_host(&path, args);
+    }
+    let path = resolve_container_path(&args.path, workspace)
+        .map_err(|err| tool_err(format!("list_dir 读取失败：{err}")))?;
+    list_dir_container(&path, args, workspace)
+}
+
+fn list_dir_host(path: &Path, args: &ListDirArgs) -> Result<(Vec<String>, bool), ToolResult> {
+    if !path.is_dir() {
+        return Err(tool_err("list_dir 读取失败：不是目录".to_string()));
+    }
+    let mut entries = Vec::new();
+    let mut truncated = false;
+    let mut stack = vec![(path.to_path_buf(), String::new(), 0usize)];
+    while let Some((base, rel_prefix, depth)) = stack.pop() {
+        let items =
+            std::fs::read_dir(&base).map_err(|e| tool_err(format!("list_dir 读取失败：{e}")))?;
+        for item in items.flatten() {
+            let name = item.file_name().to_string_lossy().to_string();
+            if !args.include_hidden && name.starts_with('.') {
+                continue;
+            }
+            let rel = if rel_prefix.is_empty() {
+                name
+            } else {
+                format!("{rel_prefix}/{name}")
+            };
+            let is_dir = item.file_type().is_ok_and(|t| t.is_dir());
+            entries.push(if is_dir {
+                format!("{rel}/")
+            } else {
+                rel.clone()
+            });
+            if entries.len() >= args.max_entries {
+                truncated = true;
+                break;
+            }
+            if args.recursive && is_dir && depth < args.max_depth {
+                stack.push((item.path(), rel, depth + 1));
+            }
+        }
+        if truncated {
+            break;
+        }
+    }
+    Ok((entries, truncated))
+}
+
 fn list_dir_container(
     path: &str,
     args: &ListDirArgs,
@@ -1,5 +1,7 @@
 use crate::services::code_exec_container::ensure_container_cached;
-use crate::services::workspace::{WorkspaceConfig, resolve_container_path};
+use crate::services::code_exec_container_env::host_backend_enabled;
+use crate::services::workspace::{WorkspaceConfig, resolve_container_path, resolve_host_path};
+use std::path::Path;
 
 use super::container::run_container_python;
 use super::{ToolResult, tool_err};
@@ -69,6 +71,18 @@ fn read_file_content(
     Ok(output)
 }
 
+fn read_file_host(path: &Path, max_bytes: usize) -> Result<String, ToolResult> {
+    let meta = std::fs::metadata(path).map_err(|e| tool_err(format!("read_file 读取失败：{e}")))?;
+    if meta.len() > max_bytes as u64 {
+        return Err(tool_err(format!(
+            "read_file 文件过大：{} bytes",
+            meta.len()
+        )));
+    }
+    let data = std::fs::read(path).map_err(|e| tool_err(format!("read_file 读取失败：{e}")))?;
+    Ok(String::from_utf8_lossy(&data).to_string())
+}
+
 const READ_FILE_SCRIPT: &str = r#"
 import json, os, sys
 args = json.load(sys.stdin)
@@ -136,6 +150,11 @@ fn read_file_for_args(
     args: &ReadFileArgs,
     workspace: &WorkspaceConfig,
 ) -> Result<String, ToolResult> {
+    if host_backend_enabled() {
+        let path = resolve_host_path(&args.path, workspace)
+            .map_err(|err| tool_err(format!("read_file 读取失败：{err}")))?;
+        return read_file_host(&path, args.max_bytes);
+    }
     let path = match resolve_container_path(&args.path, workspace) {
         Ok(val) => val,
         Err(err) => return Err(tool_err(format!("read_file 读取失败：{err}"))),
@@ -1,4 +1,5 @@
 use crate::args::Args;
+use crate::services::workspace_sync::prepare_workspace_copy;
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
@@ -9,6 +10,14 @@ const WORKSPACE_MAX_BYTES: u64 = 1_000_000;
 pub(crate) struct WorkspaceConfig {
     pub(crate) host_path: PathBuf,
     pub(crate) mount_path: String,
+    /// 隔离模式下挂载进容器的工作区副本；为 None 时直接挂载宿主目录
+    pub(crate) copy_path: Option<PathBuf>,
+}
+
+impl WorkspaceConfig {
+    pub(crate) fn mount_source(&self) -> &Path {
+        self.copy_path.as_deref().unwrap_or(&self.host_path)
+    }
 }
 
 static WORKSPACE_CACHE: OnceLock<Result<WorkspaceConfig, String>> = OnceLock::new();
@@ -19,6 +28,14 @@ pub(crate) fn resolve_workspace(args: &Args) -> Result<WorkspaceConfig, String>
         .clone()
 }
 
+/// 启动时已校验过的宿主工作区目录；尚未解析或解析失败时为 None。
+pub(crate) fn cached_workspace_root() -> Option<PathBuf> {
+    WORKSPACE_CACHE
+        .get()
+        .and_then(|cfg| cfg.as_ref().ok())
+        .map(|cfg| cfg.host_path.clone())
+}
+
 fn build_workspace_config(args: &Args) -> Result<WorkspaceConfig, String> {
     let path = args.workspace.trim();
     if path.is_empty() {
@@ -30,10 +47,17 @@ fn build_workspace_config(args: &Args) -> Result<WorkspaceConfig, String> {
     if !host_path.is_dir() {
         return Err("workspace 必须是目录".to_string());
     }
-    validate_workspace_size(&host_path)?;
+    let copy_path = if args.isolated_workspace_enabled() {
+        let max_bytes = args.isolated_workspace_max_mb.saturating_mul(1_000_000);
+        Some(prepare_workspace_copy(&host_path, max_bytes)?)
+    } else {
+        validate_workspace_size(&host_path)?;
+        None
+    };
     Ok(WorkspaceConfig {
         host_path,
         mount_path: WORKSPACE_MOUNT.to_string(),
+        copy_path,
     })
 }
 
@@ -79,6 +103,48 @@ pub(crate) fn resolve_container_path(
     Ok(resolve_relative_path(raw, workspace))
 }
 
+/// 本机执行后端使用：按 resolve_container_path 的规则解析后映射回宿主机路径，并拒绝越界访问。
+pub(crate) fn resolve_host_path(
+    input: &str,
+    workspace: &WorkspaceConfig,
+) -> Result<PathBuf, String> {
+    let container_path = resolve_container_path(input, workspace)?;
+    let suffix = Path::new(&container_path)
+        .strip_prefix(&workspace.mount_path)
+        .map_err(|_| "禁止访问 workspace 之外的路径".to_string())?;
+    let root = workspace.mount_source();
+    let joined = root.join(suffix);
+    let canonical_root = root
+        .canonicalize()
+        .map_err(|e| format!("workspace 路径不可用：{e}"))?;
+    let resolved = canonicalize_existing_prefix(&joined)?;
+    if !resolved.starts_with(&canonical_root) {
+        return Err("禁止访问 workspace 之外的路径".to_string());
+    }
+    Ok(resolved)
+}
+
+fn canonicalize_existing_prefix(path: &Path) -> Result<PathBuf, String> {
+    let mut missing = Vec::new();
+    let mut current = path.to_path_buf();
+    loop {
+        if let Ok(canonical) = current.canonicalize() {
+            let mut out = canonical;
+            for part in missing.iter().rev() {
+                out.push(part);
+            }
+            return Ok(out);
+        }
+        let Some(name) = current.file_name().map(|n| n.to_os_string()) else {
+            return Err("路径不可用".to_string());
+        };
+        missing.push(name);
+        if !current.pop() {
+            return Err("路径不可用".to_string());
+        }
+    }
+}
+
 fn resolve_absolute_path(
     raw: &str,
     input_path: &Path,
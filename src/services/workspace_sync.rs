@@ -0,0 +1,417 @@
+use crate::persist::content_hash;
+use crate::services::workspace::WorkspaceConfig;
+use crate::ui::state::{WorkspaceChangeItem, WorkspaceChangeKind};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+
+const DIFF_MAX_BYTES: usize = 200_000;
+const NULL_DEVICE: &str = "/dev/null";
+
+/// 复制时记录的文件清单（相对路径 → 内容摘要），用于识别容器内删除的文件，
+/// 以及宿主机上在复制之后又被改动的文件；同步后更新为同步进宿主机的内容。
+static COPY_MANIFEST: Mutex<BTreeMap<PathBuf, String>> = Mutex::new(BTreeMap::new());
+
+pub(crate) fn prepare_workspace_copy(host: &Path, max_bytes: u64) -> Result<PathBuf, String> {
+    let files = list_workspace_files(host)?;
+    let root = copy_root();
+    if root.exists() {
+        std::fs::remove_dir_all(&root).map_err(|e| format!("workspace 副本清理失败：{e}"))?;
+    }
+    std::fs::create_dir_all(&root).map_err(|e| format!("workspace 副本创建失败：{e}"))?;
+    let mut total: u64 = 0;
+    let mut manifest = BTreeMap::new();
+    for rel in files {
+        let src = host.join(&rel);
+        let Ok(meta) = std::fs::symlink_metadata(&src) else {
+            continue;
+        };
+        if !meta.is_file() {
+            continue;
+        }
+        total = total.saturating_add(meta.len());
+        if total > max_bytes {
+            return Err(format!(
+                "workspace 大小超过限制：{total} bytes（可用 --isolated-workspace-max-mb 调整）"
+            ));
+        }
+        let dst = root.join(&rel);
+        copy_file(&src, &dst).map_err(|e| format!("workspace 复制失败：{e}"))?;
+        if let Some(hash) = file_hash(&dst) {
+            manifest.insert(rel, hash);
+        }
+    }
+    if let Ok(mut guard) = COPY_MANIFEST.lock() {
+        *guard = manifest;
+    }
+    Ok(root)
+}
+
+pub(crate) fn collect_workspace_changes(
+    workspace: &WorkspaceConfig,
+) -> Result<Vec<WorkspaceChangeItem>, String> {
+    let Some(copy) = workspace.copy_path.as_deref() else {
+        return Err("未启用隔离工作区".to_string());
+    };
+    let host = workspace.host_path.as_path();
+    let manifest = copy_manifest();
+    let copy_files = walk_files(copy)?;
+    let extra: Vec<PathBuf> = copy_files
+        .iter()
+        .filter(|rel| !manifest.contains_key(*rel))
+        .cloned()
+        .collect();
+    let ignored = ignored_paths(host, &extra);
+    let mut items = Vec::new();
+    for rel in &copy_files {
+        let snapshot = manifest.get(rel);
+        let host_file = host.join(rel);
+        let copy_file = copy.join(rel);
+        if snapshot.is_none() && ignored.contains(rel) {
+            continue;
+        }
+        if snapshot.is_some() && file_hash(&copy_file).as_ref() == snapshot {
+            continue;
+        }
+        if files_equal(&host_file, &copy_file) || host_file.is_dir() {
+            continue;
+        }
+        let (kind, old) = if host_file.is_file() {
+            (WorkspaceChangeKind::Modified, host_file.as_path())
+        } else {
+            (WorkspaceChangeKind::Added, Path::new(NULL_DEVICE))
+        };
+        let conflict = host_diverged(&host_file, snapshot);
+        items.push(build_item(rel, kind, old, &copy_file, conflict));
+    }
+    for (rel, hash) in &manifest {
+        let host_file = host.join(rel);
+        if copy.join(rel).exists() || !host_file.is_file() {
+            continue;
+        }
+        items.push(build_item(
+            rel,
+            WorkspaceChangeKind::Deleted,
+            &host_file,
+            Path::new(NULL_DEVICE),
+            host_diverged(&host_file, Some(hash)),
+        ));
+    }
+    items.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(items)
+}
+
+/// 把选中的改动写回宿主机。审阅之后宿主机上又有文件被改动时一个都不写，
+/// 避免覆盖用户刚做的修改；审阅时已标为冲突的文件由用户决定是否覆盖。
+pub(crate) fn apply_workspace_changes(
+    workspace: &WorkspaceConfig,
+    items: &[WorkspaceChangeItem],
+) -> Result<usize, String> {
+    let Some(copy) = workspace.copy_path.as_deref() else {
+        return Err("未启用隔离工作区".to_string());
+    };
+    let selected: Vec<&WorkspaceChangeItem> = items.iter().filter(|item| item.selected).collect();
+    let diverged = newly_diverged(workspace, &selected);
+    if !diverged.is_empty() {
+        return Err(format!(
+            "宿主机上的文件在审阅后被改动，未同步任何文件：{}；请重新打开 /changes 查看",
+            diverged.join("、")
+        ));
+    }
+    let mut applied = 0usize;
+    for item in selected {
+        let host_file = workspace.host_path.join(&item.path);
+        let synced = copy.join(&item.path);
+        let result = match item.kind {
+            WorkspaceChangeKind::Added | WorkspaceChangeKind::Modified => {
+                copy_file(&synced, &host_file)
+            }
+            WorkspaceChangeKind::Deleted => std::fs::remove_file(&host_file),
+        };
+        result.map_err(|e| format!("同步 {} 失败：{e}", item.path))?;
+        record_synced(&item.path, file_hash(&synced));
+        applied += 1;
+    }
+    Ok(applied)
+}
+
+/// 退出时删除工作区副本；仍有未同步的改动时保留副本，返回提示。
+pub(crate) fn discard_workspace_copy(workspace: &WorkspaceConfig) -> Option<String> {
+    let copy = workspace.copy_path.as_deref()?;
+    match collect_workspace_changes(workspace) {
+        Ok(items) if items.is_empty() => std::fs::remove_dir_all(copy)
+            .err()
+            .map(|e| format!("隔离工作区副本清理失败：{e}")),
+        Ok(items) => Some(format!(
+            "隔离工作区还有 {} 个未同步的改动，副本保留在 {}",
+            items.len(),
+            copy.display()
+        )),
+        Err(err) => Some(format!(
+            "读取隔离工作区改动失败（{err}），副本保留在 {}",
+            copy.display()
+        )),
+    }
+}
+
+fn copy_manifest() -> BTreeMap<PathBuf, String> {
+    COPY_MANIFEST
+        .lock()
+        .map(|guard| guard.clone())
+        .unwrap_or_default()
+}
+
+fn record_synced(path: &str, hash: Option<String>) {
+    let Ok(mut manifest) = COPY_MANIFEST.lock() else {
+        return;
+    };
+    match hash {
+        Some(hash) => manifest.insert(PathBuf::from(path), hash),
+        None => manifest.remove(Path::new(path)),
+    };
+}
+
+/// 宿主机文件与复制时的内容不同（含复制后新建或删除）。
+fn host_diverged(host_file: &Path, snapshot: Option<&String>) -> bool {
+    file_hash(host_file).as_ref() != snapshot
+}
+
+fn newly_diverged(workspace: &WorkspaceConfig, selected: &[&WorkspaceChangeItem]) -> Vec<String> {
+    let manifest = copy_manifest();
+    selected
+        .iter()
+        .filter(|item| !item.conflict)
+        .filter(|item| {
+            let host_file = workspace.host_path.join(&item.path);
+            host_diverged(&host_file, manifest.get(Path::new(&item.path)))
+        })
+        .map(|item| item.path.clone())
+        .collect()
+}
+
+fn file_hash(path: &Path) -> Option<String> {
+    std::fs::read(path).ok().map(|bytes| content_hash(&bytes))
+}
+
+fn copy_root() -> PathBuf {
+    std::env::temp_dir().join(format!("deepchat-workspace-{}", std::process::id()))
+}
+
+fn copy_file(src: &Path, dst: &Path) -> std::io::Result<()> {
+    if let Some(parent) = dst.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::copy(src, dst).map(|_| ())
+}
+
+fn list_workspace_files(host: &Path) -> Result<Vec<PathBuf>, String> {
+    match git_listed_files(host) {
+        Some(files) => Ok(files),
+        None => walk_files(host),
+    }
+}
+
+fn git_listed_files(host: &Path) -> Option<Vec<PathBuf>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(host)
+        .args([
+            "ls-files",
+            "--cached",
+            "--others",
+            "--exclude-standard",
+            "-z",
+        ])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(split_nul_paths(&output.stdout))
+}
+
+fn ignored_paths(host: &Path, candidates: &[PathBuf]) -> BTreeSet<PathBuf> {
+    if candidates.is_empty() {
+        return BTreeSet::new();
+    }
+    let child = Command::new("git")
+        .arg("-C")
+        .arg(host)
+        .args(["check-ignore", "--stdin", "-z"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn();
+    let Ok(mut child) = child else {
+        return BTreeSet::new();
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        let input: Vec<u8> = candidates
+            .iter()
+            .flat_map(|rel| {
+                let mut bytes = rel.to_string_lossy().as_bytes().to_vec();
+                bytes.push(0);
+                bytes
+            })
+            .collect();
+        std::thread::spawn(move || {
+            let _ = stdin.write_all(&input);
+        });
+    }
+    match child.wait_with_output() {
+        Ok(output) => split_nul_paths(&output.stdout).into_iter().collect(),
+        Err(_) => BTreeSet::new(),
+    }
+}
+
+fn split_nul_paths(raw: &[u8]) -> Vec<PathBuf> {
+    raw.split(|b| *b == 0)
+        .filter(|s| !s.is_empty())
+        .map(|s| PathBuf::from(String::from_utf8_lossy(s).as_ref()))
+        .collect()
+}
+
+fn walk_files(root: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut out = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let entries = std::fs::read_dir(&dir).map_err(|e| format!("workspace 读取失败：{e}"))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("workspace 读取失败：{e}"))?;
+            let path = entry.path();
+            let meta =
+                std::fs::symlink_metadata(&path).map_err(|e| format!("workspace 读取失败：{e}"))?;
+            if meta.file_type().is_symlink() {
+                continue;
+            }
+            if meta.is_dir() {
+                if entry.file_name() != ".git" {
+                    stack.push(path);
+                }
+                continue;
+            }
+            if meta.is_file()
+                && let Ok(rel) = path.strip_prefix(root)
+            {
+                out.push(rel.to_path_buf());
+            }
+        }
+    }
+    out.sort();
+    Ok(out)
+}
+
+fn files_equal(a: &Path, b: &Path) -> bool {
+    let (Ok(meta_a), Ok(meta_b)) = (std::fs::metadata(a), std::fs::metadata(b)) else {
+        return false;
+    };
+    if meta_a.len() != meta_b.len() {
+        return false;
+    }
+    match (std::fs::read(a), std::fs::read(b)) {
+        (Ok(left), Ok(right)) => left == right,
+        _ => false,
+    }
+}
+
+fn build_item(
+    rel: &Path,
+    kind: WorkspaceChangeKind,
+    old: &Path,
+    new: &Path,
+    conflict: bool,
+) -> WorkspaceChangeItem {
+    let path = rel.to_string_lossy().to_string();
+    let diff = file_diff(&path, old, new);
+    let (added, removed) = count_diff_lines(&diff);
+    WorkspaceChangeItem {
+        path,
+        kind,
+        added,
+        removed,
+        diff,
+        conflict,
+        selected: !conflict,
+    }
+}
+
+fn file_diff(rel: &str, old: &Path, new: &Path) -> String {
+    let output = Command::new("git")
+        .args(["diff", "--no-index", "--no-color", "--"])
+        .arg(old)
+        .arg(new)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output();
+    let Ok(output) = output else {
+        return "（无法生成 diff：git 不可用）".to_string();
+    };
+    let raw = String::from_utf8_lossy(&output.stdout);
+    let diff = relabel_diff_headers(&raw, rel, &[old, new]);
+    truncate_diff(diff)
+}
+
+/// git diff --no-index 的文件头使用绝对路径，这里替换为工作区内的相对路径。
+fn relabel_diff_headers(raw: &str, rel: &str, paths: &[&Path]) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut in_header = true;
+    for line in raw.split_inclusive('\n') {
+        if line.starts_with("@@") {
+            in_header = false;
+        } else if line.starts_with("diff --git ") {
+            in_header = true;
+        }
+        if !in_header {
+            out.push_str(line);
+            continue;
+        }
+        let mut line = line.to_string();
+        for path in paths {
+            let abs = path.to_string_lossy();
+            let abs = abs.trim_start_matches('/');
+            if abs.is_empty() || abs == NULL_DEVICE.trim_start_matches('/') {
+                continue;
+            }
+            line = line
+                .replace(&format!("a/{abs}"), &format!("a/{rel}"))
+                .replace(&format!("b/{abs}"), &format!("b/{rel}"));
+        }
+        out.push_str(&line);
+    }
+    out
+}
+
+fn truncate_diff(mut diff: String) -> String {
+    if diff.len() <= DIFF_MAX_BYTES {
+        return diff;
+    }
+    let mut cut = DIFF_MAX_BYTES;
+    while !diff.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    diff.truncate(cut);
+    diff.push_str("\n…（diff 过长已截断）\n");
+    diff
+}
+
+fn count_diff_lines(diff: &str) -> (usize, usize) {
+    let mut added = 0usize;
+    let mut removed = 0usize;
+    let mut in_hunk = false;
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            in_hunk = false;
+        } else if line.starts_with("@@") {
+            in_hunk = true;
+        } else if in_hunk && line.starts_with('+') {
+            added += 1;
+        } else if in_hunk && line.starts_with('-') {
+            removed += 1;
+        }
+    }
+    (added, removed)
+}
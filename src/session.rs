@@ -21,7 +21,7 @@ impl SessionLocation {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Default)]
 pub struct SessionData {
     pub id: String,
     #[serde(default)]
@@ -37,6 +37,14 @@ pub struct SessionData {
 pub struct LoadedSession {
     pub location: SessionLocation,
     pub data: SessionData,
+    /// 会话文件损坏、已隔离并以空会话代替时的提示
+    pub notice: Option<String>,
+}
+
+/// 会话文件的读取结果；JSON 损坏时文件已被改名隔离。
+enum SessionRaw {
+    Parsed(serde_json::Value),
+    Quarantined(String),
 }
 
 fn sessions_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
@@ -60,9 +68,13 @@ fn resolve_session_path(input: &str) -> Result<(PathBuf, bool), Box<dyn std::err
 
 pub fn load_session(input: &str) -> Result<LoadedSession, Box<dyn std::error::Error>> {
     let (path, custom_path) = resolve_session_path(input)?;
-    let raw = read_session_raw(&path)?;
-    reject_legacy_tabs(&raw)?;
-    let mut data = parse_session_data(raw)?;
+    let (mut data, notice) = match read_session_raw(&path)? {
+        SessionRaw::Parsed(raw) => {
+            reject_legacy_tabs(&raw)?;
+            (parse_session_data(raw)?, None)
+        }
+        SessionRaw::Quarantined(notice) => (SessionData::default(), Some(notice)),
+    };
     let id = resolve_session_id(&data, input, custom_path, &path);
     normalize_loaded_session(&mut data, &id);
     Ok(LoadedSession {
@@ -72,13 +84,23 @@ pub fn load_session(input: &str) -> Result<LoadedSession, Box<dyn std::error::Er
             custom_path,
         },
         data,
+        notice,
     })
 }
 
-fn read_session_raw(path: &PathBuf) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+fn read_session_raw(path: &PathBuf) -> Result<SessionRaw, Box<dyn std::error::Error>> {
     let text = fs::read_to_string(path)?;
-    let raw: serde_json::Value = serde_json::from_str(&text)?;
-    Ok(raw)
+    match serde_json::from_str(&text) {
+        Ok(raw) => Ok(SessionRaw::Parsed(raw)),
+        Err(err) if crate::persist::is_corrupted_json(&err) => {
+            let moved = crate::persist::quarantine(path)?;
+            Ok(SessionRaw::Quarantined(format!(
+                "会话文件已损坏（{err}），已移到 {}，本次以空会话启动",
+                moved.display()
+            )))
+        }
+        Err(err) => Err(err.into()),
+    }
 }
 
 fn reject_legacy_tabs(raw: &serde_json::Value) -> Result<(), Box<dyn std::error::Error>> {
@@ -207,8 +229,8 @@ fn build_session_data(
     }
 }
 
-fn write_session(path: &PathBuf, session: &SessionData) -> Result<(), Box<dyn std::error::Error>> {
+fn write_session(path: &Path, session: &SessionData) -> Result<(), Box<dyn std::error::Error>> {
     let text = serde_json::to_string_pretty(session)?;
-    fs::write(path, text)?;
+    crate::persist::write_atomic(path, &text)?;
     Ok(())
 }
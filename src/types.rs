@@ -12,6 +12,16 @@ pub struct Message {
     pub tool_call_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_calls: Option<Vec<ToolCall>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<Attachment>,
+}
+
+/// 消息附带的图片；文件保存在对话目录旁的 attachments 目录中。
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Attachment {
+    pub name: String,
+    pub mime_type: String,
+    pub path: String,
 }
 
 pub const ROLE_USER: &str = "user";
@@ -19,14 +29,19 @@ pub const ROLE_ASSISTANT: &str = "assistant";
 pub const ROLE_REASONING: &str = "reasoning";
 pub const ROLE_SYSTEM: &str = "system";
 pub const ROLE_TOOL: &str = "tool";
+/// `/compact` 生成的对话摘要，替代被压缩的较早消息。
+pub const ROLE_SUMMARY: &str = "summary";
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Usage {
     #[serde(alias = "input_tokens")]
     pub prompt_tokens: Option<u64>,
     #[serde(alias = "output_tokens")]
     pub completion_tokens: Option<u64>,
     pub total_tokens: Option<u64>,
+    /// 命中提示缓存的输入 tokens（包含在 prompt_tokens 内）
+    #[serde(default, alias = "cache_read_input_tokens")]
+    pub cached_tokens: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -11,7 +11,7 @@ use runtime_state::{
     load_question_set_option, maybe_fork_retry, run_initial_requests, sync_active_category,
     validate_args,
 };
-use runtime_terminal::{ensure_tty_ready, setup_terminal, teardown_terminal};
+use runtime_terminal::{ensure_tty_ready, install_panic_hook, setup_terminal, teardown_terminal};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::Sender;
@@ -19,29 +19,57 @@ use std::thread::JoinHandle;
 use std::time::Instant;
 
 mod runtime_state;
-mod runtime_terminal;
+pub(super) mod runtime_terminal;
 
 pub fn run(
     args: Args,
     cfg: crate::config::Config,
     theme: &RenderTheme,
+    startup_notice: Option<String>,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    crate::services::net::init_cassette(&args)?;
+    crate::services::runtime_agent_guard::init_agent_limits(cfg.agent_limits);
+    crate::services::runtime_tools::init_default_tools(args.tool_flags());
+    crate::services::runtime_titles::init_title_model(cfg.title_model.clone());
     let mut registry = build_model_registry(&cfg);
     let mut prompt_registry = load_prompts(&cfg.prompts_dir, "default", &args.system)?;
+    let startup_notice = join_notices(startup_notice, prompt_registry.skipped_notice());
     validate_args(&args)?;
     apply_model_override(&args, &mut registry)?;
     apply_prompt_override(&args, &cfg, &mut prompt_registry)?;
     let question_set = load_question_set_option(&args)?;
+    // 隔离模式在这里才复制工作区，之前的校验失败不会留下副本
+    crate::services::workspace::resolve_workspace(&args)
+        .map_err(|e| format!("workspace 校验失败：{e}"))?;
     let tavily_api_key = cfg.tavily_api_key.clone();
-    run_with_context(
-        &args,
-        &cfg,
+    let ctx = RunContext {
+        args: &args,
+        cfg: &cfg,
         theme,
-        &registry,
-        &prompt_registry,
-        question_set.as_ref(),
-        &tavily_api_key,
-    )
+        registry: &registry,
+        question_set: question_set.as_ref(),
+        tavily_api_key: &tavily_api_key,
+    };
+    let result = run_with_context(ctx, &mut prompt_registry, startup_notice);
+    finish_isolated_workspace(&args);
+    result
+}
+
+fn join_notices(first: Option<String>, second: Option<String>) -> Option<String> {
+    match (first, second) {
+        (Some(a), Some(b)) => Some(format!("{a}；{b}")),
+        (a, b) => a.or(b),
+    }
+}
+
+/// 退出界面后清理隔离工作区副本；还有未同步的改动时保留副本并提示路径。
+fn finish_isolated_workspace(args: &Args) {
+    let Ok(workspace) = crate::services::workspace::resolve_workspace(args) else {
+        return;
+    };
+    if let Some(note) = crate::services::workspace_sync::discard_workspace_copy(&workspace) {
+        eprintln!("{note}");
+    }
 }
 
 fn apply_model_override(
@@ -80,39 +108,50 @@ fn apply_prompt_override(
     Ok(())
 }
 
+/// 启动界面时已经确定、运行期间只读的参数。
+struct RunContext<'a> {
+    args: &'a Args,
+    cfg: &'a crate::config::Config,
+    theme: &'a RenderTheme,
+    registry: &'a crate::model_registry::ModelRegistry,
+    question_set: Option<&'a Vec<String>>,
+    tavily_api_key: &'a str,
+}
+
 fn run_with_context(
-    args: &Args,
-    cfg: &crate::config::Config,
-    theme: &RenderTheme,
-    registry: &crate::model_registry::ModelRegistry,
-    prompt_registry: &crate::llm::prompts::PromptRegistry,
-    question_set: Option<&Vec<String>>,
-    tavily_api_key: &str,
+    ctx: RunContext<'_>,
+    prompt_registry: &mut crate::llm::prompts::PromptRegistry,
+    startup_notice: Option<String>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let mut state = init_state(
-        args,
-        cfg,
-        registry,
+        ctx.args,
+        ctx.cfg,
+        ctx.registry,
         prompt_registry,
-        question_set,
-        tavily_api_key,
+        ctx.question_set,
+        ctx.tavily_api_key,
     )?;
+    if let Some(text) = startup_notice
+        && let Some(tab) = state.tabs.get_mut(state.active_tab)
+    {
+        crate::ui::notice::push_long_notice(&mut tab.app, text);
+    }
     let channels = init_and_spawn_preheat();
     prepare_requests(
-        question_set,
+        ctx.question_set,
         &mut state,
-        registry,
+        ctx.registry,
         prompt_registry,
-        args,
+        ctx.args,
         &channels,
     );
     run_ui_loop(
         &mut state,
         &channels,
-        registry,
+        ctx.registry,
         prompt_registry,
-        args,
-        theme,
+        ctx.args,
+        ctx.theme,
     )?;
     finalize_session(&mut state)?;
     Ok(())
@@ -160,11 +199,12 @@ fn run_ui_loop(
     state: &mut RunState,
     channels: &Channels,
     registry: &crate::model_registry::ModelRegistry,
-    prompt_registry: &crate::llm::prompts::PromptRegistry,
+    prompt_registry: &mut crate::llm::prompts::PromptRegistry,
     args: &Args,
     theme: &RenderTheme,
 ) -> Result<(), Box<dyn std::error::Error>> {
     ensure_tty_ready()?;
+    install_panic_hook();
     let mut terminal = setup_terminal()?;
     run_loop_with_terminal(
         &mut terminal,
@@ -184,7 +224,7 @@ fn run_loop_with_terminal(
     state: &mut RunState,
     channels: &Channels,
     registry: &crate::model_registry::ModelRegistry,
-    prompt_registry: &crate::llm::prompts::PromptRegistry,
+    prompt_registry: &mut crate::llm::prompts::PromptRegistry,
     args: &Args,
     theme: &RenderTheme,
 ) -> Result<(), Box<dyn std::error::Error>> {
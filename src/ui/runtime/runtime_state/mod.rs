@@ -86,6 +86,11 @@ fn load_run_state(
         session_location: Some(loaded.location.clone()),
     };
     apply_tab_config(&mut state.tabs, cfg, tavily_api_key, &state.log_session_id);
+    if let Some(notice) = loaded.notice
+        && let Some(tab) = state.tabs.get_mut(state.active_tab)
+    {
+        crate::ui::notice::push_long_notice(&mut tab.app, notice);
+    }
     Ok(state)
 }
 
@@ -238,6 +243,7 @@ pub(crate) fn sync_active_category(state: &mut RunState) {
 }
 
 pub(crate) fn finalize_session(state: &mut RunState) -> Result<(), Box<dyn std::error::Error>> {
+    crate::recovery::clear_snapshot();
     for tab in &state.tabs {
         let _ = crate::conversation::save_conversation(
             &crate::ui::runtime_helpers::tab_to_conversation(tab),
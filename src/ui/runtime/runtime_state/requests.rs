@@ -58,7 +58,7 @@ fn start_tab_request_for_question(
     };
     let model = model_for_tab(tab_state, registry);
     let flags = request_flags(args);
-    let params = build_request_params(tab_state, question, model, args, tx, flags);
+    let params = build_request_params(tab_state, question, model, tx, flags);
     start_tab_request(params);
 }
 
@@ -78,6 +78,7 @@ struct RequestFlags {
     enable_read_code: bool,
     enable_modify_file: bool,
     enable_ask_questions: bool,
+    enable_delegate_task: bool,
     log_requests: Option<String>,
 }
 
@@ -89,6 +90,7 @@ fn request_flags(args: &Args) -> RequestFlags {
         enable_read_code: args.read_code_enabled(),
         enable_modify_file: args.modify_file_enabled(),
         enable_ask_questions: args.ask_questions_enabled(),
+        enable_delegate_task: args.delegate_task_enabled(),
         log_requests: args.log_requests.clone(),
     }
 }
@@ -97,7 +99,6 @@ fn build_request_params<'a>(
     tab_state: &'a mut TabState,
     question: &'a str,
     model: &'a crate::model_registry::ModelProfile,
-    args: &'a Args,
     tx: &'a mpsc::Sender<RuntimeEvent>,
     flags: RequestFlags,
 ) -> crate::services::runtime_requests::StartTabRequestParams<'a> {
@@ -109,7 +110,9 @@ fn build_request_params<'a>(
         api_key: &model.api_key,
         model: &model.model,
         max_tokens: model.max_tokens,
-        show_reasoning: args.show_reasoning,
+        context_window: model.context_window,
+        reasoning: model.reasoning,
+        timeouts: model.timeouts,
         tx,
         enable_web_search: flags.enable_web_search,
         enable_code_exec: flags.enable_code_exec,
@@ -117,6 +120,7 @@ fn build_request_params<'a>(
         enable_read_code: flags.enable_read_code,
         enable_modify_file: flags.enable_modify_file,
         enable_ask_questions: flags.enable_ask_questions,
+        enable_delegate_task: flags.enable_delegate_task,
         log_requests: flags.log_requests,
         log_session_id,
     }
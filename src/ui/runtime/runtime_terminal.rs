@@ -1,3 +1,4 @@
+use crossterm::cursor::Show;
 use crossterm::event::{
     DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
 };
@@ -47,6 +48,51 @@ pub(crate) fn teardown_terminal(
     Ok(())
 }
 
+/// panic 时先恢复终端，再把打开的对话写入恢复文件。只处理界面线程：
+/// 后台线程 panic 不会让程序退出，此时不能把终端切回普通模式。
+pub(crate) fn install_panic_hook() {
+    let ui_thread = std::thread::current().id();
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if std::thread::current().id() == ui_thread {
+            restore_terminal_after_panic();
+            if let Some(path) = crate::recovery::dump_snapshot() {
+                eprintln!(
+                    "deepchat 异常退出，打开的对话已保存到 {}，下次启动时可恢复。",
+                    path.display()
+                );
+            }
+        }
+        previous(info);
+    }));
+}
+
+fn restore_terminal_after_panic() {
+    let _ = disable_raw_mode();
+    let _ = execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        DisableBracketedPaste,
+        Show
+    );
+}
+
+/// 外部程序（如 `$EDITOR`）退出后恢复界面。
+pub(crate) fn resume_terminal(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    enable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )?;
+    terminal.clear()?;
+    Ok(())
+}
+
 #[cfg(unix)]
 fn ensure_controlling_tty() -> Result<(), Box<dyn std::error::Error>> {
     if std::fs::File::open("/dev/tty").is_err() {
@@ -1,5 +1,6 @@
 mod driver;
 mod event_wait;
+mod prompt_ops;
 mod snapshot;
 
 use crate::args::Args;
@@ -23,7 +24,7 @@ pub(crate) struct RunLoopParams<'a> {
     pub tx: &'a mpsc::Sender<RuntimeEvent>,
     pub preheat_tx: &'a mpsc::Sender<PreheatTask>,
     pub registry: &'a crate::model_registry::ModelRegistry,
-    pub prompt_registry: &'a crate::llm::prompts::PromptRegistry,
+    pub prompt_registry: &'a mut crate::llm::prompts::PromptRegistry,
     pub args: &'a Args,
     pub theme: &'a RenderTheme,
     pub start_time: Instant,
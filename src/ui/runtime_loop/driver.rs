@@ -1,7 +1,7 @@
 use super::{RenderSnapshot, RunLoopParams, should_stop_after_iteration};
 use crate::ui::events::EventBatch;
 use crate::ui::runtime_helpers::PreheatResult;
-use crate::ui::runtime_loop::{event_wait, snapshot};
+use crate::ui::runtime_loop::{event_wait, prompt_ops, snapshot};
 use crate::ui::runtime_view::ViewState;
 use crate::framework::widget_system::WidgetSystem;
 
@@ -58,6 +58,7 @@ impl LoopState {
         if dispatch_inputs(params, self)? {
             return Ok(true);
         }
+        prompt_ops::apply_pending_prompt_op(params)?;
         apply_preheat_results(&mut self.events.preheat, params.tabs);
         if !should_render(&outcome, params, self) {
             self.events.clear();
@@ -102,7 +103,9 @@ fn should_render(
     if event_wait::preheat_touches_active_tab(&state.events.preheat, *params.active_tab) {
         return true;
     }
-    !state.events.llm.is_empty() || !state.events.terminal.is_empty()
+    !state.events.llm.is_empty()
+        || !state.events.terminal.is_empty()
+        || !state.events.titles.is_empty()
 }
 
 fn dispatch_input_events(
@@ -0,0 +1,88 @@
+use super::RunLoopParams;
+use crate::services::runtime_prompts::{apply_prompt_op, finish_prompt_op};
+use crate::ui::input_thread::set_input_paused;
+use crate::ui::runtime::runtime_terminal::{resume_terminal, teardown_terminal};
+use crate::ui::state::PromptOp;
+use ratatui::Terminal;
+use ratatui::backend::CrosstermBackend;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+/// 输入线程单次轮询的时长；暂停后等它结束，避免吞掉编辑器的第一个按键。
+const INPUT_POLL_GRACE: Duration = Duration::from_millis(60);
+
+/// 执行当前标签页记录的提示词操作；需要重新加载的列表在这里统一替换。
+pub(super) fn apply_pending_prompt_op(
+    params: &mut RunLoopParams<'_>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let active_tab = *params.active_tab;
+    let Some(op) = params
+        .tabs
+        .get_mut(active_tab)
+        .and_then(|tab| tab.app.pending_prompt_op.take())
+    else {
+        return Ok(());
+    };
+    let PromptOp::EditExternal { key } = &op else {
+        apply_prompt_op(op, params.prompt_registry, params.tabs, active_tab);
+        return Ok(());
+    };
+    let result = edit_in_external_editor(params, key)?;
+    finish_prompt_op(result, &op, params.prompt_registry, params.tabs, active_tab);
+    Ok(())
+}
+
+fn edit_in_external_editor(
+    params: &mut RunLoopParams<'_>,
+    key: &str,
+) -> Result<Result<String, String>, Box<dyn std::error::Error>> {
+    let Some(editor) = editor_command() else {
+        return Ok(Err(
+            "未设置 $VISUAL 或 $EDITOR，可在提示词列表中按 e 使用内置编辑器".to_string(),
+        ));
+    };
+    let path = match params.prompt_registry.ensure_file(key) {
+        Ok(path) => path,
+        Err(err) => return Ok(Err(err)),
+    };
+    set_input_paused(true);
+    std::thread::sleep(INPUT_POLL_GRACE);
+    let outcome = suspend_for_editor(params.terminal, &editor, &path);
+    set_input_paused(false);
+    Ok(outcome?.map(|_| format!("已编辑提示词：{key}")))
+}
+
+fn suspend_for_editor(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    editor: &str,
+    path: &Path,
+) -> Result<Result<(), String>, Box<dyn std::error::Error>> {
+    teardown_terminal(terminal)?;
+    let status = run_editor(editor, path);
+    resume_terminal(terminal)?;
+    Ok(status)
+}
+
+fn editor_command() -> Option<String> {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.trim().is_empty())
+}
+
+/// 编辑器命令可带参数（如 `code --wait`），按空白拆分后把文件路径追加在末尾。
+fn run_editor(editor: &str, path: &Path) -> Result<(), String> {
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or_default();
+    let status = Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .map_err(|e| format!("启动编辑器失败：{program}：{e}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("编辑器异常退出：{status}"))
+    }
+}
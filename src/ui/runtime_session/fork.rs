@@ -22,6 +22,10 @@ pub(crate) fn fork_last_tab_for_retry(
     let mut new_tab = create_retry_tab(source, &model_key, &prompt_key)?;
     insert_system_prompt(&mut history, &system_prompt);
     apply_retry_history(&mut new_tab, source, history, model_key, prompt_key);
+    new_tab.app.fork_of = Some(crate::conversation::ForkOrigin {
+        parent_id: source.conversation_id.clone(),
+        message_index: msg_idx,
+    });
     tabs.push(new_tab);
     *active_tab = tabs.len().saturating_sub(1);
     Some((*active_tab, content))
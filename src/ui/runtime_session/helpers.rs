@@ -24,11 +24,17 @@ pub(crate) fn load_tabs(
     prompt_registry: &crate::llm::prompts::PromptRegistry,
     args: &Args,
     categories: &mut Vec<String>,
+    notices: &mut Vec<String>,
 ) -> Result<Vec<TabState>, Box<dyn std::error::Error>> {
     let mut tabs = Vec::new();
     for conv_id in &session.open_conversations {
-        let conv = crate::conversation::load_conversation(conv_id)
-            .map_err(|e| format!("无法读取对话 {conv_id}: {e}"))?;
+        let conv = match crate::conversation::load_conversation(conv_id) {
+            Ok(conv) => conv,
+            Err(e) => {
+                notices.push(format!("无法读取对话 {conv_id}：{e}"));
+                continue;
+            }
+        };
         let category = normalize_category(&conv.category, categories);
         let model_key = resolve_conv_model(&conv, registry);
         let prompt_key = resolve_conv_prompt(&conv, prompt_registry);
@@ -172,6 +178,7 @@ pub(crate) fn insert_system_prompt(history: &mut Vec<crate::types::Message>, sys
             content: system_prompt.to_string(),
             tool_call_id: None,
             tool_calls: None,
+            attachments: Vec::new(),
         },
     );
 }
@@ -219,6 +226,16 @@ fn apply_conversation_state(
 ) {
     state.app.messages = conv.messages.clone();
     state.app.code_exec_container_id = conv.code_exec_container_id.clone();
+    state.app.archived_messages = conv.archived_messages.clone();
+    state.app.agent_limits = conv.agent_limits;
+    state.app.tool_flags = conv.tool_flags;
+    state.app.title = conv.title.clone();
+    state.app.answer_variants = conv.answer_variants.clone();
+    state.app.fork_of = conv.fork_of.clone();
+    for record in &conv.usage {
+        crate::framework::widget_system::runtime::logic::add_usage_totals(&mut state.app, record);
+    }
+    state.app.usage_log = conv.usage.clone();
     ensure_system_prompt(state, prompt_key, prompt_registry, args);
     state.app.follow = false;
     state.app.scroll = u16::MAX;
@@ -31,10 +31,7 @@ fn spawn_preheat_worker(
     tx: mpsc::Sender<RuntimeEvent>,
 ) {
     std::thread::spawn(move || {
-        loop {
-            let Some(task) = recv_task(&preheat_rx) else {
-                break;
-            };
+        while let Some(task) = recv_task(&preheat_rx) {
             let entry = crate::render::build_cache_entry(
                 &task.msg,
                 task.width,
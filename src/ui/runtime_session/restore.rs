@@ -1,4 +1,5 @@
 use crate::args::Args;
+use crate::ui::notice::push_long_notice;
 use crate::ui::runtime_helpers::TabState;
 
 use super::helpers::{
@@ -15,7 +16,15 @@ pub(crate) fn restore_tabs_from_session(
     args: &Args,
 ) -> RestoreTabsResult {
     let (mut categories, active_category_name) = init_categories(session);
-    let mut tabs = load_tabs(session, registry, prompt_registry, args, &mut categories)?;
+    let mut notices = Vec::new();
+    let mut tabs = load_tabs(
+        session,
+        registry,
+        prompt_registry,
+        args,
+        &mut categories,
+        &mut notices,
+    )?;
     ensure_default_tab(
         &mut tabs,
         &active_category_name,
@@ -24,6 +33,12 @@ pub(crate) fn restore_tabs_from_session(
         args,
     )?;
     let active_tab = resolve_active_tab(session, &tabs);
+    // 读不出的对话跳过，不让一个坏文件挡住整个会话
+    if !notices.is_empty()
+        && let Some(tab) = tabs.get_mut(active_tab)
+    {
+        push_long_notice(&mut tab.app, notices.join("；"));
+    }
     let active_category =
         resolve_active_category(&active_category_name, &categories, &tabs, active_tab);
     Ok((tabs, active_tab, categories, active_category))
@@ -0,0 +1,212 @@
+//! 启动 deepchat-mock，经由客户端的 rig 请求路径跑完脚本化的对话。
+
+use futures::StreamExt;
+use rig::completion::{CompletionModel, GetTokenUsage};
+use rig::streaming::StreamedAssistantContent;
+use rust_tui::llm::rig::{
+    CompletionModelChoice, RigRequestContext, build_completion_request, completion_model_for,
+    prepare_rig_context,
+};
+use rust_tui::types::{Message, ROLE_ASSISTANT, ROLE_TOOL, ROLE_USER, ToolCall, ToolFunctionCall};
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+
+const SCRIPT: &str = r#"{
+  "responses": [
+    {
+      "match": "你好",
+      "reasoning": ["先想一想"],
+      "text": ["你好！", "我是 mock。"],
+      "usage": { "prompt_tokens": 120, "completion_tokens": 24, "cached_tokens": 64 }
+    },
+    {
+      "match": "搜索",
+      "tool_calls": [{ "id": "call_1", "name": "web_search", "arguments": { "query": "deepchat" } }]
+    },
+    { "text": ["根据搜索结果，", "这是离线服务。"] },
+    { "match": "限流", "error": { "status": 429, "message": "rate limit exceeded", "retry_after": 7 } }
+  ]
+}"#;
+
+const PROMPTS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config/prompts");
+
+/// 每个测试各起一个 mock，端口由系统分配；结束时结束进程并删除脚本。
+struct MockServer {
+    child: Child,
+    script: PathBuf,
+    base_url: String,
+}
+
+impl MockServer {
+    fn start(name: &str) -> Self {
+        let script =
+            std::env::temp_dir().join(format!("deepchat-mock-{}-{name}.json", std::process::id()));
+        std::fs::write(&script, SCRIPT).expect("write script");
+        let mut child = Command::new(env!("CARGO_BIN_EXE_deepchat-mock"))
+            .arg(&script)
+            .args(["--port", "0"])
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("spawn deepchat-mock");
+        let mut stderr = BufReader::new(child.stderr.take().expect("stderr"));
+        let mut banner = String::new();
+        stderr.read_line(&mut banner).expect("read banner");
+        // mock 每个请求都会写日志，持续读走以免管道写满或关闭
+        std::thread::spawn(move || for _ in stderr.lines() {});
+        let base_url = banner
+            .split("http://")
+            .nth(1)
+            .and_then(|rest| rest.split('（').next())
+            .map(|addr| format!("http://{addr}"))
+            .unwrap_or_else(|| panic!("unexpected banner: {banner}"));
+        Self {
+            child,
+            script,
+            base_url,
+        }
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = std::fs::remove_file(&self.script);
+    }
+}
+
+fn message(role: &str, content: &str) -> Message {
+    Message {
+        role: role.to_string(),
+        content: content.to_string(),
+        tool_call_id: None,
+        tool_calls: None,
+        attachments: Vec::new(),
+    }
+}
+
+fn context(messages: &[Message]) -> RigRequestContext {
+    prepare_rig_context(messages, PROMPTS_DIR, &[], None, false)
+        .expect("rig context")
+        .0
+}
+
+/// 流式回复中收集到的内容。
+#[derive(Default)]
+struct Reply {
+    text: String,
+    reasoning: String,
+    tool_calls: Vec<(String, serde_json::Value)>,
+    input_tokens: Option<u64>,
+    raw_usage: serde_json::Value,
+}
+
+async fn stream_reply<M: CompletionModel>(
+    model: M,
+    ctx: &RigRequestContext,
+) -> Result<Reply, String> {
+    let mut stream = build_completion_request(&model, ctx)
+        .max_tokens(1024)
+        .stream()
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut reply = Reply::default();
+    while let Some(item) = stream.next().await {
+        match item.map_err(|e| e.to_string())? {
+            StreamedAssistantContent::Text(text) => reply.text.push_str(&text.text),
+            StreamedAssistantContent::ReasoningDelta { reasoning, .. } => {
+                reply.reasoning.push_str(&reasoning)
+            }
+            StreamedAssistantContent::Reasoning(reasoning) => {
+                reply.reasoning.push_str(&reasoning.reasoning.join(""))
+            }
+            StreamedAssistantContent::ToolCall(call) => reply
+                .tool_calls
+                .push((call.function.name, call.function.arguments)),
+            StreamedAssistantContent::Final(res) => {
+                reply.input_tokens = res.token_usage().map(|u| u.input_tokens);
+                reply.raw_usage = serde_json::to_value(&res).expect("usage json")["usage"].clone();
+            }
+            _ => {}
+        }
+    }
+    Ok(reply)
+}
+
+async fn send(server: &MockServer, model: &str, messages: &[Message]) -> Result<Reply, String> {
+    let ctx = context(messages);
+    match completion_model_for(&server.base_url, "test-key", model)? {
+        CompletionModelChoice::OpenAi(model) => stream_reply(model, &ctx).await,
+        CompletionModelChoice::DeepSeek(model) => stream_reply(model, &ctx).await,
+        CompletionModelChoice::Anthropic(model) => stream_reply(model, &ctx).await,
+    }
+}
+
+#[tokio::test]
+async fn deepseek_stream_replays_text_reasoning_and_cached_usage() {
+    let server = MockServer::start("deepseek");
+    let reply = send(&server, "deepseek-chat", &[message(ROLE_USER, "你好")])
+        .await
+        .expect("reply");
+    assert_eq!(reply.text, "你好！我是 mock。");
+    assert_eq!(reply.reasoning, "先想一想");
+    assert_eq!(reply.input_tokens, Some(120));
+    assert_eq!(reply.raw_usage["prompt_cache_hit_tokens"], 64);
+}
+
+#[tokio::test]
+async fn anthropic_tool_call_round_trip() {
+    let server = MockServer::start("anthropic");
+    let mut messages = vec![message(ROLE_USER, "帮我搜索 deepchat")];
+    let reply = send(&server, "claude-mock", &messages)
+        .await
+        .expect("tool call");
+    assert_eq!(
+        reply.tool_calls,
+        vec![(
+            "web_search".to_string(),
+            serde_json::json!({ "query": "deepchat" })
+        )]
+    );
+
+    let mut assistant = message(ROLE_ASSISTANT, "");
+    assistant.tool_calls = Some(vec![ToolCall {
+        id: "call_1".to_string(),
+        kind: "function".to_string(),
+        function: ToolFunctionCall {
+            name: "web_search".to_string(),
+            arguments: r#"{"query":"deepchat"}"#.to_string(),
+        },
+    }]);
+    let mut result = message(ROLE_TOOL, r#"{"results":[]}"#);
+    result.tool_call_id = Some("call_1".to_string());
+    messages.extend([assistant, result]);
+    let reply = send(&server, "claude-mock", &messages)
+        .await
+        .expect("followup");
+    assert_eq!(reply.text, "根据搜索结果，这是离线服务。");
+    assert!(reply.tool_calls.is_empty());
+}
+
+#[tokio::test]
+async fn scripted_error_status_reaches_the_client() {
+    let server = MockServer::start("error");
+    let err = send(&server, "gpt-mock", &[message(ROLE_USER, "触发限流")])
+        .await
+        .err()
+        .expect("error");
+    assert!(err.contains("rate limit exceeded"), "{err}");
+}
+
+#[tokio::test]
+async fn retry_after_header_reaches_the_error() {
+    let server = MockServer::start("retry-after");
+    for model in ["deepseek-chat", "claude-mock"] {
+        let err = send(&server, model, &[message(ROLE_USER, "触发限流")])
+            .await
+            .err()
+            .expect("error");
+        assert!(err.contains("retry-after: 7s"), "{model}: {err}");
+    }
+}